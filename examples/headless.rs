@@ -0,0 +1,41 @@
+//! Types a few rounds without a terminal and prints the session, like a
+//! bot or a test of another frontend would.
+//!
+//! ```sh
+//! cargo run --example headless
+//! ```
+
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+use metyping::App;
+use ratatui::crossterm::event::KeyCode;
+
+fn main() -> Result<()> {
+    let mut app = App::default()
+        .with_seed(7)
+        .with_text("a headless typist never looks at the keyboard");
+    app.new_session()?;
+
+    let mut now = Instant::now();
+    for round in 0..3 {
+        // a miss in the second round, fixed right away
+        let text = app.target().to_string();
+        for (i, c) in text.chars().enumerate() {
+            now += Duration::from_millis(120);
+            if round == 1 && i == 3 {
+                app.handle_key_event_at(KeyCode::Char('#').into(), now)?;
+                now += Duration::from_millis(200);
+            }
+            app.handle_key_event_at(KeyCode::Char(c).into(), now)?;
+        }
+        app.tick(now)?;
+    }
+    app.handle_key_event_at(KeyCode::Enter.into(), now)?;
+
+    if let Some(entry) = app.session_entry() {
+        println!("{}", entry.summary());
+        println!("{}", serde_json::to_string_pretty(&entry)?);
+    }
+    Ok(())
+}
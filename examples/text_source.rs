@@ -0,0 +1,39 @@
+//! A text generator of your own: rounds of the most common English
+//! bigrams, typed by the app like any built-in generator.
+//!
+//! ```sh
+//! cargo run --example text_source
+//! ```
+
+use metyping::{App, GeneratorConfig, TextGenerator};
+use rand::seq::SliceRandom;
+
+const BIGRAMS: [&str; 10] = ["th", "he", "in", "er", "an", "re", "on", "at", "en", "nd"];
+
+/// `count` made up words of two to four common bigrams
+#[derive(Debug, Default)]
+struct Bigrams;
+
+impl TextGenerator for Bigrams {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        let words: Vec<String> = (0..config.count)
+            .map(|i| {
+                let pairs = 2 + i % 3;
+                (0..pairs)
+                    .filter_map(|_| BIGRAMS.choose(config.rng))
+                    .copied()
+                    .collect()
+            })
+            .collect();
+        words.join(" ")
+    }
+}
+
+fn main() -> color_eyre::Result<()> {
+    let mut app = App::default()
+        .with_seed(1)
+        .with_generator("bigrams", Bigrams);
+    app.new_session()?;
+    println!("first round: {}", app.target());
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! The typing test as a widget in the corner of another TUI, here a frame
+//! with a sidebar, drawn once into a buffer and printed.
+//!
+//! ```sh
+//! cargo run --example widget
+//! ```
+
+use metyping::App;
+use ratatui::{
+    backend::TestBackend,
+    layout::{Constraint, Direction, Layout},
+    widgets::{Block, Borders, Paragraph, Widget},
+    Terminal,
+};
+
+fn main() -> color_eyre::Result<()> {
+    let mut app = App::default().with_text("embedded in another app");
+    app.new_session()?;
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 26))?;
+    terminal.draw(|frame| {
+        let [sidebar, main] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(20), Constraint::Min(80)])
+            .areas(frame.size());
+        Paragraph::new("your app\n\n> practice\n  settings")
+            .block(Block::default().borders(Borders::ALL))
+            .render(sidebar, frame.buffer_mut());
+        (&app).render(main, frame.buffer_mut());
+    })?;
+
+    let buffer = terminal.backend().buffer();
+    for y in 0..buffer.area.height {
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect();
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! Tweened values of what moves on screen: the caret gliding to the next
+//! character, the text shaking on a miss and the charts of the results
+//! growing, the rainbow over a personal best. Their timing is all here, and with
+//! [`crate::theme::Accessibility::reduced_motion`] none of them starts.
+
+use std::{
+    collections::BTreeMap,
+    f64::consts::PI,
+    time::{Duration, Instant},
+};
+
+use crate::App;
+
+/// What can be animated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Anim {
+    /// index of the character the caret is at
+    Caret,
+    /// cells the text is moved to the side, from 1 down to 0
+    Shake,
+    /// share of the charts of the results shown, from 0 to 1
+    Reveal,
+    /// turns the rainbow over a personal best ran, see [`crate::color`]
+    Celebrate,
+}
+
+impl Anim {
+    fn duration(self) -> Duration {
+        match self {
+            Self::Caret => Duration::from_millis(60),
+            Self::Shake => Duration::from_millis(240),
+            Self::Reveal => Duration::from_millis(500),
+            Self::Celebrate => Duration::from_millis(2000),
+        }
+    }
+
+    fn easing(self) -> Easing {
+        match self {
+            Self::Caret | Self::Reveal => Easing::OutCubic,
+            Self::Shake => Easing::Wobble,
+            Self::Celebrate => Easing::Linear,
+        }
+    }
+}
+
+/// How a tween gets from the start to the end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// at the same speed all the way
+    Linear,
+    /// fast at first and slowing down
+    OutCubic,
+    /// swings around the end a few times, smaller every time
+    Wobble,
+}
+
+impl Easing {
+    /// the progress at `t` between 0 and 1
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::Wobble => 1.0 - (1.0 - t) * (t * 3.0 * PI).cos(),
+        }
+    }
+}
+
+/// A value going from `from` to `to`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween {
+    from: f64,
+    to: f64,
+    start: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Tween {
+    pub fn value(&self, now: Instant) -> f64 {
+        let t =
+            now.saturating_duration_since(self.start).as_secs_f64() / self.duration.as_secs_f64();
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    pub fn done(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+}
+
+/// The tweens running in the app
+#[derive(Debug, Default)]
+pub struct Animations {
+    tweens: BTreeMap<Anim, Tween>,
+}
+
+impl Animations {
+    /// tweens `anim` to `to`, from where it is if it's still running
+    pub fn start(&mut self, anim: Anim, from: f64, to: f64, now: Instant) {
+        let from = self.value(anim, now).unwrap_or(from);
+        self.tweens.insert(
+            anim,
+            Tween {
+                from,
+                to,
+                start: now,
+                duration: anim.duration(),
+                easing: anim.easing(),
+            },
+        );
+    }
+
+    /// the value of `anim` at `now`, `None` once it's done
+    pub fn value(&self, anim: Anim, now: Instant) -> Option<f64> {
+        self.tweens
+            .get(&anim)
+            .filter(|tween| !tween.done(now))
+            .map(|tween| tween.value(now))
+    }
+
+    /// the running tweens at `now` in steps of a hundredth, the screen
+    /// changes when they do
+    pub fn frame(&self, now: Instant) -> Vec<i64> {
+        self.tweens
+            .values()
+            .filter(|tween| !tween.done(now))
+            .map(|tween| (tween.value(now) * 100.0).round() as i64)
+            .collect()
+    }
+
+    pub fn stop(&mut self, anim: Anim) {
+        self.tweens.remove(&anim);
+    }
+
+    pub fn clear(&mut self) {
+        self.tweens.clear();
+    }
+}
+
+impl App<'_> {
+    /// starts `anim`, unless motion is reduced
+    pub(crate) fn animate(&mut self, anim: Anim, from: f64, to: f64, now: Instant) {
+        if self.config.accessibility.reduced_motion {
+            return;
+        }
+        self.animations.start(anim, from, to, now);
+    }
+
+    /// the value of `anim` at the time of the frame, `at_rest` when it isn't
+    /// running
+    pub(crate) fn animated(&self, anim: Anim, at_rest: f64) -> f64 {
+        self.animations
+            .value(anim, self.draw_time())
+            .unwrap_or(at_rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+
+    #[test]
+    fn tween_and_reduce_motion() {
+        let now = Instant::now();
+        let ms = |ms| now + Duration::from_millis(ms);
+        let mut animations = Animations::default();
+        animations.start(Anim::Reveal, 0.0, 1.0, now);
+        assert_eq!(animations.value(Anim::Reveal, now), Some(0.0));
+        assert_eq!(animations.value(Anim::Reveal, ms(250)), Some(0.875));
+        assert_eq!(animations.value(Anim::Reveal, ms(500)), None);
+        // a running tween goes on from where it is
+        animations.start(Anim::Caret, 0.0, 2.0, now);
+        animations.start(Anim::Caret, 1.0, 4.0, ms(30));
+        assert_eq!(animations.value(Anim::Caret, ms(30)), Some(1.75));
+        assert_eq!(Easing::Wobble.apply(2.0), 1.0);
+
+        let mut app = App::default().with_text("ab");
+        app.next_round().unwrap();
+        app.handle_key_event_at(KeyCode::Char('x').into(), now)
+            .unwrap();
+        assert!(!app.animations.frame(ms(100)).is_empty());
+        app.animations.clear();
+        app.config.accessibility.reduced_motion = true;
+        app.handle_key_event_at(KeyCode::Char('x').into(), now)
+            .unwrap();
+        app.handle_key_event_at(KeyCode::Char('a').into(), now)
+            .unwrap();
+        assert!(app.animations.frame(now).is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+//! The attract screen of an idle kiosk: a demo of typing and the best
+//! results of the day take turns, any key starts a session
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+
+use crate::{history::HistoryEntry, leaderboard::DateFilter, App};
+
+/// How long each page is shown
+const PAGE: Duration = Duration::from_secs(10);
+/// Time between two frames of the animation
+const STEP: Duration = Duration::from_millis(150);
+/// The text typed out by the demo
+const DEMO: &str = "the quick brown fox jumps over the lazy dog";
+/// Frames the finished demo text stays before it starts over
+const DEMO_PAUSE: usize = 12;
+/// Results of the day shown
+const TOP_TODAY: usize = 5;
+
+impl App<'_> {
+    /// shows the attract screen once the kiosk is idle long enough
+    pub(crate) fn tick_attract(&mut self, now: Instant) {
+        let Some(kiosk) = &mut self.kiosk else {
+            return;
+        };
+        kiosk.last_input.get_or_insert(now);
+        if kiosk.idle(now) {
+            kiosk.start_attract(now);
+            self.dirty = true;
+        }
+    }
+
+    /// the frame of the animation at `now`, `None` without attract screen
+    pub(crate) fn attract_frame(&self, now: Instant) -> Option<u64> {
+        let since = self.kiosk.as_ref()?.attract?;
+        let elapsed = now.saturating_duration_since(since);
+        Some((elapsed.as_millis() / STEP.as_millis()) as u64)
+    }
+
+    /// the fastest results of today in any mode
+    fn top_today(&self) -> Vec<&HistoryEntry> {
+        let now = Utc::now();
+        let mut top: Vec<&HistoryEntry> = self
+            .history
+            .entries()
+            .iter()
+            .filter(|e| DateFilter::Today.matches(e.timestamp, now, self.config.timezone))
+            .collect();
+        top.sort_by(|a, b| b.wpm.total_cmp(&a.wpm));
+        top.truncate(TOP_TODAY);
+        top
+    }
+
+    pub(crate) fn render_attract(&self, area: Rect, buf: &mut Buffer) {
+        let Some(frame) = self.attract_frame(self.draw_time()) else {
+            return;
+        };
+        let frames_per_page = (PAGE.as_millis() / STEP.as_millis()) as u64;
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(TOP_TODAY as u16 + 2),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        Clear.render(area, buf);
+
+        Line::styled(self.config.header.title.clone(), self.theme.title)
+            .centered()
+            .render(layout[1], buf);
+
+        let lines = if (frame / frames_per_page).is_multiple_of(2) {
+            self.demo_lines(frame)
+        } else {
+            self.top_today_lines()
+        };
+        Paragraph::new(lines).centered().render(layout[3], buf);
+
+        // blinks
+        if frame % 8 < 6 {
+            Line::styled("press any key to start", self.theme.value)
+                .centered()
+                .render(layout[4], buf);
+        }
+    }
+
+    /// the demo text typed out up to `frame`
+    fn demo_lines(&self, frame: u64) -> Vec<Line<'static>> {
+        let len = DEMO.chars().count();
+        let typed = (frame as usize % (len + DEMO_PAUSE)).min(len);
+        let (done, rest) = DEMO.split_at(typed);
+        vec![
+            Line::default(),
+            Line::from(vec![
+                Span::styled(done, self.theme.hit),
+                Span::styled(rest, self.theme.pending),
+            ]),
+        ]
+    }
+
+    /// the fastest results of today, ranked
+    fn top_today_lines(&self) -> Vec<Line<'static>> {
+        let top = self.top_today();
+        let mut lines = vec![Line::styled("best today", self.theme.title)];
+        if top.is_empty() {
+            lines.push(Line::raw("no results yet, be the first"));
+        }
+        for (i, entry) in top.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{}. ", i + 1)),
+                Span::styled(format!("{:.1} WPM", entry.wpm), self.theme.value),
+                Span::raw(format!(
+                    "  {:.1}%  {}",
+                    entry.accuracy() * 100.0,
+                    entry.mode
+                )),
+            ]));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::Screen;
+
+    #[test]
+    fn attract_when_idle_and_wake_up_on_a_key() {
+        let mut app = App::default().with_kiosk();
+        app.next_round().unwrap();
+        let now = Instant::now();
+        app.tick_attract(now);
+        let idle = Duration::from_secs(app.config.kiosk.idle_secs.into());
+        app.tick_attract(now + idle / 2);
+        assert_eq!(app.attract_frame(now + idle / 2), None);
+
+        app.tick_attract(now + idle);
+        assert_eq!(app.attract_frame(now + idle + STEP * 3), Some(3));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 24));
+        app.draw_time = Some(now + idle + PAGE);
+        (&app).render(buf.area, &mut buf);
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("no results yet, be the first"));
+
+        app.handle_key_event_at(KeyCode::Char('x').into(), now + idle * 2)
+            .unwrap();
+        assert_eq!(app.attract_frame(now + idle * 2), None);
+        assert_eq!(app.screen, Screen::Typing);
+        // the key only wakes the kiosk up
+        assert_eq!(app.round_start, None);
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::WrapErr, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    history::{History, HistoryEntry},
+    App,
+};
+
+/// Name of the file of the unlocked badges inside the data directory
+const BADGES_FILE: &str = "badges.json";
+
+/// What it takes to earn a badge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Goal {
+    /// a session at this speed
+    Wpm(f64),
+    /// this many sessions in a row at this accuracy
+    Streak { accuracy: f64, sessions: usize },
+    /// hours of typing in all sessions
+    Hours(f64),
+    /// number of sessions
+    Sessions(usize),
+}
+
+impl Goal {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Wpm(wpm) => format!("a session at {wpm} WPM"),
+            Self::Streak { accuracy, sessions } => format!(
+                "{sessions} sessions in a row at {}% accuracy",
+                accuracy * 100.0
+            ),
+            Self::Hours(1.0) => "an hour of typing".into(),
+            Self::Hours(hours) => format!("{hours} hours of typing"),
+            Self::Sessions(sessions) => format!("{sessions} sessions"),
+        }
+    }
+
+    /// how far `entries` got towards the goal, 1 once it's met
+    pub fn progress(&self, entries: &[HistoryEntry]) -> f64 {
+        let progress = match *self {
+            Self::Wpm(wpm) => entries.iter().map(|e| e.wpm).fold(0.0, f64::max) / wpm,
+            Self::Streak { accuracy, sessions } => {
+                let mut streak = 0;
+                let mut longest = 0;
+                for entry in entries {
+                    streak = if entry.accuracy() >= accuracy {
+                        streak + 1
+                    } else {
+                        0
+                    };
+                    longest = longest.max(streak);
+                }
+                longest as f64 / sessions as f64
+            }
+            Self::Hours(hours) => {
+                let ms: u64 = entries.iter().map(|e| e.duration_ms).sum();
+                ms as f64 / 3_600_000.0 / hours
+            }
+            Self::Sessions(sessions) => entries.len() as f64 / sessions as f64,
+        };
+        progress.min(1.0)
+    }
+}
+
+/// A milestone of the practice
+#[derive(Debug, PartialEq)]
+pub struct Badge {
+    /// key of the badge in the stored badges, never changes
+    pub id: &'static str,
+    pub name: &'static str,
+    pub goal: Goal,
+}
+
+/// Every badge there is, in the order they're shown
+pub static BADGES: [Badge; 11] = [
+    Badge {
+        id: "sessions-10",
+        name: "Warmed up",
+        goal: Goal::Sessions(10),
+    },
+    Badge {
+        id: "sessions-100",
+        name: "Regular",
+        goal: Goal::Sessions(100),
+    },
+    Badge {
+        id: "wpm-40",
+        name: "40 WPM",
+        goal: Goal::Wpm(40.0),
+    },
+    Badge {
+        id: "wpm-60",
+        name: "60 WPM",
+        goal: Goal::Wpm(60.0),
+    },
+    Badge {
+        id: "wpm-80",
+        name: "80 WPM",
+        goal: Goal::Wpm(80.0),
+    },
+    Badge {
+        id: "wpm-100",
+        name: "100 WPM",
+        goal: Goal::Wpm(100.0),
+    },
+    Badge {
+        id: "streak-95",
+        name: "Steady hands",
+        goal: Goal::Streak {
+            accuracy: 0.95,
+            sessions: 5,
+        },
+    },
+    Badge {
+        id: "streak-98",
+        name: "Sharpshooter",
+        goal: Goal::Streak {
+            accuracy: 0.98,
+            sessions: 10,
+        },
+    },
+    Badge {
+        id: "hours-1",
+        name: "First hour",
+        goal: Goal::Hours(1.0),
+    },
+    Badge {
+        id: "hours-10",
+        name: "Ten hours",
+        goal: Goal::Hours(10.0),
+    },
+    Badge {
+        id: "hours-100",
+        name: "Hundred hours",
+        goal: Goal::Hours(100.0),
+    },
+];
+
+/// When each badge was unlocked, stored per profile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Badges {
+    pub unlocked: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl Badges {
+    /// Default location of the badges of `profile`, `None` if the platform
+    /// has no data directory
+    pub fn path(profile: &str) -> Option<PathBuf> {
+        History::dir(profile).map(|dir| dir.join(BADGES_FILE))
+    }
+
+    /// Loads the badges stored at `path`, none unlocked if there is no file
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        serde_json::from_str(&raw).wrap_err_with(|| format!("parsing {} failed", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    /// unlocks the badges `entries` earned at `now`, returns the ones that
+    /// weren't unlocked before
+    pub fn update(&mut self, entries: &[HistoryEntry], now: DateTime<Utc>) -> Vec<&'static Badge> {
+        let earned: Vec<&'static Badge> = BADGES
+            .iter()
+            .filter(|badge| !self.unlocked.contains_key(badge.id))
+            .filter(|badge| badge.goal.progress(entries) >= 1.0)
+            .collect();
+        for badge in &earned {
+            self.unlocked.insert(badge.id.into(), now);
+        }
+        earned
+    }
+}
+
+impl App<'_> {
+    /// unlocks the badges earned with the last session and tells about them
+    pub(crate) fn record_badges(&mut self) -> Result<()> {
+        let unlocked = self.badges.update(self.history.entries(), Utc::now());
+        if unlocked.is_empty() {
+            return Ok(());
+        }
+        let names: Vec<&str> = unlocked.iter().map(|badge| badge.name).collect();
+        let plural = if names.len() == 1 { "" } else { "s" };
+        self.status = Some(format!("badge{plural} unlocked: {}", names.join(", ")));
+        match &self.badges_path {
+            Some(path) => self.badges.save(path).wrap_err("saving the badges failed"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{stats::SessionStats, Mode};
+
+    fn entry(wpm: f64, misses: usize) -> HistoryEntry {
+        HistoryEntry {
+            wpm,
+            chars: 100,
+            misses,
+            duration_ms: 60_000,
+            ..HistoryEntry::new(&SessionStats::default(), Mode::Words(10), Utc::now())
+        }
+    }
+
+    #[test]
+    fn unlock_each_badge_once() {
+        let now = Utc::now();
+        let mut badges = Badges::default();
+        let mut entries = vec![entry(45.0, 1); 4];
+        assert_eq!(badges.update(&entries, now), [&BADGES[2]]);
+
+        // a sloppy session breaks the streak
+        entries.push(entry(30.0, 10));
+        entries.push(entry(61.0, 0));
+        assert_eq!(badges.update(&entries, now), [&BADGES[3]]);
+        assert_eq!(BADGES[6].goal.progress(&entries), 0.8);
+
+        entries.extend(vec![entry(50.0, 2); 4]);
+        let names: Vec<&str> = badges
+            .update(&entries, now)
+            .iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, ["Warmed up", "Steady hands"]);
+        assert!(badges.update(&entries, now).is_empty());
+        assert_eq!(badges.unlocked.len(), 4);
+    }
+}
@@ -0,0 +1,112 @@
+//! ASCII art next to the summary of the results: a trophy for a personal
+//! best and a few encouraging lines otherwise.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Text,
+    widgets::{Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::App;
+
+const TROPHY: &str = include_str!("../assets/banners/trophy.txt");
+const KEEP_GOING: &str = include_str!("../assets/banners/keep-going.txt");
+const TROPHY_SMALL: &str = include_str!("../assets/banners/trophy-small.txt");
+const KEEP_GOING_SMALL: &str = include_str!("../assets/banners/keep-going-small.txt");
+
+/// Columns the summary keeps free of the banner
+const SUMMARY_WIDTH: u16 = 60;
+
+/// The art on the results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Banner {
+    /// no art
+    None,
+    /// a tall trophy and a mountain to climb
+    #[default]
+    Classic,
+    /// a few lines each
+    Small,
+}
+
+impl Banner {
+    /// the art of a session that set a personal best or not
+    pub fn art(self, personal_best: bool) -> Option<&'static str> {
+        match (self, personal_best) {
+            (Self::None, _) => None,
+            (Self::Classic, true) => Some(TROPHY),
+            (Self::Classic, false) => Some(KEEP_GOING),
+            (Self::Small, true) => Some(TROPHY_SMALL),
+            (Self::Small, false) => Some(KEEP_GOING_SMALL),
+        }
+    }
+}
+
+/// the columns `art` takes
+fn width(art: &str) -> u16 {
+    art.lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as u16
+}
+
+impl App<'_> {
+    /// draws the banner at the right of `area` if the summary still fits,
+    /// returns what's left of the area
+    pub(crate) fn render_banner(&self, area: Rect, buf: &mut Buffer) -> Rect {
+        let best = self.personal_best.is_some();
+        let Some(art) = self.config.banner.art(best) else {
+            return area;
+        };
+        let width = width(art) + 1;
+        if area.width < SUMMARY_WIDTH + width {
+            return area;
+        }
+        let style = if best {
+            self.theme.value
+        } else {
+            self.theme.title
+        };
+        let banner = Rect {
+            x: area.right() - width,
+            width,
+            ..area
+        };
+        Paragraph::new(Text::styled(art, style)).render(banner, buf);
+        Rect {
+            width: area.width - width,
+            ..area
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trophies_for_personal_bests() {
+        assert!(Banner::Classic
+            .art(true)
+            .unwrap()
+            .contains("personal best!"));
+        assert!(Banner::Small.art(false).unwrap().contains("keep"));
+        assert_eq!(Banner::None.art(true), None);
+        assert_eq!(width(TROPHY), 15);
+
+        let mut app = App::default();
+        let area = Rect::new(0, 0, 90, 15);
+        let mut buf = Buffer::empty(area);
+        assert_eq!(app.render_banner(area, &mut buf).width, 90 - 21);
+        app.personal_best = Some(40.0);
+        app.config.banner = Banner::Small;
+        assert_eq!(app.render_banner(area, &mut buf).width, 90 - 17);
+        assert_eq!(
+            app.render_banner(Rect::new(0, 0, 70, 15), &mut buf).width,
+            70
+        );
+    }
+}
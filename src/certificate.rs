@@ -0,0 +1,240 @@
+//! Certificates of supervised tests for classrooms and hiring screenings.
+//! A test is supervised when it was started with `--seed`, so the rounds
+//! can be typed again, and nothing flagged it. The certificate is signed
+//! with the `certificate.key` of the config, `metyping verify` checks it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{history::History, App};
+
+/// Name of the directory of the certificates inside the data directory
+const CERTIFICATES_DIR: &str = "certificates";
+/// The field holding the signature of the others
+const SIGNATURE: &str = "signature";
+
+/// How certificates are signed and written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CertificateConfig {
+    /// secret of the supervisor the signature is made with, certificates
+    /// only verify with the same key
+    pub key: String,
+    /// write HTML instead of plain text
+    pub html: bool,
+    /// directory the certificates are written to, the data directory of the
+    /// profile if empty
+    pub dir: String,
+}
+
+/// The signed result of a test
+#[derive(Debug, Clone, PartialEq)]
+pub struct Certificate {
+    /// the certified fields in order, as written
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Certificate {
+    /// the certificate of the last session of `app`, or why there is none
+    fn of(app: &App) -> Result<Self, String> {
+        let Some(seed) = app.rng.seed() else {
+            return Err("certificates need a test started with --seed".into());
+        };
+        if app.config.practice || app.guard.flag().is_some() {
+            return Err("no certificate for a practice or flagged test".into());
+        }
+        let Some(entry) = app.session_entry() else {
+            return Err("no test to certify yet".into());
+        };
+        Ok(Self {
+            fields: vec![
+                ("name", app.config.profile.clone()),
+                (
+                    "date",
+                    entry.timestamp.format("%Y-%m-%d %H:%M UTC").to_string(),
+                ),
+                ("mode", entry.mode.to_string()),
+                ("wpm", format!("{:.1}", entry.wpm)),
+                ("accuracy", format!("{:.1}%", entry.accuracy() * 100.0)),
+                ("seed", seed.to_string()),
+            ],
+        })
+    }
+
+    /// the SHA-256 of `key` and the fields, in hex
+    fn signature(&self, key: &str) -> String {
+        let mut hash = Sha256::new();
+        hash.update(key);
+        for (name, value) in &self.fields {
+            hash.update(format!("\n{name}: {value}"));
+        }
+        hash.finalize().iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// the certificate as plain text, signed with `key`
+    pub fn to_text(&self, key: &str) -> String {
+        let mut text = String::from("metyping typing test certificate\n\n");
+        for (name, value) in &self.fields {
+            text.push_str(&format!("{name}: {value}\n"));
+        }
+        text.push_str(&format!("\n{SIGNATURE}: {}\n", self.signature(key)));
+        text
+    }
+
+    /// the certificate as a HTML page, signed with `key`. Every field is on
+    /// a line of its own, so it verifies like the plain text.
+    pub fn to_html(&self, key: &str) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Typing test certificate</title></head>\n<body style=\"font-family: sans-serif; text-align: center\">\n<h1>Typing test certificate</h1>\n",
+        );
+        for (name, value) in &self.fields {
+            html.push_str(&format!("<p>{name}: <b>{}</b></p>\n", escape(value)));
+        }
+        html.push_str(&format!(
+            "<p><small>{SIGNATURE}: <code>{}</code></small></p>\n</body>\n</html>\n",
+            self.signature(key)
+        ));
+        html
+    }
+
+    /// reads the fields of a certificate in plain text or HTML and checks
+    /// its signature against `key`
+    pub fn verify(raw: &str, key: &str) -> Result<Self> {
+        let mut fields = vec![];
+        let mut signature = None;
+        for line in raw.lines().map(strip_tags) {
+            let Some((name, value)) = line.split_once(": ") else {
+                continue;
+            };
+            if name == SIGNATURE {
+                signature = Some(value.trim().to_string());
+            } else if let Some(name) = FIELDS.iter().find(|field| **field == name) {
+                fields.push((*name, value.trim().to_string()));
+            }
+        }
+        let certificate = Self { fields };
+        match signature {
+            None => Err(eyre!("there is no signature")),
+            Some(signature) if signature != certificate.signature(key) => Err(eyre!(
+                "the signature doesn't match, the certificate was changed or signed with another key"
+            )),
+            Some(_) => Ok(certificate),
+        }
+    }
+
+    /// the value of the field `name`
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The names of the certified fields
+const FIELDS: [&str; 6] = ["name", "date", "mode", "wpm", "accuracy", "seed"];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// `line` without HTML tags and escapes
+fn strip_tags(line: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// checks the certificate at `path` with the key of the config and prints
+/// what it certifies
+pub fn verify_file(path: &Path, key: &str) -> Result<()> {
+    let raw =
+        fs::read_to_string(path).wrap_err_with(|| format!("reading {} failed", path.display()))?;
+    let certificate = Certificate::verify(&raw, key)
+        .wrap_err_with(|| format!("{} isn't valid", path.display()))?;
+    let field = |name| certificate.field(name).unwrap_or("-");
+    println!(
+        "valid certificate: {} typed {} WPM at {} accuracy in {} on {}",
+        field("name"),
+        field("wpm"),
+        field("accuracy"),
+        field("mode"),
+        field("date")
+    );
+    Ok(())
+}
+
+impl App<'_> {
+    /// writes the certificate of the last session, returns a message for the
+    /// status line
+    pub(crate) fn write_certificate(&self) -> Result<String> {
+        let certificate = match Certificate::of(self) {
+            Ok(certificate) => certificate,
+            Err(reason) => return Ok(reason),
+        };
+        let config = &self.config.certificate;
+        let dir = match config.dir.as_str() {
+            "" => History::dir(&self.config.profile)
+                .map(|dir| dir.join(CERTIFICATES_DIR))
+                .ok_or_else(|| eyre!("there is no data directory"))?,
+            dir => PathBuf::from(dir),
+        };
+        let (raw, extension) = if config.html {
+            (certificate.to_html(&config.key), "html")
+        } else {
+            (certificate.to_text(&config.key), "txt")
+        };
+        let date = certificate.field("date").unwrap_or_default();
+        let file = format!("{}-{}", self.config.profile, date.replace([' ', ':'], "-"));
+        let path = dir.join(file).with_extension(extension);
+        fs::create_dir_all(&dir).wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        fs::write(&path, raw).wrap_err_with(|| format!("writing {} failed", path.display()))?;
+        Ok(format!("certificate written to {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_certificates() {
+        let mut app = App::default().with_seed(7);
+        assert_eq!(Certificate::of(&app).unwrap_err(), "no test to certify yet");
+        app.next_round().unwrap();
+        for c in app.target.clone().chars() {
+            app.handle_key_event(ratatui::crossterm::event::KeyCode::Char(c).into())
+                .unwrap();
+        }
+        let certificate = Certificate::of(&app).unwrap();
+        assert_eq!(certificate.field("seed"), Some("7"));
+
+        for raw in [certificate.to_text("secret"), certificate.to_html("secret")] {
+            assert_eq!(Certificate::verify(&raw, "secret").unwrap(), certificate);
+            assert!(Certificate::verify(&raw, "guess").is_err());
+            let forged = raw.replace("accuracy: ", "accuracy: 1");
+            assert!(Certificate::verify(&forged, "secret").is_err());
+        }
+    }
+}
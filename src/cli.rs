@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand as ClapSubcommand};
+use color_eyre::Result;
+use toml::{Table, Value};
+
+use crate::{keyboard::KeyboardLayout, Mode};
+
+/// Typing practice in the terminal
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about,
+    after_help = "Environment:\n  \
+        METYPING_MODE, METYPING_THEME, METYPING_LAYOUT  override the config, the options above override them\n  \
+        METYPING_DATA_DIR  keep the history and the progress in this directory"
+)]
+pub struct Cli {
+    /// seed the random number generator to get the same rounds every time
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// draw below the prompt instead of on the alternate screen, so the
+    /// results stay in the scrollback
+    #[arg(long)]
+    pub inline: bool,
+    /// practice the text on the clipboard, needs the `clipboard` feature
+    #[arg(long)]
+    pub clipboard: bool,
+    /// practice a random wikipedia article or one of the feed in the config,
+    /// needs the `net` feature
+    #[arg(long)]
+    pub online: bool,
+    /// print a summary of the last session when quitting
+    #[arg(long)]
+    pub summary: bool,
+    /// print the last session as JSON when quitting, for scripts
+    #[arg(long)]
+    pub json: bool,
+    /// keep the config and the history in the profile of this name, e.g.
+    /// one per user or keyboard layout
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// use this theme instead of the one in the config
+    #[arg(long)]
+    pub theme: Option<String>,
+    /// rate the texts for this keyboard layout instead of the one in the
+    /// config
+    #[arg(long, value_enum)]
+    pub layout: Option<KeyboardLayout>,
+    /// start in this mode instead of the one in the config, e.g. `words 25`
+    /// or `timed 60s`
+    #[arg(long)]
+    pub mode: Option<Mode>,
+    /// show the frame rate and the time to draw a frame, to check that the
+    /// app stays idle when nothing changes
+    #[arg(long)]
+    pub frame_stats: bool,
+    /// lock the app for a demo machine: nothing is saved, the settings
+    /// can't be changed and quitting takes Ctrl+Alt+Q and the
+    /// `kiosk.passphrase` of the config. Ctrl+U switches between the users
+    /// taking turns.
+    #[arg(long)]
+    pub kiosk: bool,
+    #[command(subcommand)]
+    pub command: Option<Subcommand>,
+}
+
+impl Cli {
+    /// the settings of the config given on the command line, they take
+    /// precedence over the files and aren't saved
+    pub fn overrides(&self) -> Result<Table> {
+        let mut table = Table::new();
+        if let Some(theme) = &self.theme {
+            table.insert("theme".into(), Value::try_from(theme)?);
+        }
+        if let Some(layout) = self.layout {
+            table.insert("layout".into(), Value::try_from(layout)?);
+        }
+        if let Some(mode) = self.mode {
+            table.insert("mode".into(), Value::try_from(mode)?);
+        }
+        Ok(table)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ClapSubcommand)]
+pub enum Subcommand {
+    /// open the trends of the history right away
+    Stats,
+    /// recompute the speeds and the consistency of every session from its
+    /// keystrokes, time and key latencies, in order of time, e.g. after
+    /// importing sessions
+    Recompute,
+    /// show the training plan of today
+    Plan {
+        #[command(subcommand)]
+        action: Option<PlanAction>,
+    },
+    /// show the courses and the drills passed
+    Course {
+        #[command(subcommand)]
+        action: Option<CourseAction>,
+    },
+    /// check the signature of a certificate with the `certificate.key` of
+    /// the config
+    Verify { path: PathBuf },
+    /// print the digest of this week in Markdown
+    Digest,
+    /// work with style files
+    Style {
+        #[command(subcommand)]
+        action: StyleAction,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, ClapSubcommand)]
+pub enum StyleAction {
+    /// check the style file for mistakes, with the line of each
+    Check { path: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq, ClapSubcommand)]
+pub enum CourseAction {
+    /// load the course pack in a TOML or JSON file into the profile
+    Load { path: PathBuf },
+    /// write the loaded course of this name to a TOML or JSON file, to
+    /// share it
+    Export { name: String, path: PathBuf },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ClapSubcommand)]
+pub enum PlanAction {
+    /// practice the items scheduled for today one after the other
+    Run,
+}
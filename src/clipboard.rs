@@ -0,0 +1,23 @@
+use color_eyre::{eyre::eyre, Result};
+
+/// The text on the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn read() -> Result<String> {
+    use color_eyre::eyre::WrapErr;
+
+    let text = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .wrap_err("reading the clipboard failed")?;
+    if text.trim().is_empty() {
+        return Err(eyre!("the clipboard holds no text"));
+    }
+    Ok(text)
+}
+
+/// The text on the system clipboard
+#[cfg(not(feature = "clipboard"))]
+pub fn read() -> Result<String> {
+    Err(eyre!(
+        "built without clipboard support, rebuild with `--features clipboard`"
+    ))
+}
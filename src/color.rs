@@ -0,0 +1,247 @@
+//! What colors the terminal can show and the effects made of them: typed
+//! text in a gradient or a rainbow and a rainbow celebrating a personal
+//! best. Every color goes through [`ColorDepth::color`], so the effects
+//! turn into the nearest colors a terminal has, or none at all.
+
+use std::env;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Span,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{anim::Anim, App, SpanType};
+
+/// Ends of the gradient of the typed text
+const GRADIENT: [(u8, u8, u8); 2] = [(0, 175, 255), (255, 95, 215)];
+/// The 16 colors of a basic terminal, without black and white, with what
+/// they usually look like
+const ANSI: [(Color, (u8, u8, u8)); 12] = [
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+];
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// `NO_COLOR` is set or the terminal is dumb
+    None,
+    #[default]
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// the depth of the terminal the app runs in
+    pub fn detect() -> Self {
+        Self::detect_from(|name| env::var(name).ok())
+    }
+
+    /// the depth told by the variables `var` looks up
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty()) || term == "dumb" {
+            return Self::None;
+        }
+        match var("COLORTERM").as_deref() {
+            Some("truecolor" | "24bit") => Self::TrueColor,
+            _ if term.contains("256color") => Self::Ansi256,
+            _ => Self::Ansi16,
+        }
+    }
+
+    /// the color closest to `rgb` the terminal has, `None` without colors
+    pub fn color(self, (r, g, b): (u8, u8, u8)) -> Option<Color> {
+        match self {
+            Self::None => None,
+            Self::Ansi16 => ANSI
+                .iter()
+                .min_by_key(|(_, (ar, ag, ab))| {
+                    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+                    d(r, *ar) + d(g, *ag) + d(b, *ab)
+                })
+                .map(|(color, _)| *color),
+            Self::Ansi256 => {
+                // the 6x6x6 cube of the 256 colors starts at 16
+                let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+                Some(Color::Indexed(
+                    (16 + 36 * level(r) + 6 * level(g) + level(b)) as u8,
+                ))
+            }
+            Self::TrueColor => Some(Color::Rgb(r, g, b)),
+        }
+    }
+}
+
+/// How the typed text is colored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextEffect {
+    /// the hits in the color of the theme
+    #[default]
+    None,
+    /// from blue to pink along the text
+    Gradient,
+    /// all the colors of the rainbow along the text
+    Rainbow,
+}
+
+/// Colorful effects, shown as far as the terminal can
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectsConfig {
+    /// the colors of the typed text
+    pub text: TextEffect,
+    /// a rainbow over a new personal best on the results
+    pub celebrate: bool,
+}
+
+impl Default for EffectsConfig {
+    fn default() -> Self {
+        Self {
+            text: TextEffect::None,
+            celebrate: true,
+        }
+    }
+}
+
+/// the color at `t` between 0 and 1 of the gradient between `from` and `to`
+fn gradient(from: (u8, u8, u8), to: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// the fully saturated color of `hue` in turns, 0 and 1 are red
+fn rainbow(hue: f64) -> (u8, u8, u8) {
+    let h = hue.rem_euclid(1.0) * 6.0;
+    let x = ((1.0 - (h % 2.0 - 1.0).abs()) * 255.0).round() as u8;
+    match h as u8 {
+        0 => (255, x, 0),
+        1 => (x, 255, 0),
+        2 => (0, 255, x),
+        3 => (0, x, 255),
+        4 => (x, 0, 255),
+        _ => (255, 0, x),
+    }
+}
+
+impl App<'_> {
+    /// colors the hits of the typed text in the cells from `area`, one per
+    /// character
+    pub(crate) fn render_text_effect(&self, area: Rect, buf: &mut Buffer) {
+        let effect = self.config.effects.text;
+        let length = self.target.chars().count().max(1) as f64;
+        let mut x = area.x;
+        for span in &self.spans {
+            for _ in span.span.content.chars() {
+                if x >= area.right() {
+                    return;
+                }
+                let t = f64::from(x - area.x) / length;
+                let rgb = match effect {
+                    TextEffect::None => return,
+                    TextEffect::Gradient => gradient(GRADIENT[0], GRADIENT[1], t),
+                    TextEffect::Rainbow => rainbow(t),
+                };
+                if let Some(color) = self.colors.color(rgb) {
+                    if span.span_type == SpanType::HIT {
+                        buf.get_mut(x, area.y).set_fg(color);
+                    }
+                }
+                x += 1;
+            }
+        }
+    }
+
+    /// `text` in a rainbow that runs along it while the celebration does,
+    /// in `fallback` without colors
+    pub(crate) fn celebrated(&self, text: &str, fallback: Style) -> Vec<Span<'static>> {
+        let length = text.chars().count().max(1) as f64;
+        let shift = self.animated(Anim::Celebrate, 0.0);
+        text.char_indices()
+            .enumerate()
+            .map(|(i, (at, c))| {
+                let cell = text[at..at + c.len_utf8()].to_string();
+                match self.colors.color(rainbow(i as f64 / length - shift)) {
+                    Some(color) => Span::styled(cell, fallback.fg(color)),
+                    None => Span::styled(cell, fallback),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+
+    #[test]
+    fn detect_and_degrade_colors() {
+        let detect = |vars: &[(&str, &str)]| {
+            ColorDepth::detect_from(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(detect(&[("COLORTERM", "truecolor")]), ColorDepth::TrueColor);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorDepth::Ansi256);
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]),
+            ColorDepth::None
+        );
+
+        let pink = GRADIENT[1];
+        assert_eq!(
+            ColorDepth::TrueColor.color(pink),
+            Some(Color::Rgb(255, 95, 215))
+        );
+        assert_eq!(ColorDepth::Ansi256.color(pink), Some(Color::Indexed(212)));
+        assert_eq!(ColorDepth::Ansi16.color(pink), Some(Color::LightMagenta));
+        assert_eq!(ColorDepth::None.color(pink), None);
+        assert_eq!(rainbow(0.5), (0, 255, 255));
+        assert_eq!(gradient((0, 0, 0), (255, 100, 10), 0.5), (128, 50, 5));
+    }
+
+    #[test]
+    fn celebrate_personal_bests() {
+        let now = Instant::now();
+        let mut app = App::default().with_text("abc");
+        let session = |app: &mut App, millis| {
+            app.new_session().unwrap();
+            for (i, c) in "abc".chars().enumerate() {
+                let at = now + Duration::from_millis(millis * i as u64);
+                app.handle_key_event_at(KeyCode::Char(c).into(), at)
+                    .unwrap();
+            }
+            app.finish_session(now).unwrap();
+        };
+        session(&mut app, 400);
+        assert_eq!(app.personal_best, None);
+        session(&mut app, 200);
+        let best = app.history.entries()[0].wpm;
+        assert_eq!(app.personal_best, Some(best));
+        assert!(app.animations.value(Anim::Celebrate, now).is_some());
+        session(&mut app, 300);
+        assert_eq!(app.personal_best, None);
+    }
+}
@@ -0,0 +1,413 @@
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+
+use crate::{
+    config::Strict,
+    form::{FieldEvent, TextInput},
+    history::{ExportFormat, History},
+    theme::Theme,
+    App, Mode, Screen,
+};
+
+/// Names of all commands, shown when a command is unknown
+const COMMANDS: &str =
+    "mode, theme, generator, practice, strict, export, macro, drill, plan, courses, profile, stats, badges, leaderboard, trends, history, settings, quit";
+
+/// A command of the `:` command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `:mode words 50`
+    Mode(Mode),
+    /// `:theme gruvbox`
+    Theme(String),
+    /// `:generator markov`
+    Generator(String),
+    /// `:practice on`
+    Practice(bool),
+    /// `:strict round`
+    Strict(Strict),
+    /// `:export csv [path]`, the path defaults to the data directory
+    Export(ExportFormat, Option<PathBuf>),
+    /// `:macro record`, `:macro save <name>`, ...
+    Macro(MacroCommand),
+    /// `:drill`, practice the weak spots of the history
+    Drill,
+    /// `:profile <name>` switches to the profile, without a name it opens
+    /// the list of profiles
+    Profile(Option<String>),
+    /// `:stats`, `:leaderboard`, `:trends`, `:history`, `:plan` and
+    /// `:settings`
+    Screen(Screen),
+    /// `:quit` or `:q`
+    Quit,
+}
+
+/// Recording and replaying sequences of commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum MacroCommand {
+    /// starts recording the following commands
+    Record,
+    /// stops recording and stores the commands under the name
+    Save(String),
+    Run(String),
+    Delete(String),
+    List,
+}
+
+fn on_off(arg: &str) -> Result<bool, String> {
+    match arg {
+        "on" | "true" => Ok(true),
+        "off" | "false" => Ok(false),
+        _ => Err(format!("expected on or off, got '{arg}'")),
+    }
+}
+
+/// Parses a command line without the leading `:`
+pub fn parse(line: &str) -> Result<Command, String> {
+    let line = line.trim();
+    let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+    let args = args.trim();
+    let command = match name {
+        "mode" => Command::Mode(args.parse()?),
+        "theme" => Command::Theme(args.into()),
+        "generator" => Command::Generator(args.into()),
+        "practice" => Command::Practice(on_off(args)?),
+        "strict" => Command::Strict(match args {
+            "off" => Strict::Off,
+            "round" => Strict::Round,
+            "session" => Strict::Session,
+            _ => return Err(format!("expected off, round or session, got '{args}'")),
+        }),
+        "export" => {
+            let (format, path) = args.split_once(' ').unwrap_or((args, ""));
+            let format = match format {
+                "csv" => ExportFormat::Csv,
+                "json" => ExportFormat::Json,
+                _ => return Err(format!("expected csv or json, got '{format}'")),
+            };
+            let path = Some(path.trim())
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from);
+            Command::Export(format, path)
+        }
+        "macro" => {
+            let (action, name) = args.split_once(' ').unwrap_or((args, ""));
+            let name = name.trim();
+            let named = |command: fn(String) -> MacroCommand| {
+                if name.is_empty() {
+                    Err(format!("macro {action} needs a name"))
+                } else {
+                    Ok(command(name.into()))
+                }
+            };
+            Command::Macro(match action {
+                "record" => MacroCommand::Record,
+                "save" => named(MacroCommand::Save)?,
+                "run" => named(MacroCommand::Run)?,
+                "delete" => named(MacroCommand::Delete)?,
+                "list" => MacroCommand::List,
+                _ => {
+                    return Err(format!(
+                        "expected record, save, run, delete or list, got '{action}'"
+                    ))
+                }
+            })
+        }
+        "drill" => Command::Drill,
+        "profile" => Command::Profile(Some(args).filter(|a| !a.is_empty()).map(String::from)),
+        "stats" => Command::Screen(Screen::Stats),
+        "badges" => Command::Screen(Screen::Badges),
+        "leaderboard" => Command::Screen(Screen::Leaderboard),
+        "trends" => Command::Screen(Screen::Trends),
+        "history" => Command::Screen(Screen::History),
+        "plan" => Command::Screen(Screen::Plan),
+        "courses" => Command::Screen(Screen::Courses),
+        "settings" => Command::Screen(Screen::Settings),
+        "quit" | "q" => Command::Quit,
+        _ => return Err(format!("unknown command '{name}', try: {COMMANDS}")),
+    };
+    Ok(command)
+}
+
+impl App<'_> {
+    pub(crate) fn open_command_line(&mut self) {
+        if self.kiosk_locks("commands") {
+            return;
+        }
+        self.command_line = Some(TextInput::default());
+    }
+
+    /// runs `command` and returns a message for the status line
+    fn run_command(&mut self, command: Command) -> Result<String> {
+        let message = match command {
+            Command::Mode(mode) => {
+                self.switch_mode(mode)?;
+                match self.confirm {
+                    Some(_) => String::new(),
+                    None => format!("mode: {mode}"),
+                }
+            }
+            Command::Theme(name) => match Theme::by_name(&name) {
+                Some(theme) => {
+                    self.use_theme(theme);
+                    self.config.theme = name;
+                    format!("theme: {}", self.config.theme)
+                }
+                None => format!(
+                    "unknown theme '{name}', available: {}",
+                    Theme::NAMES.join(", ")
+                ),
+            },
+            Command::Generator(name) => {
+                if self.generators.get(&name).is_none() {
+                    return Ok(format!(
+                        "unknown generator '{name}', available: {}",
+                        self.generators.names().join(", ")
+                    ));
+                }
+                self.config.generator = name;
+                self.new_session()?;
+                format!("generator: {}", self.config.generator)
+            }
+            Command::Practice(on) => {
+                self.config.practice = on;
+                format!("practice: {}", if on { "on" } else { "off" })
+            }
+            Command::Strict(strict) => {
+                self.config.strict = strict;
+                format!("strict: {strict:?}").to_lowercase()
+            }
+            Command::Export(format, path) => {
+                let Some(path) = path.or_else(|| {
+                    History::dir(&self.config.profile)
+                        .map(|dir| dir.join(format!("history.{}", format.extension())))
+                }) else {
+                    return Ok("no data directory, give a path to export to".into());
+                };
+                let file = File::create(&path)
+                    .wrap_err_with(|| format!("creating {} failed", path.display()))?;
+                self.history.export(format, BufWriter::new(file))?;
+                format!(
+                    "exported {} sessions to {}",
+                    self.history.entries().len(),
+                    path.display()
+                )
+            }
+            Command::Macro(command) => self.run_macro_command(command)?,
+            Command::Drill => self.start_drill()?,
+            Command::Profile(Some(name)) => self.switch_profile(&name)?,
+            Command::Profile(None) => {
+                self.open_profiles();
+                String::new()
+            }
+            Command::Screen(Screen::Settings) => {
+                self.open_settings();
+                String::new()
+            }
+            Command::Screen(screen) => {
+                self.push_screen(screen);
+                String::new()
+            }
+            Command::Quit => {
+                self.exit();
+                String::new()
+            }
+        };
+        Ok(message)
+    }
+
+    fn run_macro_command(&mut self, command: MacroCommand) -> Result<String> {
+        let message = match command {
+            MacroCommand::Record => {
+                self.recording = Some(vec![]);
+                "recording, finish with :macro save <name>".into()
+            }
+            MacroCommand::Save(name) => {
+                let Some(lines) = self.recording.take() else {
+                    return Ok("not recording, start with :macro record".into());
+                };
+                let count = lines.len();
+                self.config.macros.insert(name.clone(), lines);
+                self.save_config()?;
+                format!("saved {count} commands as '{name}'")
+            }
+            MacroCommand::Run(name) => {
+                let Some(lines) = self.config.macros.get(&name).cloned() else {
+                    return Ok(format!("no macro named '{name}'"));
+                };
+                for line in &lines {
+                    match parse(line) {
+                        Ok(Command::Macro(_)) => {
+                            return Ok(format!("'{name}': macros can't run macros"))
+                        }
+                        Ok(command) => {
+                            self.run_command(command)?;
+                        }
+                        Err(err) => return Ok(format!("'{name}': {err}")),
+                    }
+                }
+                format!("ran '{name}'")
+            }
+            MacroCommand::Delete(name) => {
+                if self.config.macros.remove(&name).is_none() {
+                    return Ok(format!("no macro named '{name}'"));
+                }
+                self.save_config()?;
+                format!("deleted '{name}'")
+            }
+            MacroCommand::List => {
+                let names: Vec<&str> = self.config.macros.keys().map(String::as_str).collect();
+                if names.is_empty() {
+                    "no macros".into()
+                } else {
+                    format!("macros: {}", names.join(", "))
+                }
+            }
+        };
+        Ok(message)
+    }
+
+    pub(crate) fn handle_command_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some(input) = &mut self.command_line else {
+            return Ok(());
+        };
+        match input.handle_key(key_event) {
+            FieldEvent::Submit => {
+                let line = input.value().to_string();
+                self.command_line = None;
+                let message = match parse(&line) {
+                    Ok(command) => {
+                        if let (Some(recording), false) =
+                            (&mut self.recording, matches!(command, Command::Macro(_)))
+                        {
+                            recording.push(line.trim().to_string());
+                        }
+                        self.run_command(command).unwrap_or_else(|err| {
+                            err.chain()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(": ")
+                        })
+                    }
+                    Err(err) => err,
+                };
+                self.status = Some(message).filter(|m| !m.is_empty());
+            }
+            FieldEvent::Cancel => self.command_line = None,
+            FieldEvent::Edited | FieldEvent::Ignored => {}
+        }
+        Ok(())
+    }
+
+    /// the command line or the last status message on the bottom row of
+    /// `area`
+    pub(crate) fn render_command_line(&self, area: Rect, buf: &mut Buffer) {
+        let line = match (&self.command_line, &self.status) {
+            (Some(input), _) => {
+                let mut spans = vec![Span::styled(":", self.theme.title)];
+                spans.extend(input.spans(self.theme.value));
+                Line::from(spans)
+            }
+            (None, Some(status)) => {
+                Line::from(Span::styled(format!(" {status}"), self.theme.title))
+            }
+            (None, None) => return,
+        };
+        let row = Rect::new(
+            area.x,
+            area.bottom().saturating_sub(1),
+            area.width,
+            1.min(area.height),
+        );
+        Clear.render(row, buf);
+        Paragraph::new(line).render(row, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::KeyCode;
+
+    #[test]
+    fn parse_and_run_commands() {
+        assert_eq!(parse("mode words 50"), Ok(Command::Mode(Mode::Words(50))));
+        assert_eq!(parse("mode timed 60s"), Ok(Command::Mode(Mode::Timed(60))));
+        assert_eq!(
+            parse("export csv /tmp/out.csv"),
+            Ok(Command::Export(
+                ExportFormat::Csv,
+                Some("/tmp/out.csv".into())
+            ))
+        );
+        assert!(parse("mode words 0").is_err());
+        assert!(parse("export xml").is_err());
+        assert!(parse("dance").unwrap_err().contains("try: mode"));
+
+        let mut app = App {
+            session: crate::SessionState::Finished,
+            screen: Screen::Results,
+            ..Default::default()
+        };
+        for key in ":theme gruvbox"
+            .chars()
+            .map(KeyCode::Char)
+            .chain([KeyCode::Enter])
+        {
+            app.handle_key_event(key.into()).unwrap();
+        }
+        assert_eq!(app.theme.name, "gruvbox");
+        assert_eq!(app.status.as_deref(), Some("theme: gruvbox"));
+        assert!(app.command_line.is_none());
+
+        // a command that fails only tells why
+        app.open_command_line();
+        for c in "export csv /metyping-missing/out.csv".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        let status = app.status.clone().unwrap();
+        assert!(status.starts_with("creating /metyping-missing/out.csv failed: "));
+        assert_eq!(app.screen, Screen::Results);
+        assert!(!app.exit);
+    }
+
+    #[test]
+    fn record_and_run_macros() {
+        let mut app = App {
+            session: crate::SessionState::Finished,
+            screen: Screen::Results,
+            ..Default::default()
+        };
+        let run = |app: &mut App, line: &str| {
+            app.open_command_line();
+            for c in line.chars() {
+                app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+            }
+            app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        };
+        run(&mut app, "macro record");
+        run(&mut app, "mode numbers 5");
+        run(&mut app, "strict session");
+        run(&mut app, "macro save drill");
+        assert_eq!(
+            app.config.macros["drill"],
+            vec!["mode numbers 5", "strict session"]
+        );
+
+        run(&mut app, "mode random");
+        run(&mut app, "strict off");
+        run(&mut app, "macro run drill");
+        assert_eq!(app.mode, Mode::Numbers(5));
+        assert_eq!(app.config.strict, Strict::Session);
+        assert_eq!(app.status.as_deref(), Some("ran 'drill'"));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    history::HistoryEntry,
+    stats::{CharClass, Tally},
+};
+
+/// Average time to press a key in two sessions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyDiff {
+    pub key: char,
+    pub before_ms: f64,
+    pub after_ms: f64,
+}
+
+impl KeyDiff {
+    /// negative if the key got faster
+    pub fn delta_ms(&self) -> f64 {
+        self.after_ms - self.before_ms
+    }
+}
+
+/// Keystrokes and misses of a class of characters in two sessions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassDiff {
+    pub class: CharClass,
+    pub before: Tally,
+    pub after: Tally,
+}
+
+impl ClassDiff {
+    /// change of the accuracy in percentage points
+    pub fn delta(&self) -> f64 {
+        (self.after.accuracy() - self.before.accuracy()) * 100.0
+    }
+}
+
+/// The keys pressed at least `min_count` times in both sessions, the ones
+/// that changed the most first
+pub fn key_diffs(before: &HistoryEntry, after: &HistoryEntry, min_count: usize) -> Vec<KeyDiff> {
+    let after_keys = after.latencies.keys(min_count);
+    let mut diffs: Vec<KeyDiff> = before
+        .latencies
+        .keys(min_count)
+        .into_iter()
+        .filter_map(|b| {
+            let a = after_keys.iter().find(|a| a.key == b.key)?;
+            Some(KeyDiff {
+                key: b.key,
+                before_ms: b.mean_ms,
+                after_ms: a.mean_ms,
+            })
+        })
+        .collect();
+    diffs.sort_by(|a, b| b.delta_ms().abs().total_cmp(&a.delta_ms().abs()));
+    diffs
+}
+
+/// The classes of characters typed in either session
+pub fn class_diffs(before: &HistoryEntry, after: &HistoryEntry) -> Vec<ClassDiff> {
+    let tally = |entry: &HistoryEntry, class| {
+        entry
+            .classes
+            .iter()
+            .find(|(c, _)| *c == class)
+            .map(|(_, tally)| tally)
+            .unwrap_or_default()
+    };
+    [
+        CharClass::Letter,
+        CharClass::Digit,
+        CharClass::Numpad,
+        CharClass::Symbol,
+    ]
+    .into_iter()
+    .map(|class| ClassDiff {
+        class,
+        before: tally(before, class),
+        after: tally(after, class),
+    })
+    .filter(|d| d.before != Tally::default() || d.after != Tally::default())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{
+        stats::{ClassTallies, KeyLatencies},
+        Mode,
+    };
+
+    fn entry(text: &str, latency_ms: u64, missed: char) -> HistoryEntry {
+        let mut latencies = KeyLatencies::default();
+        let mut classes = ClassTallies::default();
+        for c in text.chars() {
+            latencies.record(c, Duration::from_millis(latency_ms));
+            classes.hit(c);
+        }
+        classes.miss(missed);
+        let mut entry = HistoryEntry::new(&Default::default(), Mode::Random, Utc::now());
+        entry.latencies = latencies;
+        entry.classes = classes;
+        entry
+    }
+
+    #[test]
+    fn diff_keys_and_classes() {
+        let mut before = entry("aaab", 200, 'a');
+        let after = entry("aaac", 150, '1');
+        before.latencies.record('a', Duration::from_millis(400));
+
+        let keys = key_diffs(&before, &after, 2);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, 'a');
+        assert_eq!(keys[0].delta_ms(), -100.0);
+
+        let classes = class_diffs(&before, &after);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].class, CharClass::Letter);
+        assert_eq!(
+            classes[0].before,
+            Tally {
+                chars: 4,
+                misses: 1
+            }
+        );
+        assert_eq!(classes[0].after.misses, 0);
+        assert!(classes[0].delta() > 0.0);
+        assert_eq!(classes[1].class, CharClass::Digit);
+        assert_eq!(
+            classes[1].after,
+            Tally {
+                chars: 0,
+                misses: 1
+            }
+        );
+    }
+}
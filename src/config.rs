@@ -0,0 +1,517 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::widgets::{BorderType, Borders};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::{
+    banner::Banner,
+    certificate::CertificateConfig,
+    color::EffectsConfig,
+    digest::DigestConfig,
+    keyboard::KeyboardLayout,
+    kiosk::KioskConfig,
+    lesson::LessonConfig,
+    online::OnlineConfig,
+    plan::PlanItem,
+    profile,
+    race::RaceConfig,
+    score::ScoreFormula,
+    session::SessionConfig,
+    theme::Accessibility,
+    words::{MixConfig, WordFilter},
+    zone::Zone,
+    Mode,
+};
+
+/// Name of the config file inside the config directory
+const CONFIG_FILE: &str = "config.toml";
+
+/// User configuration, loaded from `<config dir>/metyping/config.toml`.
+/// Every field has a default so a partial (or missing) file is fine. The
+/// file of another profile only holds what it changes, see [`Config::load`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the active profile, shown in the header. Set from the
+    /// directory the config is loaded from, see [`crate::profile`].
+    pub profile: String,
+    /// Name of the theme to use, see [`crate::theme::Theme::by_name`]
+    pub theme: String,
+    /// Art next to the summary of the results, `classic`, `small` or
+    /// `none`
+    pub banner: Banner,
+    /// Colorful effects as far as the terminal can show them, e.g.
+    /// `effects = { text = "rainbow", celebrate = false }`
+    pub effects: EffectsConfig,
+    /// Path of a style file laid over the theme and reloaded when it
+    /// changes, e.g. `style = "/home/me/themes/gruvbox.css"`, see
+    /// [`crate::style`]
+    pub style: String,
+    /// Markers that don't rely on color, used with any theme
+    pub accessibility: Accessibility,
+    /// Mode to start in, e.g. `mode = "random"` or `mode = { words = 10 }`
+    pub mode: Mode,
+    /// Generator of the text in word based modes, `words` for words of the
+    /// list, `markov` or `ngram` for made up words that read like them
+    pub generator: String,
+    /// Word list of word based modes, `english` or `german`
+    pub word_list: String,
+    /// A second word list mixed into the first, e.g.
+    /// `mix = { list = "german", ratio = 0.3 }`
+    pub mix: MixConfig,
+    /// Which words of the lists are practiced, e.g.
+    /// `filter = { min_length = 5, hard_only = true, no_repeats = true }`
+    pub filter: WordFilter,
+    /// Keyboard layout used to rate how awkward a text is to type
+    pub layout: KeyboardLayout,
+    /// Warm-up mode: misses are shown but not counted and sessions are not
+    /// recorded to the history
+    pub practice: bool,
+    /// What a miss does, see [`Strict`]
+    pub strict: Strict,
+    /// What it takes to quit while a test is in progress, see
+    /// [`QuitProtection`]
+    pub quit_protection: QuitProtection,
+    /// Capture the mouse to make tabs and buttons clickable. Disable to keep
+    /// the terminal's own text selection.
+    pub mouse: bool,
+    /// Draw below the prompt instead of on the alternate screen, so the
+    /// results stay in the scrollback after quitting
+    pub inline: bool,
+    /// Where days start for the leaderboard and the trends, `local` or an
+    /// offset like `+05:30` to keep them the same on every machine
+    pub timezone: Zone,
+    pub header: HeaderConfig,
+    pub panels: PanelsConfig,
+    pub score: ScoreConfig,
+    pub race: RaceConfig,
+    pub session: SessionConfig,
+    /// When the lessons unlock the next letter
+    pub lesson: LessonConfig,
+    /// Named lists of command lines, recorded with `:macro record` and
+    /// `:macro save <name>`
+    pub macros: BTreeMap<String, Vec<String>>,
+    /// The training plan, sessions scheduled on days of the week, run with
+    /// `metyping plan run`
+    pub plan: Vec<PlanItem>,
+    /// Where the texts of `--online` are fetched from, e.g.
+    /// `online = { feed = "https://example.com/rss.xml" }`
+    pub online: OnlineConfig,
+    /// Shell command the JSON report of every recorded session is piped
+    /// into, e.g. `report_command = "jq -c . >> ~/typing.jsonl"`
+    pub report_command: String,
+    /// Phrases of the translate mode, `german-english` or the path of a file
+    /// with a phrase, a tab and its translation per line
+    pub phrases: String,
+    /// How a `--kiosk` session quits and idles, e.g.
+    /// `kiosk = { passphrase = "open sesame", idle_secs = 30 }`
+    pub kiosk: KioskConfig,
+    /// Where the weekly digests are written, e.g.
+    /// `digest = { dir = "/home/me/journal/typing" }`
+    pub digest: DigestConfig,
+    /// How the certificates of supervised tests are signed and written, e.g.
+    /// `certificate = { key = "only the teacher knows", html = true }`
+    pub certificate: CertificateConfig,
+    /// What the file of the profile is layered with, left out when saving
+    #[serde(skip)]
+    layers: Layers,
+}
+
+/// The settings from other places than the file of the profile
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Layers {
+    /// the global config a profile inherits, `None` for the default profile
+    inherited: Option<Table>,
+    /// the file of the profile as loaded
+    own: Table,
+    /// given on the command line or in the environment, they're not saved
+    overrides: Table,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            profile: profile::DEFAULT_PROFILE.into(),
+            theme: "default".into(),
+            style: String::new(),
+            banner: Banner::default(),
+            effects: EffectsConfig::default(),
+            accessibility: Accessibility::default(),
+            mode: Mode::default(),
+            generator: "words".into(),
+            word_list: "english".into(),
+            mix: MixConfig::default(),
+            filter: WordFilter::default(),
+            layout: KeyboardLayout::default(),
+            practice: false,
+            strict: Strict::default(),
+            quit_protection: QuitProtection::default(),
+            mouse: true,
+            inline: false,
+            timezone: Zone::default(),
+            header: HeaderConfig::default(),
+            panels: PanelsConfig::default(),
+            score: ScoreConfig::default(),
+            race: RaceConfig::default(),
+            session: SessionConfig::default(),
+            lesson: LessonConfig::default(),
+            macros: BTreeMap::new(),
+            plan: vec![],
+            online: OnlineConfig::default(),
+            report_command: String::new(),
+            phrases: "german-english".into(),
+            kiosk: KioskConfig::default(),
+            digest: DigestConfig::default(),
+            certificate: CertificateConfig::default(),
+            layers: Layers::default(),
+        }
+    }
+}
+
+/// Strict modes for accuracy training
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strict {
+    /// a miss is marked and the expected key has to be pressed to go on
+    #[default]
+    Off,
+    /// a miss fails the round and the next one starts
+    Round,
+    /// a miss ends the session
+    Session,
+}
+
+/// Guards against losing a test to an accidental Esc
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuitProtection {
+    /// ask in a dialog
+    #[default]
+    Dialog,
+    /// Esc has to be pressed twice within a second
+    DoubleEsc,
+    /// quit right away
+    Off,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeaderConfig {
+    pub show: bool,
+    pub title: String,
+    pub show_profile: bool,
+    pub show_mode: bool,
+    pub show_clock: bool,
+    /// strftime-like format, see chrono's `format` module
+    pub clock_format: String,
+}
+
+impl Default for HeaderConfig {
+    fn default() -> Self {
+        Self {
+            show: true,
+            title: "metyping".into(),
+            show_profile: true,
+            show_mode: true,
+            show_clock: true,
+            clock_format: "%H:%M".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelsConfig {
+    pub wins: PanelConfig,
+    pub fails: PanelConfig,
+    pub input: PanelConfig,
+}
+
+impl Default for PanelsConfig {
+    fn default() -> Self {
+        Self {
+            wins: PanelConfig::new(" WINS ", BorderStyle::Rounded),
+            fails: PanelConfig::new(" FAILS ", BorderStyle::Rounded),
+            input: PanelConfig::new("", BorderStyle::None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PanelConfig {
+    pub title: String,
+    pub border: BorderStyle,
+}
+
+impl PanelConfig {
+    pub fn new(title: &str, border: BorderStyle) -> Self {
+        Self {
+            title: title.into(),
+            border,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreConfig {
+    /// formula of the skill score shown on the stats screen
+    pub formula: ScoreFormula,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    None,
+    Plain,
+    #[default]
+    Rounded,
+    Double,
+    Thick,
+}
+
+impl BorderStyle {
+    pub fn borders(self) -> Borders {
+        match self {
+            Self::None => Borders::NONE,
+            _ => Borders::ALL,
+        }
+    }
+
+    pub fn border_type(self) -> BorderType {
+        match self {
+            Self::None | Self::Plain => BorderType::Plain,
+            Self::Rounded => BorderType::Rounded,
+            Self::Double => BorderType::Double,
+            Self::Thick => BorderType::Thick,
+        }
+    }
+}
+
+impl Config {
+    /// Directory holding the config file of `profile`, `None` if the
+    /// platform has no config directory
+    pub fn dir(profile: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| profile::dir(&dir.join("metyping"), profile))
+    }
+
+    /// Path of the config file of `profile`
+    pub fn path(profile: &str) -> Option<PathBuf> {
+        Self::dir(profile).map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Loads the config of `profile`. A setting is taken from the first of
+    /// `overrides`, the file of the profile, the file of the default profile
+    /// and the defaults that has it, so a profile only holds what it
+    /// changes.
+    pub fn load(profile: &str, overrides: Table) -> Result<Self> {
+        let inherited = match profile {
+            profile::DEFAULT_PROFILE => None,
+            _ => Some(Self::read(Self::path(profile::DEFAULT_PROFILE))?),
+        };
+        let own = Self::read(Self::path(profile))?;
+        let mut config = Self::layered(Layers {
+            inherited,
+            own,
+            overrides,
+        })
+        .wrap_err_with(|| format!("loading the config of the profile {profile} failed"))?;
+        config.profile = profile.into();
+        Ok(config)
+    }
+
+    /// the settings in the file at `path`, none if there is no file
+    fn read(path: Option<PathBuf>) -> Result<Table> {
+        match path {
+            Some(path) if path.exists() => {
+                let raw = fs::read_to_string(&path)
+                    .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+                raw.parse()
+                    .wrap_err_with(|| format!("parsing {} failed", path.display()))
+            }
+            _ => Ok(Table::new()),
+        }
+    }
+
+    /// the config of `layers` stacked on top of each other
+    fn layered(layers: Layers) -> Result<Self> {
+        let mut merged = layers.inherited.clone().unwrap_or_default();
+        merge(&mut merged, layers.own.clone());
+        merge(&mut merged, layers.overrides.clone());
+        let mut config: Self = Value::Table(merged).try_into()?;
+        config.layers = layers;
+        Ok(config)
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        Ok(toml::from_str(raw)?)
+    }
+
+    /// the settings given on the command line or in the environment
+    pub fn overrides(&self) -> &Table {
+        &self.layers.overrides
+    }
+
+    /// the settings as a table, with the defaults
+    fn table(&self) -> Result<Table> {
+        match Value::try_from(self)? {
+            Value::Table(table) => Ok(table),
+            _ => unreachable!("the config is a table"),
+        }
+    }
+
+    /// Writes the config to `path`, creating its directory if needed. A
+    /// profile other than the default one only gets the settings that
+    /// differ from the ones it inherits, and the overridden ones stay as they
+    /// were in the file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        let mut table = self.table()?;
+        for (key, value) in &self.layers.overrides {
+            if table.get(key) == Some(value) {
+                match self.layers.own.get(key) {
+                    Some(own) => table.insert(key.clone(), own.clone()),
+                    None => table.remove(key),
+                };
+            }
+        }
+        // the profile is the directory the file is in
+        table.remove("profile");
+        if let Some(inherited) = &self.layers.inherited {
+            let inherited = Self::layered(Layers {
+                inherited: Some(inherited.clone()),
+                ..Default::default()
+            })?;
+            table = diff(table, &inherited.table()?);
+        }
+        fs::write(path, toml::to_string_pretty(&table)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+}
+
+/// sets the settings of `over` in `base`, tables are merged key by key
+fn merge(base: &mut Table, over: Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(over)) => merge(base, over),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// the settings of `table` that aren't the same in `base`
+fn diff(table: Table, base: &Table) -> Table {
+    table
+        .into_iter()
+        .filter_map(|(key, value)| match (value, base.get(&key)) {
+            (value, Some(base)) if value == *base => None,
+            (Value::Table(table), Some(Value::Table(base))) => {
+                let changed = diff(table, base);
+                (!changed.is_empty()).then_some((key, Value::Table(changed)))
+            }
+            (value, _) => Some((key, value)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn parse_partial_config() {
+        let config = Config::parse(
+            r#"
+            profile = "colemak"
+            mode = { words = 12 }
+
+            [header]
+            show_clock = false
+
+            [panels.input]
+            title = " TYPE "
+            border = "double"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.profile, "colemak");
+        assert_eq!(config.theme, "default");
+        assert_eq!(config.mode, Mode::Words(12));
+        assert!(!config.header.show_clock);
+        assert!(config.header.show_mode);
+        assert_eq!(config.panels.input.title, " TYPE ");
+        assert_eq!(config.panels.input.border, BorderStyle::Double);
+        assert_eq!(config.panels.wins, PanelsConfig::default().wins);
+    }
+
+    #[test]
+    fn save_roundtrip() {
+        let path = temp_path("config.toml");
+        let config = Config {
+            practice: true,
+            mode: Mode::Race(20),
+            macros: BTreeMap::from([(
+                "drill".into(),
+                vec!["mode symbols 25".into(), "strict round".into()],
+            )]),
+            ..Default::default()
+        };
+        config.save(&path).unwrap();
+        let raw = fs::read_to_string(&path).unwrap();
+        assert_eq!(Config::parse(&raw).unwrap(), config);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn profiles_override_the_global_config() {
+        let layers = Layers {
+            inherited: Some(
+                "theme = \"nord\"\nmode = \"random\"\n[header]\nshow_clock = false"
+                    .parse()
+                    .unwrap(),
+            ),
+            own: "mode = { words = 12 }\n[header]\ntitle = \"colemak\""
+                .parse()
+                .unwrap(),
+            overrides: "mode = \"quote\"".parse().unwrap(),
+        };
+        let mut config = Config::layered(layers).unwrap();
+        assert_eq!(config.theme, "nord");
+        assert_eq!(config.mode, Mode::Quote);
+        assert!(!config.header.show_clock);
+        assert_eq!(config.header.title, "colemak");
+        assert_eq!(config.layout, KeyboardLayout::Qwerty);
+
+        // the file keeps what the profile changes, not what it inherits or
+        // is overridden
+        config.practice = true;
+        let path = temp_path("profile.toml");
+        config.save(&path).unwrap();
+        let saved: Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+        fs::remove_file(path).unwrap();
+        let expected: Table =
+            "practice = true\nmode = { words = 12 }\n[header]\ntitle = \"colemak\"\n"
+                .parse()
+                .unwrap();
+        assert_eq!(saved, expected);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_border() {
+        assert!(Config::parse("[panels.wins]\nborder = \"wavy\"").is_err());
+    }
+}
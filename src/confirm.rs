@@ -0,0 +1,189 @@
+use std::time::Instant;
+
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::{App, Mode};
+
+/// What answering yes to a [`Confirm`] dialog does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmAction {
+    /// quit, the test in progress is lost unless saved to resume
+    Quit,
+    /// start over in this mode and lose the test in progress
+    Discard(Mode),
+    /// continue the test quit last time
+    Resume,
+    /// delete the entry at this index of the history
+    DeleteEntry(usize),
+}
+
+/// A yes/no question asked before a destructive action, No is selected
+/// until changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirm {
+    question: String,
+    action: ConfirmAction,
+    yes: bool,
+}
+
+impl Confirm {
+    pub fn new(question: impl Into<String>, action: ConfirmAction) -> Self {
+        Self {
+            question: question.into(),
+            action,
+            yes: false,
+        }
+    }
+}
+
+impl App<'_> {
+    /// asks `question` and runs `action` if the answer is yes
+    pub(crate) fn confirm(&mut self, question: impl Into<String>, action: ConfirmAction) {
+        self.confirm = Some(Confirm::new(question, action));
+    }
+
+    fn run_confirmed(&mut self, action: ConfirmAction) -> Result<()> {
+        match action {
+            ConfirmAction::Quit => self.exit(),
+            ConfirmAction::Discard(mode) => {
+                self.mode = mode;
+                self.new_session()?;
+            }
+            ConfirmAction::DeleteEntry(index) => self.delete_entry(index)?,
+            ConfirmAction::Resume => {
+                if let Some(snapshot) = self.resumable.take() {
+                    self.resume(snapshot, Instant::now());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_declined(&mut self, action: ConfirmAction) -> Result<()> {
+        match action {
+            ConfirmAction::Resume => match self.resumable.take() {
+                Some(snapshot) => self.record_snapshot(snapshot),
+                None => Ok(()),
+            },
+            ConfirmAction::Quit | ConfirmAction::Discard(_) | ConfirmAction::DeleteEntry(_) => {
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn handle_confirm_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some(confirm) = &mut self.confirm else {
+            return Ok(());
+        };
+        let answer = match key_event.code {
+            KeyCode::Char('y') => Some(true),
+            KeyCode::Char('n') | KeyCode::Esc => Some(false),
+            KeyCode::Enter => Some(confirm.yes),
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char('h' | 'l') => {
+                confirm.yes = !confirm.yes;
+                None
+            }
+            _ => None,
+        };
+        match answer {
+            Some(true) => {
+                let action = confirm.action;
+                self.confirm = None;
+                self.run_confirmed(action)
+            }
+            Some(false) => {
+                let action = confirm.action;
+                self.confirm = None;
+                self.run_declined(action)
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn render_confirm(&self, area: Rect, buf: &mut Buffer) {
+        let Some(confirm) = &self.confirm else {
+            return;
+        };
+        let width = (confirm.question.chars().count() as u16 + 6)
+            .max(24)
+            .min(area.width);
+        let height = 5.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        let button = |label: &'static str, selected: bool| {
+            if selected {
+                Span::styled(label, Style::new().add_modifier(Modifier::REVERSED))
+            } else {
+                Span::raw(label)
+            }
+        };
+        let lines = vec![
+            Line::from(confirm.question.as_str()),
+            Line::default(),
+            Line::from(vec![
+                button("[ yes ]", confirm.yes),
+                Span::raw("  "),
+                button("[ no ]", !confirm.yes),
+            ]),
+        ];
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .title(Span::styled(" CONFIRM ", self.theme.title))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(self.theme.border),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn answer_yes_or_no() {
+        let mut app = App::default();
+        app.next_round().unwrap();
+        app.handle_key_event(KeyCode::Char('#').into()).unwrap();
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        let question = |app: &App| app.confirm.as_ref().map(|c| c.question.clone());
+        assert_eq!(question(&app).as_deref(), Some("Quit and lose this test?"));
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(app.confirm.is_none());
+        assert!(!app.exit);
+
+        app.resume_path = Some(temp_path("resume.json"));
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(
+            question(&app).as_deref(),
+            Some("Quit? The test is saved to resume.")
+        );
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+
+        app.confirm("quit?", ConfirmAction::Quit);
+        app.handle_key_event(KeyCode::Char('x').into()).unwrap();
+        assert!(app.confirm.is_some());
+        app.handle_key_event(KeyCode::Left.into()).unwrap();
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(app.exit);
+    }
+}
@@ -0,0 +1,330 @@
+//! Course packs, drills a teacher hands out as one TOML or JSON file:
+//!
+//! ```toml
+//! name = "Home row"
+//!
+//! [[drills]]
+//! name = "left hand"
+//! mode = { text = 5 }
+//! text = "asdf fdsa sad dad fad"
+//! pass = { wpm = 20, accuracy = 0.95 }
+//!
+//! [[drills]]
+//! name = "words"
+//! mode = { words = 15 }
+//! generator = "markov"
+//! ```
+//!
+//! The drills unlock in order, each once the one before is passed.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    generator,
+    history::{History, HistoryEntry},
+    App, Mode, TEXT_ROUND_WORDS,
+};
+
+/// Name of the directory of the loaded packs inside the data directory
+const COURSES_DIR: &str = "courses";
+/// Name of the file of the passed drills inside the data directory
+const PROGRESS_FILE: &str = "courses.json";
+
+/// A pack of drills practiced in order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Course {
+    pub name: String,
+    pub drills: Vec<CourseDrill>,
+}
+
+/// A session of a course
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CourseDrill {
+    pub name: String,
+    pub mode: Mode,
+    /// generator of word based modes, the one of the config if empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub generator: String,
+    /// text practiced instead of generated text
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    #[serde(default)]
+    pub pass: PassCriteria,
+}
+
+/// What a session of a drill needs to pass, any finished session by default
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PassCriteria {
+    pub wpm: f64,
+    /// share of the keystrokes that have to be right, between 0 and 1
+    pub accuracy: f64,
+}
+
+impl PassCriteria {
+    pub fn met(&self, entry: &HistoryEntry) -> bool {
+        entry.wpm >= self.wpm && entry.accuracy() >= self.accuracy
+    }
+}
+
+impl Course {
+    /// Parses the pack at `path`, JSON if it ends in `.json` and TOML
+    /// otherwise
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        let course: Self = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&raw)?
+        } else {
+            toml::from_str(&raw)?
+        };
+        course
+            .validate()
+            .wrap_err_with(|| format!("{} isn't a course", path.display()))?;
+        Ok(course)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(eyre!("the course needs a name"));
+        }
+        if self.drills.is_empty() {
+            return Err(eyre!("{} has no drills", self.name));
+        }
+        let mut names = BTreeSet::new();
+        if let Some(drill) = self.drills.iter().find(|d| !names.insert(&d.name)) {
+            return Err(eyre!("there are two drills called {}", drill.name));
+        }
+        Ok(())
+    }
+
+    /// Writes the pack to `path`, as JSON if it ends in `.json` and TOML
+    /// otherwise
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        let raw = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::to_string_pretty(self)?
+        } else {
+            toml::to_string(self)?
+        };
+        fs::write(path, raw).wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    /// Directory of the packs loaded into `profile`, `None` if the platform
+    /// has no data directory
+    pub fn dir(profile: &str) -> Option<PathBuf> {
+        History::dir(profile).map(|dir| dir.join(COURSES_DIR))
+    }
+
+    /// The packs in `dir` sorted by name, none if there is no directory
+    pub fn list(dir: &Path) -> Result<Vec<Self>> {
+        let Ok(files) = fs::read_dir(dir) else {
+            return Ok(vec![]);
+        };
+        let mut courses = files
+            .filter_map(|file| file.ok())
+            .map(|file| file.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .map(|path| Self::load(&path))
+            .collect::<Result<Vec<_>>>()?;
+        courses.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(courses)
+    }
+
+    /// Copies the pack into `dir`, replacing a loaded pack of the same name
+    pub fn install(&self, dir: &Path) -> Result<()> {
+        let file: String = self
+            .name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        self.save(&dir.join(file).with_extension("toml"))
+    }
+}
+
+/// The drills passed per course, stored per profile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CourseProgress {
+    pub passed: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl CourseProgress {
+    /// Default location of the progress of `profile`, `None` if the platform
+    /// has no data directory
+    pub fn path(profile: &str) -> Option<PathBuf> {
+        History::dir(profile).map(|dir| dir.join(PROGRESS_FILE))
+    }
+
+    /// Loads the progress stored at `path`, nothing passed if there is no
+    /// file
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        serde_json::from_str(&raw).wrap_err_with(|| format!("parsing {} failed", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    pub fn is_passed(&self, course: &Course, drill: &CourseDrill) -> bool {
+        self.passed
+            .get(&course.name)
+            .is_some_and(|drills| drills.contains(&drill.name))
+    }
+
+    /// the number of drills of `course` that can be practiced, the passed
+    /// ones and the one after them
+    pub fn unlocked(&self, course: &Course) -> usize {
+        let passed = course
+            .drills
+            .iter()
+            .take_while(|drill| self.is_passed(course, drill))
+            .count();
+        (passed + 1).min(course.drills.len())
+    }
+}
+
+impl App<'_> {
+    /// starts the drill at `index` of the course at `course`, returns a
+    /// message for the status line
+    pub(crate) fn start_course_drill(&mut self, course: usize, index: usize) -> Result<String> {
+        let Some(pack) = self.courses.get(course).filter(|p| index < p.drills.len()) else {
+            return Ok(String::new());
+        };
+        if index >= self.course_progress.unlocked(pack) {
+            return Ok(format!("pass {} first", pack.drills[index - 1].name));
+        }
+        let drill = pack.drills[index].clone();
+        let message = format!("{}: {}", pack.name, drill.name);
+        if !drill.generator.is_empty() {
+            if self.generators.get(&drill.generator).is_none() {
+                return Ok(format!("unknown generator '{}'", drill.generator));
+            }
+            self.config.generator = drill.generator.clone();
+        }
+        self.mode = drill.mode;
+        if !drill.text.is_empty() {
+            self.generators
+                .register("text", generator::Text::new(&drill.text));
+            if !matches!(self.mode, Mode::Text(_)) {
+                self.mode = Mode::Text(TEXT_ROUND_WORDS);
+            }
+        }
+        self.course_drill = Some((course, index));
+        self.new_session()?;
+        Ok(message)
+    }
+
+    /// passes the running drill if `entry` meets its criteria and tells
+    /// what's next
+    pub(crate) fn record_course_drill(&mut self, entry: &HistoryEntry) -> Result<()> {
+        let Some((course, index)) = self.course_drill else {
+            return Ok(());
+        };
+        let pack = &self.courses[course];
+        let drill = &pack.drills[index];
+        if entry.mode != self.mode {
+            return Ok(());
+        }
+        if !drill.pass.met(entry) {
+            self.status = Some(format!(
+                "{} needs {:.0} WPM at {:.0}% accuracy",
+                drill.name,
+                drill.pass.wpm,
+                drill.pass.accuracy * 100.0
+            ));
+            return Ok(());
+        }
+        self.status = Some(match pack.drills.get(index + 1) {
+            Some(next) => format!("passed {}, next: {}", drill.name, next.name),
+            None => format!("passed {}, {} is done", drill.name, pack.name),
+        });
+        self.course_progress
+            .passed
+            .entry(pack.name.clone())
+            .or_default()
+            .insert(drill.name.clone());
+        self.course_drill = None;
+        match &self.course_path {
+            Some(path) => self
+                .course_progress
+                .save(path)
+                .wrap_err("saving the course progress failed"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::stats::SessionStats;
+
+    #[test]
+    fn unlock_the_drills_in_order() {
+        let course: Course = toml::from_str(
+            r#"
+            name = "Home row"
+            [[drills]]
+            name = "left hand"
+            mode = { text = 5 }
+            text = "asdf fdsa"
+            pass = { wpm = 20, accuracy = 0.9 }
+            [[drills]]
+            name = "words"
+            mode = { words = 15 }
+            "#,
+        )
+        .unwrap();
+        assert!(course.validate().is_ok());
+        let mut app = App {
+            courses: vec![course],
+            ..Default::default()
+        };
+        assert_eq!(
+            app.start_course_drill(0, 1).unwrap(),
+            "pass left hand first"
+        );
+        assert_eq!(app.start_course_drill(0, 0).unwrap(), "Home row: left hand");
+        assert_eq!(app.mode, Mode::Text(5));
+        assert_eq!(app.target, "asdf fdsa");
+
+        let mut entry = HistoryEntry::new(&SessionStats::default(), app.mode, Utc::now());
+        entry.wpm = 15.0;
+        app.record_course_drill(&entry).unwrap();
+        assert_eq!(
+            app.status.as_deref(),
+            Some("left hand needs 20 WPM at 90% accuracy")
+        );
+        entry.wpm = 25.0;
+        app.record_course_drill(&entry).unwrap();
+        assert_eq!(app.status.as_deref(), Some("passed left hand, next: words"));
+        assert_eq!(app.course_progress.unlocked(&app.courses[0]), 2);
+    }
+}
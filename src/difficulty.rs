@@ -0,0 +1,90 @@
+use crate::keyboard::KeyboardLayout;
+
+/// Relative frequency of the letters a-z in english text, in percent
+const LETTER_FREQUENCY: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.8, 4.0, 2.4, 6.7, 7.5, 1.9, 0.1, 6.0,
+    6.3, 9.1, 2.8, 1.0, 2.4, 0.15, 2.0, 0.07,
+];
+
+/// Text longer than this doesn't get any harder by length alone
+const MAX_LENGTH: usize = 50;
+
+/// How hard a text is to type. A score of 1.0 is an easy, short text made of
+/// common letters; harder texts score higher.
+///
+/// The score adds up four weighted components:
+/// - length, up to [`MAX_LENGTH`] characters (weight 0.25)
+/// - average rarity of the letters (weight 0.5)
+/// - share of digits and symbols (weight 1.0)
+/// - share of awkward bigrams on the active layout (weight 0.8)
+pub fn score(text: &str, layout: KeyboardLayout) -> f64 {
+    let chars: Vec<char> = text.chars().filter(|c| *c != ' ').collect();
+    if chars.is_empty() {
+        return 1.0;
+    }
+
+    let length = chars.len().min(MAX_LENGTH) as f64 / MAX_LENGTH as f64;
+    let rarity = chars.iter().map(|c| rarity(*c)).sum::<f64>() / chars.len() as f64;
+    let symbols = chars.iter().filter(|c| !c.is_alphabetic()).count() as f64 / chars.len() as f64;
+
+    1.0 + 0.25 * length + 0.5 * rarity + symbols + 0.8 * awkwardness(text, layout)
+}
+
+/// 0.0 for the most common letter up to almost 1.0 for the rarest ones.
+/// Characters that aren't letters count as rare.
+fn rarity(c: char) -> f64 {
+    let max = LETTER_FREQUENCY[4];
+    match c.to_ascii_lowercase() {
+        c @ 'a'..='z' => 1.0 - (LETTER_FREQUENCY[c as usize - 'a' as usize] / max).sqrt(),
+        _ => 1.0,
+    }
+}
+
+/// Average awkwardness of the bigrams inside the words of `text`. Typing two
+/// different keys with the same finger counts fully, jumping over the home
+/// row with one hand counts half.
+fn awkwardness(text: &str, layout: KeyboardLayout) -> f64 {
+    let mut bigrams = 0;
+    let mut total = 0.0;
+    for word in text.split(' ') {
+        let keys: Vec<_> = word.chars().map(|c| layout.key(c)).collect();
+        for pair in keys.windows(2) {
+            bigrams += 1;
+            let (Some(a), Some(b)) = (pair[0], pair[1]) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            if a.finger() == b.finger() {
+                total += 1.0;
+            } else if a.is_left_hand() == b.is_left_hand() && a.row.abs_diff(b.row) == 2 {
+                total += 0.5;
+            }
+        }
+    }
+
+    if bigrams == 0 {
+        0.0
+    } else {
+        total / bigrams as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harder_texts_score_higher() {
+        let layout = KeyboardLayout::Qwerty;
+        assert_eq!(score("", layout), 1.0);
+
+        let easy = score("tea", layout);
+        assert!(easy < score("jazz", layout));
+        assert!(easy < score("t3a!", layout));
+        assert!(easy < score("tea tea tea tea tea tea", layout));
+        // "ed" is typed with the same finger on qwerty, not on colemak
+        assert!(score("ed", layout) > score("ed", KeyboardLayout::Colemak));
+    }
+}
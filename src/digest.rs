@@ -0,0 +1,196 @@
+//! A weekly digest of the practice in Markdown, for typists who keep a
+//! journal: how much was practiced, how the speed went, the personal bests
+//! and what to focus on next. `metyping digest` prints the one of this
+//! week. With `digest.dir` in the config, the digest of last week is
+//! written there at the first launch after it.
+
+use std::{fs, path::Path};
+
+use chrono::{Duration, NaiveDate, Utc};
+use color_eyre::{eyre::WrapErr, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    drill::Focus,
+    history::{History, HistoryEntry},
+    trends::Period,
+    zone::Zone,
+};
+
+/// Where the digests are written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// directory the digest of the last week is written to at launch, none
+    /// are written if empty
+    pub dir: String,
+}
+
+/// the digest of the week starting at `start`, a Monday
+pub fn weekly(history: &History, start: NaiveDate, zone: Zone) -> String {
+    let end = start + Duration::days(7);
+    let in_week = |from: NaiveDate, entry: &&HistoryEntry| {
+        let date = zone.date(entry.timestamp);
+        from <= date && date < from + Duration::days(7)
+    };
+    let week: Vec<&HistoryEntry> = history
+        .entries()
+        .iter()
+        .filter(|entry| in_week(start, entry))
+        .collect();
+    let before: Vec<&HistoryEntry> = history
+        .entries()
+        .iter()
+        .filter(|entry| in_week(start - Duration::days(7), entry))
+        .collect();
+
+    let mut text = format!("# Typing digest, week of {}\n\n", start.format("%Y-%m-%d"));
+
+    text.push_str("## Practice\n\n");
+    let minutes = week.iter().map(|e| e.duration_ms).sum::<u64>() as f64 / 60_000.0;
+    let mut days: Vec<NaiveDate> = week.iter().map(|e| zone.date(e.timestamp)).collect();
+    days.dedup();
+    text.push_str(&format!(
+        "- {} sessions, {minutes:.0} minutes\n- practiced on {} of 7 days\n\n",
+        week.len(),
+        days.len()
+    ));
+
+    text.push_str("## Trend\n\n");
+    match (average_wpm(&week), average_wpm(&before)) {
+        (None, _) => text.push_str("- no sessions this week\n\n"),
+        (Some(wpm), before) => {
+            let chars = week.iter().map(|e| e.chars).sum();
+            let misses = week.iter().map(|e| e.misses).sum();
+            let accuracy = crate::stats::accuracy(chars, misses) * 100.0;
+            let change = match before {
+                Some(before) if wpm >= before => format!("up {:.1} WPM", wpm - before),
+                Some(before) => format!("down {:.1} WPM", before - wpm),
+                None => "no sessions the week before".into(),
+            };
+            text.push_str(&format!(
+                "- {wpm:.1} WPM at {accuracy:.1}% accuracy, {change}\n\n"
+            ));
+        }
+    }
+
+    text.push_str("## Personal bests\n\n");
+    let bests = personal_bests(history.entries(), start, end, zone);
+    if bests.is_empty() {
+        text.push_str("- none this week\n");
+    }
+    for best in bests {
+        text.push_str(&format!(
+            "- {}: {:.1} WPM on {}\n",
+            best.category(),
+            best.wpm,
+            zone.date(best.timestamp).format("%A")
+        ));
+    }
+    text.push('\n');
+
+    text.push_str("## Suggested focus\n\n");
+    let focus = Focus::from_history(history);
+    if focus.is_empty() {
+        text.push_str("- keep practicing, the weak spots show after a few sessions\n");
+    }
+    if !focus.keys.is_empty() {
+        let keys: Vec<String> = focus.keys.iter().map(|key| format!("`{key}`")).collect();
+        text.push_str(&format!("- keys missed the most: {}\n", keys.join(", ")));
+    }
+    if !focus.bigrams.is_empty() {
+        let bigrams: Vec<String> = focus.bigrams.iter().map(|b| format!("`{b}`")).collect();
+        text.push_str(&format!("- slowest transitions: {}\n", bigrams.join(", ")));
+    }
+    text
+}
+
+fn average_wpm(entries: &[&HistoryEntry]) -> Option<f64> {
+    (!entries.is_empty()).then(|| entries.iter().map(|e| e.wpm).sum::<f64>() / entries.len() as f64)
+}
+
+/// the fastest session of each category between `start` and `end` that beat
+/// every session of it before `start`
+fn personal_bests(
+    entries: &[HistoryEntry],
+    start: NaiveDate,
+    end: NaiveDate,
+    zone: Zone,
+) -> Vec<&HistoryEntry> {
+    let mut bests: Vec<&HistoryEntry> = vec![];
+    for category in crate::leaderboard::categories(entries) {
+        let (earlier, week): (Vec<&HistoryEntry>, Vec<&HistoryEntry>) = entries
+            .iter()
+            .filter(|e| e.category() == category && zone.date(e.timestamp) < end)
+            .partition(|e| zone.date(e.timestamp) < start);
+        let Some(record) = earlier.iter().map(|e| e.wpm).max_by(f64::total_cmp) else {
+            continue;
+        };
+        if let Some(best) = week
+            .into_iter()
+            .filter(|e| e.wpm > record)
+            .max_by(|a, b| a.wpm.total_cmp(&b.wpm))
+        {
+            bests.push(best);
+        }
+    }
+    bests
+}
+
+/// prints the digest of this week so far
+pub fn print(history: &History, zone: Zone) -> Result<()> {
+    let today = zone.date(Utc::now());
+    print!("{}", weekly(history, Period::Week.start(today), zone));
+    Ok(())
+}
+
+/// writes the digest of last week to `config.dir`, unless it's there
+/// already or there's no directory
+pub fn write_last_week(history: &History, config: &DigestConfig, zone: Zone) -> Result<()> {
+    if config.dir.is_empty() {
+        return Ok(());
+    }
+    let start = Period::Week.start(zone.date(Utc::now())) - Duration::days(7);
+    let dir = Path::new(&config.dir);
+    let path = dir.join(format!("{}.md", start.format("%Y-%m-%d")));
+    if path.exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir).wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+    fs::write(&path, weekly(history, start, zone))
+        .wrap_err_with(|| format!("writing {} failed", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone};
+
+    use super::*;
+    use crate::{stats::SessionStats, Mode};
+
+    #[test]
+    fn weekly_digest() {
+        let mut history = History::default();
+        for (day, wpm) in [(1, 40.0), (6, 50.0), (7, 45.0), (7, 60.0), (9, 55.0)] {
+            let timestamp = Utc.with_ymd_and_hms(2024, 5, day, 12, 0, 0).unwrap();
+            let mut entry = HistoryEntry::new(&SessionStats::default(), Mode::Words(10), timestamp);
+            entry.wpm = wpm;
+            entry.chars = 95;
+            entry.misses = 5;
+            entry.duration_ms = 60_000;
+            history.push(entry).unwrap();
+        }
+        // the week of Monday the 6th
+        let start = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        let digest = weekly(
+            &history,
+            start,
+            Zone::Fixed(FixedOffset::east_opt(0).unwrap()),
+        );
+        assert!(digest.starts_with("# Typing digest, week of 2024-05-06\n"));
+        assert!(digest.contains("- 4 sessions, 4 minutes\n- practiced on 3 of 7 days\n"));
+        assert!(digest.contains("- 52.5 WPM at 95.0% accuracy, up 12.5 WPM\n"));
+        assert!(digest.contains("- words 10: 60.0 WPM on Tuesday\n"));
+        assert!(digest.contains("## Suggested focus\n\n- keep practicing"));
+    }
+}
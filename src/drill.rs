@@ -0,0 +1,157 @@
+use std::{cmp::Reverse, fmt};
+
+use color_eyre::Result;
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::{generator, history::History, words::WordList, App, Mode};
+
+/// Number of transitions and of keys a drill focuses on
+const FOCUS_SIZE: usize = 5;
+/// Samples of a key or transition before it's considered
+const MIN_SAMPLES: usize = 3;
+/// Longest word of a drill
+const MAX_WORD_LEN: usize = 8;
+/// Number of the best fitting words a drill draws from
+const POOL_SIZE: usize = 40;
+/// Words per round of a drill started from the analytics
+const DRILL_ROUND_WORDS: u8 = 10;
+
+/// The weak spots of the history a drill trains
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Focus {
+    /// pairs of keys inside words, slowest first
+    pub bigrams: Vec<String>,
+    /// keys missed the most for how often they're typed, worst first
+    pub keys: Vec<char>,
+}
+
+impl Focus {
+    pub fn from_history(history: &History) -> Self {
+        let bigrams = history
+            .bigrams()
+            .slowest(FOCUS_SIZE, MIN_SAMPLES)
+            .into_iter()
+            .map(|b| b.key)
+            .collect();
+
+        let misses = history.key_misses();
+        let mut keys: Vec<(char, f64)> = history
+            .latencies()
+            .keys(MIN_SAMPLES)
+            .into_iter()
+            .filter_map(|key| {
+                let missed = *misses.get(&key.key)?;
+                Some((key.key, missed as f64 / (missed + key.count) as f64))
+            })
+            .filter(|(key, _)| !key.is_whitespace())
+            .collect();
+        keys.sort_by(|a, b| b.1.total_cmp(&a.1));
+        keys.truncate(FOCUS_SIZE);
+
+        Self {
+            bigrams,
+            keys: keys.into_iter().map(|(key, _)| key).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bigrams.is_empty() && self.keys.is_empty()
+    }
+
+    /// how much of the focus `word` trains, the weakest spots weigh the most
+    pub fn score(&self, word: &str) -> usize {
+        let bigrams: usize = self
+            .bigrams
+            .iter()
+            .enumerate()
+            .map(|(rank, bigram)| word.matches(bigram.as_str()).count() * 2 * (FOCUS_SIZE - rank))
+            .sum();
+        let keys: usize = self
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(rank, key)| word.chars().filter(|c| c == key).count() * (FOCUS_SIZE - rank))
+            .sum();
+        bigrams + keys
+    }
+
+    /// `count` words of the list drawn from the ones that train the focus
+    /// best, random words if none do
+    pub fn words(&self, words: &WordList, rng: &mut dyn RngCore, count: usize) -> String {
+        let mut pool: Vec<(&str, usize)> = words
+            .words
+            .iter()
+            .filter(|w| w.chars().count() <= MAX_WORD_LEN)
+            .map(|w| (w.as_str(), self.score(w)))
+            .filter(|(_, score)| *score > 0)
+            .collect();
+        if pool.is_empty() {
+            return words.sample(rng, count);
+        }
+        pool.sort_by_key(|(_, score)| Reverse(*score));
+        pool.truncate(POOL_SIZE);
+        (0..count)
+            .filter_map(|_| pool.choose_weighted(rng, |(_, score)| *score).ok())
+            .map(|(word, _)| *word)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl fmt::Display for Focus {
+    /// e.g. `th, er, q, z`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self.keys.iter().map(char::to_string);
+        let all: Vec<String> = self.bigrams.iter().cloned().chain(keys).collect();
+        write!(f, "{}", all.join(", "))
+    }
+}
+
+impl App<'_> {
+    /// starts a drill of the weak spots in the history, returns a message
+    /// for the status line
+    pub(crate) fn start_drill(&mut self) -> Result<String> {
+        let focus = Focus::from_history(&self.history);
+        let message = if focus.is_empty() {
+            "not enough data for a drill yet, practicing random words".into()
+        } else {
+            format!("drill: {focus}")
+        };
+        self.generators
+            .register("drill", generator::Drill::new(focus));
+        if !matches!(self.mode, Mode::Drill(_)) {
+            self.mode = Mode::Drill(DRILL_ROUND_WORDS);
+        }
+        self.new_session()?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn draw_the_words_that_train_the_focus() {
+        let focus = Focus {
+            bigrams: vec!["th".into()],
+            keys: vec!['q'],
+        };
+        assert_eq!(focus.score("the"), 10);
+        assert_eq!(focus.score("quit"), 5);
+        assert_eq!(focus.score("dog"), 0);
+        assert_eq!(focus.to_string(), "th, q");
+
+        let words = WordList::from_text("test", "the quit dog cat with extraordinary");
+        let text = focus.words(&words, &mut StdRng::seed_from_u64(1), 20);
+        assert_eq!(text.split(' ').count(), 20);
+        assert!(text
+            .split(' ')
+            .all(|w| ["the", "quit", "with"].contains(&w)));
+
+        let text = Focus::default().words(&words, &mut StdRng::seed_from_u64(1), 3);
+        assert_eq!(text.split(' ').count(), 3);
+    }
+}
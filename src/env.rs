@@ -0,0 +1,77 @@
+//! `METYPING_*` environment variables. They override the config files and
+//! are overridden by the command line, handy in containers and for demos.
+
+use std::{env, path::PathBuf};
+
+use clap::ValueEnum;
+use color_eyre::{eyre::eyre, Result};
+use toml::{Table, Value};
+
+use crate::{keyboard::KeyboardLayout, Mode};
+
+/// Mode to start in, e.g. `words 25`
+pub const MODE: &str = "METYPING_MODE";
+/// Name of the theme
+pub const THEME: &str = "METYPING_THEME";
+/// Keyboard layout the texts are rated for
+pub const LAYOUT: &str = "METYPING_LAYOUT";
+/// Directory of the history and the progress of all profiles
+pub const DATA_DIR: &str = "METYPING_DATA_DIR";
+
+/// the value of the variable `name`, `None` if unset or empty
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// where the history and the progress of the profiles are kept,
+/// [`DATA_DIR`] or `metyping` in the data directory of the platform
+pub fn data_dir() -> Option<PathBuf> {
+    var(DATA_DIR)
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_dir().map(|dir| dir.join("metyping")))
+}
+
+/// the settings of the config set in the environment
+pub fn overrides() -> Result<Table> {
+    overrides_from(var)
+}
+
+/// the settings of the config in the variables `var` looks up
+fn overrides_from(var: impl Fn(&str) -> Option<String>) -> Result<Table> {
+    let mut table = Table::new();
+    if let Some(mode) = var(MODE) {
+        let mode: Mode = mode.parse().map_err(|error| eyre!("{MODE}: {error}"))?;
+        table.insert("mode".into(), Value::try_from(mode)?);
+    }
+    if let Some(theme) = var(THEME) {
+        table.insert("theme".into(), Value::String(theme));
+    }
+    if let Some(layout) = var(LAYOUT) {
+        let layout =
+            KeyboardLayout::from_str(&layout, true).map_err(|error| eyre!("{LAYOUT}: {error}"))?;
+        table.insert("layout".into(), Value::try_from(layout)?);
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_the_settings_from_variables() {
+        let vars = |name: &str| match name {
+            MODE => Some("timed 30s".to_string()),
+            LAYOUT => Some("Colemak".to_string()),
+            _ => None,
+        };
+        let table = overrides_from(vars).unwrap();
+        let expected: Table = "mode = { timed = 30 }\nlayout = \"colemak\""
+            .parse()
+            .unwrap();
+        assert_eq!(table, expected);
+
+        let error = overrides_from(|_| Some("sprint".into())).unwrap_err();
+        assert_eq!(error.to_string(), "METYPING_MODE: unknown mode 'sprint'");
+    }
+}
@@ -1,11 +1,13 @@
 use std::panic;
 
-use color_eyre::{config::HookBuilder, eyre};
+use color_eyre::config::HookBuilder;
 
 use crate::tui;
 
-/// This replaces the standard color_eyre panic and error hooks with hooks that
-/// restore the terminal before printing the panic or error.
+/// This replaces the standard color_eyre panic hook with one that restores
+/// the terminal before printing the panic. The error hook runs for every
+/// report made, also those shown as a status, so it leaves the terminal to
+/// the drop of [`tui::Tui`].
 pub fn install_hooks() -> color_eyre::Result<()> {
     let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
 
@@ -16,14 +18,7 @@ pub fn install_hooks() -> color_eyre::Result<()> {
         panic_hook(panic_info);
     }));
 
-    // convert from a color_eyre EyreHook to a eyre ErrorHook
-    let eyre_hook = eyre_hook.into_eyre_hook();
-    eyre::set_hook(Box::new(
-        move |error: &(dyn std::error::Error + 'static)| {
-            let _ = tui::restore(); // ignore any errors as we are already failing
-            eyre_hook(error)
-        },
-    ))?;
+    eyre_hook.install()?;
 
     Ok(())
 }
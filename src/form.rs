@@ -0,0 +1,208 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    style::{Modifier, Style},
+    text::Span,
+};
+
+/// What a key press did to a form field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldEvent {
+    /// the value or the cursor changed
+    Edited,
+    /// the user wants to keep the value
+    Submit,
+    /// the user wants to throw the value away
+    Cancel,
+    /// the key isn't handled by the field
+    Ignored,
+}
+
+/// A single line text field with a cursor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    value: String,
+    /// position of the cursor in characters
+    cursor: usize,
+    /// longest value in characters
+    max_len: Option<usize>,
+}
+
+impl TextInput {
+    /// a field holding `value` with the cursor at its end
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.into(),
+            cursor: value.chars().count(),
+            max_len: None,
+        }
+    }
+
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// byte index of the character at `cursor`
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> FieldEvent {
+        let control = key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key_event.code {
+            KeyCode::Enter => return FieldEvent::Submit,
+            KeyCode::Esc => return FieldEvent::Cancel,
+            KeyCode::Char(c) if !control => {
+                if self.max_len.is_some_and(|max| self.len() >= max) {
+                    return FieldEvent::Ignored;
+                }
+                let i = self.byte_index(self.cursor);
+                self.value.insert(i, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let i = self.byte_index(self.cursor);
+                self.value.remove(i);
+            }
+            KeyCode::Delete if self.cursor < self.len() => {
+                let i = self.byte_index(self.cursor);
+                self.value.remove(i);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            _ => return FieldEvent::Ignored,
+        }
+        FieldEvent::Edited
+    }
+
+    /// the value in `style` with the cursor shown reversed
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        let (before, rest) = self.value.split_at(self.byte_index(self.cursor));
+        let mut chars = rest.chars();
+        let at_cursor = chars.next().map_or(" ".into(), String::from);
+        vec![
+            Span::styled(before.to_string(), style),
+            Span::styled(at_cursor, style.add_modifier(Modifier::REVERSED)),
+            Span::styled(chars.as_str().to_string(), style),
+        ]
+    }
+}
+
+/// A text field that only takes digits of a number in `min..=max`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberInput {
+    input: TextInput,
+    min: u32,
+    max: u32,
+}
+
+impl NumberInput {
+    pub fn new(value: u32, min: u32, max: u32) -> Self {
+        Self {
+            input: TextInput::new(&value.to_string()).max_len(max.to_string().len()),
+            min,
+            max,
+        }
+    }
+
+    /// the number in the field or why it isn't a valid one
+    pub fn value(&self) -> Result<u32, String> {
+        let range = || format!("enter a number from {} to {}", self.min, self.max);
+        let value: u32 = self.input.value().parse().map_err(|_| range())?;
+        if (self.min..=self.max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(range())
+        }
+    }
+
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> FieldEvent {
+        match key_event.code {
+            KeyCode::Char(c) if !c.is_ascii_digit() => FieldEvent::Ignored,
+            _ => self.input.handle_key(key_event),
+        }
+    }
+
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        self.input.spans(style)
+    }
+}
+
+/// Any kind of field, for forms that mix them
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Text(TextInput),
+    Number(NumberInput),
+}
+
+impl Field {
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> FieldEvent {
+        match self {
+            Self::Text(input) => input.handle_key(key_event),
+            Self::Number(input) => input.handle_key(key_event),
+        }
+    }
+
+    pub fn spans(&self, style: Style) -> Vec<Span<'static>> {
+        match self {
+            Self::Text(input) => input.spans(style),
+            Self::Number(input) => input.spans(style),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_text_and_numbers() {
+        let mut input = TextInput::new("héllo").max_len(6);
+        input.handle_key(KeyCode::Left.into());
+        input.handle_key(KeyCode::Backspace.into());
+        assert_eq!(input.value(), "hélo");
+        input.handle_key(KeyCode::Home.into());
+        input.handle_key(KeyCode::Delete.into());
+        input.handle_key(KeyCode::Char('H').into());
+        input.handle_key(KeyCode::Char('!').into());
+        assert_eq!(input.value(), "H!élo");
+        input.handle_key(KeyCode::End.into());
+        input.handle_key(KeyCode::Char('?').into());
+        assert_eq!(
+            input.handle_key(KeyCode::Char('?').into()),
+            FieldEvent::Ignored
+        );
+        assert_eq!(input.value(), "H!élo?");
+        assert_eq!(input.handle_key(KeyCode::Enter.into()), FieldEvent::Submit);
+
+        let mut number = NumberInput::new(2, 1, 20);
+        assert_eq!(
+            number.handle_key(KeyCode::Char('x').into()),
+            FieldEvent::Ignored
+        );
+        number.handle_key(KeyCode::Char('0').into());
+        assert_eq!(number.value(), Ok(20));
+        number.handle_key(KeyCode::Backspace.into());
+        number.handle_key(KeyCode::Backspace.into());
+        assert!(number.value().is_err());
+        number.handle_key(KeyCode::Char('9').into());
+        number.handle_key(KeyCode::Char('9').into());
+        assert_eq!(number.value(), Err("enter a number from 1 to 20".into()));
+    }
+}
@@ -0,0 +1,531 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::{
+    drill::Focus, ngram::NgramModel, phrases::PhraseList, words::WordList, ALPHABET, DIGITS,
+    SPECIALS,
+};
+
+/// Marks the start and end of a word in the markov chain
+const BOUNDARY: char = '\0';
+/// Short quotes, one per line
+const QUOTES: &str = include_str!("../assets/quotes.txt");
+
+/// What a generator should produce for the next round
+pub struct GeneratorConfig<'a> {
+    pub rng: &'a mut dyn RngCore,
+    pub words: &'a WordList,
+    /// number of characters or words, depending on the generator
+    pub count: usize,
+}
+
+/// Produces the text of a round
+pub trait TextGenerator: fmt::Debug {
+    fn next(&mut self, config: GeneratorConfig) -> String;
+
+    /// what is shown above the text of the last round, e.g. the phrase it
+    /// translates
+    fn prompt(&self) -> Option<&str> {
+        None
+    }
+
+    /// starts over for a new session
+    fn reset(&mut self) {}
+}
+
+/// Random letters, `count` of them
+#[derive(Debug, Default)]
+pub struct Chars;
+
+impl TextGenerator for Chars {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        (0..config.count)
+            .map(|_| ALPHABET[config.rng.gen_range(0..ALPHABET.len())])
+            .collect()
+    }
+}
+
+/// `count` random words of the word list
+#[derive(Debug, Default)]
+pub struct Words {
+    /// the words of the session so far, with [`crate::words::WordFilter::no_repeats`]
+    used: HashSet<String>,
+}
+
+impl TextGenerator for Words {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        if config.words.filter.no_repeats {
+            return config
+                .words
+                .sample_unused(config.rng, config.count, &mut self.used);
+        }
+        config.words.sample(config.rng, config.count)
+    }
+
+    fn reset(&mut self) {
+        self.used.clear();
+    }
+}
+
+/// The words of a given text in order, `count` per round, starting over at
+/// the end. Random words of the list until there is a text.
+#[derive(Debug, Default)]
+pub struct Text {
+    words: Vec<String>,
+    /// index of the first word of the next round
+    next: usize,
+}
+
+impl Text {
+    /// splits `text` into words, so line breaks and runs of spaces turn into
+    /// single spaces
+    pub fn new(text: &str) -> Self {
+        Self {
+            words: text.split_whitespace().map(String::from).collect(),
+            next: 0,
+        }
+    }
+}
+
+impl TextGenerator for Text {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        if self.words.is_empty() {
+            return config.words.sample(config.rng, config.count);
+        }
+        let end = (self.next + config.count).min(self.words.len());
+        let round = self.words[self.next..end].join(" ");
+        self.next = if end == self.words.len() { 0 } else { end };
+        round
+    }
+}
+
+/// `count` words training the weak spots of the history, see [`Focus`]
+#[derive(Debug, Default)]
+pub struct Drill(Focus);
+
+impl Drill {
+    pub fn new(focus: Focus) -> Self {
+        Self(focus)
+    }
+}
+
+impl TextGenerator for Drill {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        self.0.words(config.words, config.rng, config.count)
+    }
+}
+
+/// `count` made up words of the letters unlocked in the lessons, each with
+/// the letter in focus. Random words of the list until there are letters.
+#[derive(Debug, Default)]
+pub struct Lesson {
+    letters: Vec<char>,
+    focus: Option<char>,
+    /// letter frequencies of the word list, the words read like its language
+    model: NgramModel,
+}
+
+impl Lesson {
+    /// shortest and longest made up word
+    const LEN: (usize, usize) = (3, 6);
+    /// words made up to get one with the letter in focus before it's put in
+    const TRIES: usize = 5;
+
+    pub fn new(letters: Vec<char>, focus: Option<char>) -> Self {
+        Self {
+            letters,
+            focus,
+            model: NgramModel::default(),
+        }
+    }
+
+    fn word(&self, rng: &mut dyn RngCore) -> String {
+        let mut word = String::new();
+        for _ in 0..Self::TRIES {
+            word = self.model.word(rng, &self.letters, Self::LEN);
+            if self.focus.is_none_or(|focus| word.contains(focus)) {
+                return word;
+            }
+        }
+        let mut word: Vec<char> = word.chars().collect();
+        if let Some(focus) = self.focus {
+            let i = rng.gen_range(0..word.len());
+            word[i] = focus;
+        }
+        word.into_iter().collect()
+    }
+}
+
+impl TextGenerator for Lesson {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        if self.letters.is_empty() {
+            return config.words.sample(config.rng, config.count);
+        }
+        if self.model.list != config.words.label() {
+            self.model = NgramModel::build(config.words);
+        }
+        (0..config.count)
+            .map(|_| self.word(config.rng))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A random quote per round, `count` is ignored
+#[derive(Debug)]
+pub struct Quotes(Vec<&'static str>);
+
+impl Default for Quotes {
+    fn default() -> Self {
+        Self(
+            QUOTES
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect(),
+        )
+    }
+}
+
+impl TextGenerator for Quotes {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        self.0
+            .choose(config.rng)
+            .copied()
+            .unwrap_or_default()
+            .into()
+    }
+}
+
+/// `n` random digits
+fn digits(rng: &mut dyn RngCore, n: usize) -> String {
+    (0..n)
+        .map(|_| DIGITS[rng.gen_range(0..DIGITS.len())])
+        .collect()
+}
+
+/// a random word of the list
+fn word(rng: &mut dyn RngCore, words: &WordList) -> String {
+    words
+        .words
+        .choose(rng)
+        .cloned()
+        .unwrap_or_else(|| "x".into())
+}
+
+/// `count` numbers the way they show up in real text: phone numbers,
+/// prices, percentages, dates and plain numbers
+#[derive(Debug, Default)]
+pub struct Numbers;
+
+impl TextGenerator for Numbers {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        let rng = config.rng;
+        (0..config.count)
+            .map(|_| {
+                let n = rng.gen_range(1..=4);
+                match rng.gen_range(0..5) {
+                    0 => format!("{}-{}", digits(rng, 3), digits(rng, 4)),
+                    1 => format!("${}.{}", digits(rng, n), digits(rng, 2)),
+                    2 => format!("{}%", digits(rng, n.min(2))),
+                    3 => format!(
+                        "{}-{:02}-{:02}",
+                        rng.gen_range(1970..2040),
+                        rng.gen_range(1..=12),
+                        rng.gen_range(1..=28)
+                    ),
+                    _ => digits(rng, n + 1),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// `count` bits of code-like text: calls, indexing, operators and runs of
+/// symbols
+#[derive(Debug, Default)]
+pub struct Symbols;
+
+impl TextGenerator for Symbols {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        const OPERATORS: [&str; 10] = ["==", "!=", "->", "=>", "&&", "||", "+=", "<=", "::", ".."];
+        let rng = config.rng;
+        let words = config.words;
+        (0..config.count)
+            .map(|_| {
+                let (a, b) = (word(rng, words), word(rng, words));
+                match rng.gen_range(0..5) {
+                    0 => format!("{a}({b});"),
+                    1 => format!("{a}[{}]", digits(rng, 1)),
+                    2 => format!("{a} {} {b}", OPERATORS.choose(rng).unwrap_or(&"==")),
+                    3 => format!("{{{a}: \"{b}\"}}"),
+                    _ => (0..rng.gen_range(2..=4))
+                        .map(|_| SPECIALS[rng.gen_range(0..SPECIALS.len())])
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// `count` pseudo-words from a markov chain over the letter pairs of the
+/// word list, they read like real words but can't be typed from memory
+#[derive(Debug, Default)]
+pub struct Markov {
+    /// name of the word list the chain was built from
+    list: String,
+    /// the letters following each pair of letters
+    chain: HashMap<(char, char), Vec<char>>,
+}
+
+impl Markov {
+    /// longest pseudo-word, keeps rare loops in the chain from running away
+    const MAX_LEN: usize = 12;
+
+    fn build(words: &WordList) -> HashMap<(char, char), Vec<char>> {
+        let mut chain: HashMap<(char, char), Vec<char>> = HashMap::new();
+        for word in &words.words {
+            let mut state = (BOUNDARY, BOUNDARY);
+            for c in word.chars().chain([BOUNDARY]) {
+                chain.entry(state).or_default().push(c);
+                state = (state.1, c);
+            }
+        }
+        chain
+    }
+
+    fn word(&self, rng: &mut dyn RngCore) -> String {
+        let mut word = String::new();
+        let mut state = (BOUNDARY, BOUNDARY);
+        while word.len() < Self::MAX_LEN {
+            match self.chain.get(&state).and_then(|next| next.choose(rng)) {
+                Some(&c) if c != BOUNDARY => {
+                    word.push(c);
+                    state = (state.1, c);
+                }
+                _ => break,
+            }
+        }
+        word
+    }
+}
+
+impl TextGenerator for Markov {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        if self.list != config.words.name || self.chain.is_empty() {
+            self.list.clone_from(&config.words.name);
+            self.chain = Self::build(config.words);
+        }
+        (0..config.count)
+            .map(|_| self.word(config.rng))
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// `count` made up words of 3 to 8 letters, with the letter frequencies
+/// of the word list so they can be pronounced in its language
+#[derive(Debug, Default)]
+pub struct Ngram(NgramModel);
+
+impl Ngram {
+    /// shortest and longest made up word
+    const LEN: (usize, usize) = (3, 8);
+}
+
+impl TextGenerator for Ngram {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        if self.0.list != config.words.label() {
+            self.0 = NgramModel::build(config.words);
+        }
+        let letters = self.0.letters();
+        (0..config.count)
+            .map(|_| self.0.word(config.rng, &letters, Self::LEN))
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The translation of a random phrase of the list per round, `count` is
+/// ignored. The phrase is the prompt.
+#[derive(Debug, Default)]
+pub struct Translate {
+    phrases: PhraseList,
+    /// index of the phrase of the last round
+    current: Option<usize>,
+}
+
+impl Translate {
+    pub fn new(phrases: PhraseList) -> Self {
+        Self {
+            phrases,
+            current: None,
+        }
+    }
+}
+
+impl TextGenerator for Translate {
+    fn next(&mut self, config: GeneratorConfig) -> String {
+        let len = self.phrases.phrases.len();
+        // not the same phrase twice in a row
+        let mut i = config.rng.gen_range(0..len);
+        if Some(i) == self.current && len > 1 {
+            i = (i + 1) % len;
+        }
+        self.current = Some(i);
+        self.phrases.phrases[i].translation.clone()
+    }
+
+    fn prompt(&self) -> Option<&str> {
+        self.current
+            .map(|i| self.phrases.phrases[i].source.as_str())
+    }
+}
+
+/// The available generators by name
+#[derive(Debug)]
+pub struct Generators(BTreeMap<String, Box<dyn TextGenerator>>);
+
+impl Default for Generators {
+    fn default() -> Self {
+        let mut generators = Self(BTreeMap::new());
+        generators.register("chars", Chars);
+        generators.register("words", Words::default());
+        generators.register("markov", Markov::default());
+        generators.register("ngram", Ngram::default());
+        generators.register("numbers", Numbers);
+        generators.register("symbols", Symbols);
+        generators.register("text", Text::default());
+        generators.register("quotes", Quotes::default());
+        generators.register("drill", Drill::default());
+        generators.register("lesson", Lesson::default());
+        generators.register("translate", Translate::default());
+        generators
+    }
+}
+
+impl Generators {
+    /// adds `generator` under `name`, replacing any generator of that name
+    pub fn register(&mut self, name: &str, generator: impl TextGenerator + 'static) {
+        self.0.insert(name.into(), Box::new(generator));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn TextGenerator> {
+        self.0.get(name).map(|g| g.as_ref())
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut (dyn TextGenerator + 'static)> {
+        self.0.get_mut(name).map(|g| g.as_mut())
+    }
+
+    /// replaces the generators of `names` with those of `other`
+    pub fn adopt(&mut self, mut other: Generators, names: &[&str]) {
+        for name in names {
+            if let Some(generator) = other.0.remove(*name) {
+                self.0.insert((*name).into(), generator);
+            }
+        }
+    }
+
+    /// starts every generator over for a new session
+    pub fn reset(&mut self) {
+        self.0.values_mut().for_each(|generator| generator.reset());
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markov_words_follow_the_list() {
+        let words = WordList::from_text("test", "abc abd");
+        let mut markov = Markov::default();
+        let text = markov.next(GeneratorConfig {
+            rng: &mut rand::thread_rng(),
+            words: &words,
+            count: 20,
+        });
+        let generated: Vec<&str> = text.split(' ').collect();
+        assert_eq!(generated.len(), 20);
+        assert!(generated.iter().all(|w| *w == "abc" || *w == "abd"));
+
+        let mut generators = Generators::default();
+        assert_eq!(
+            generators.names(),
+            vec![
+                "chars",
+                "drill",
+                "lesson",
+                "markov",
+                "ngram",
+                "numbers",
+                "quotes",
+                "symbols",
+                "text",
+                "translate",
+                "words"
+            ]
+        );
+        assert!(generators.get("pseudo").is_none());
+        assert!(generators.get_mut("markov").is_some());
+    }
+
+    #[test]
+    fn lesson_words_use_the_unlocked_letters() {
+        let words = WordList::from_text("test", "abc");
+        let mut lesson = Lesson::new(vec!['e', 't', 'a', 'o', 'n'], Some('n'));
+        let text = lesson.next(GeneratorConfig {
+            rng: &mut rand::thread_rng(),
+            words: &words,
+            count: 20,
+        });
+        let generated: Vec<&str> = text.split(' ').collect();
+        assert_eq!(generated.len(), 20);
+        assert!(generated
+            .iter()
+            .all(|w| w.contains('n') && w.chars().all(|c| "etaon".contains(c))));
+    }
+
+    #[test]
+    fn text_in_rounds() {
+        let words = WordList::from_text("test", "abc");
+        let mut text = Text::new("one  two\nthree\tfour five");
+        let mut next = |count| {
+            text.next(GeneratorConfig {
+                rng: &mut rand::thread_rng(),
+                words: &words,
+                count,
+            })
+        };
+        assert_eq!(next(2), "one two");
+        assert_eq!(next(2), "three four");
+        assert_eq!(next(2), "five");
+        assert_eq!(next(3), "one two three");
+
+        let mut generators = Generators::default();
+        generators.register("text", Text::new("mine"));
+        let mut other = Generators::default();
+        other.register("text", Text::new("theirs"));
+        generators.adopt(other, &["text", "unknown"]);
+        let text = generators.get_mut("text").unwrap().next(GeneratorConfig {
+            rng: &mut rand::thread_rng(),
+            words: &words,
+            count: 1,
+        });
+        assert_eq!(text, "theirs");
+        assert_eq!(generators.names().len(), 11);
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Keystrokes a burst is measured over
+const BURST_KEYS: usize = 12;
+/// Speed no human sustains over [`BURST_KEYS`] keystrokes
+const MAX_BURST_WPM: f64 = 300.0;
+
+/// Why a session isn't recorded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flag {
+    /// text was pasted into the test
+    Paste,
+    /// keystrokes came in faster than anyone types
+    Burst,
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Paste => write!(f, "text was pasted"),
+            Self::Burst => write!(f, "keystrokes faster than {MAX_BURST_WPM} WPM"),
+        }
+    }
+}
+
+/// Watches the input of a session for pastes and keystrokes too fast to be
+/// typed, a flagged session is practice only
+#[derive(Debug, Default)]
+pub struct InputGuard {
+    keys: VecDeque<Instant>,
+    flag: Option<Flag>,
+}
+
+impl InputGuard {
+    /// records a keystroke at `now`, flags the session if it completes a
+    /// burst
+    pub fn keystroke(&mut self, now: Instant) {
+        self.keys.push_back(now);
+        if self.keys.len() > BURST_KEYS {
+            self.keys.pop_front();
+        }
+        if let (BURST_KEYS, Some(first)) = (self.keys.len(), self.keys.front()) {
+            // BURST_KEYS keystrokes span BURST_KEYS - 1 intervals
+            let chars = (BURST_KEYS - 1) as f64;
+            let fastest = Duration::from_secs_f64(chars / 5.0 / MAX_BURST_WPM * 60.0);
+            if now.duration_since(*first) < fastest {
+                self.flag.get_or_insert(Flag::Burst);
+            }
+        }
+    }
+
+    pub fn paste(&mut self) {
+        self.flag = Some(Flag::Paste);
+    }
+
+    /// why the session must not be recorded, `None` if it may
+    pub fn flag(&self) -> Option<Flag> {
+        self.flag
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_bursts_and_pastes() {
+        let start = Instant::now();
+        let mut guard = InputGuard::default();
+        // 100 ms per key is 120 WPM
+        for i in 0..30 {
+            guard.keystroke(start + Duration::from_millis(i * 100));
+        }
+        assert_eq!(guard.flag(), None);
+
+        // pasted without bracketed paste, all keys at once
+        let later = start + Duration::from_secs(10);
+        for _ in 0..BURST_KEYS {
+            guard.keystroke(later);
+        }
+        assert_eq!(guard.flag(), Some(Flag::Burst));
+
+        guard.reset();
+        guard.paste();
+        assert_eq!(guard.flag(), Some(Flag::Paste));
+    }
+}
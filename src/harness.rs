@@ -0,0 +1,158 @@
+//! Drives the app with key presses at chosen times and draws it to a
+//! buffer, to test what ends up on the screen. Snapshots of the screen are
+//! stored in `src/snapshots`, run the tests with `UPDATE_SNAPSHOTS=1` to
+//! write them after a change to the drawing.
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use ratatui::{backend::TestBackend, crossterm::event::KeyCode, Terminal};
+
+use crate::App;
+
+pub struct Harness<'a> {
+    pub app: App<'a>,
+    terminal: Terminal<TestBackend>,
+    /// the time of the next key press
+    pub now: Instant,
+}
+
+impl<'a> Harness<'a> {
+    /// starts a session of `app` on a terminal of `width` x `height`
+    pub fn new(mut app: App<'a>, width: u16, height: u16) -> Self {
+        // the wall clock would change every snapshot
+        app.config.header.show_clock = false;
+        // and the animations would catch the screen in motion
+        app.config.accessibility.reduced_motion = true;
+        app.new_session().unwrap();
+        Self {
+            app,
+            terminal: Terminal::new(TestBackend::new(width, height)).unwrap(),
+            now: Instant::now(),
+        }
+    }
+
+    /// lets `duration` pass on the clock of the app
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.now += duration;
+        self.app.tick(self.now).unwrap();
+        self
+    }
+
+    pub fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.app.handle_key_event_at(code.into(), self.now).unwrap();
+        self
+    }
+
+    /// types `text` with `interval` between the keys
+    pub fn type_text(&mut self, text: &str, interval: Duration) -> &mut Self {
+        for c in text.chars() {
+            self.wait(interval).press(KeyCode::Char(c));
+        }
+        self
+    }
+
+    /// types the rest of the current round
+    pub fn finish_round(&mut self, interval: Duration) -> &mut Self {
+        let rest = self.app.remainder.span.content.to_string();
+        self.type_text(&rest, interval)
+    }
+
+    /// the screen at the current time, one line per row without trailing
+    /// spaces
+    pub fn screen(&mut self) -> String {
+        self.app.draw_time = Some(self.now);
+        let frame = self
+            .terminal
+            .draw(|frame| self.app.render_frame(frame))
+            .unwrap();
+        self.app.area = frame.area;
+        let buf = self.terminal.backend().buffer();
+        (0..buf.area.height)
+            .map(|y| {
+                let line: String = (0..buf.area.width)
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// compares the screen to the snapshot `name`
+    pub fn assert_snapshot(&mut self, name: &str) {
+        let screen = self.screen() + "\n";
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/snapshots")
+            .join(format!("{name}.txt"));
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, screen).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "no snapshot {}, run with UPDATE_SNAPSHOTS=1 to write it",
+                path.display()
+            )
+        });
+        assert!(
+            screen == expected,
+            "the screen differs from {}:\n{screen}",
+            path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mode, Screen};
+
+    /// time between two keys, 120 WPM
+    const KEY_INTERVAL: Duration = Duration::from_millis(100);
+
+    fn harness(mode: Mode) -> Harness<'static> {
+        let mut app = App {
+            mode,
+            ..Default::default()
+        }
+        .with_seed(7);
+        app.config.session.rounds = 2;
+        Harness::new(app, 80, 24)
+    }
+
+    #[test]
+    fn draw_a_session_from_start_to_results() {
+        let mut h = harness(Mode::Words(3));
+        h.assert_snapshot("typing_start");
+
+        h.finish_round(KEY_INTERVAL).type_text("x", KEY_INTERVAL);
+        h.assert_snapshot("typing_second_round");
+
+        h.press(KeyCode::Backspace).finish_round(KEY_INTERVAL);
+        assert_eq!(h.app.screen, Screen::Results);
+        h.assert_snapshot("results");
+    }
+
+    #[test]
+    fn draw_the_countdown_and_the_time_left() {
+        let mut h = harness(Mode::Timed(30));
+        assert!(h.screen().contains("press Space to start"));
+
+        h.press(KeyCode::Char(' '));
+        assert!(h.screen().contains('3'));
+        h.wait(Duration::from_secs(2));
+        assert!(!h.screen().contains("press Space"));
+        assert!(h.screen().contains('1'));
+
+        h.wait(Duration::from_secs(1)).type_text("ab", KEY_INTERVAL);
+        h.wait(Duration::from_secs(10));
+        assert!(h.screen().contains("20s left"));
+        h.wait(Duration::from_secs(20));
+        assert_eq!(h.app.screen, Screen::Results);
+    }
+}
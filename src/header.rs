@@ -0,0 +1,288 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use color_eyre::Result;
+
+use crate::{
+    config::{QuitProtection, Strict},
+    confirm::ConfirmAction,
+    App, Mode, Screen,
+};
+
+/// Separator between the parts of the header
+const SEPARATOR: &str = " │ ";
+/// Columns of the `‹ ` and `› ` marking hidden tabs
+const TAB_MARKERS: u16 = 4;
+
+impl App<'_> {
+    /// the modes selectable from the header tabs, keeping the options of the
+    /// current and configured mode
+    fn tab_modes(&self) -> [Mode; 8] {
+        let chars = match (self.mode, self.config.mode) {
+            (Mode::Chars(n), _) | (_, Mode::Chars(n)) => n,
+            _ => 5,
+        };
+        let words = match (self.mode, self.config.mode) {
+            (Mode::Words(n), _) | (_, Mode::Words(n)) => n,
+            _ => 10,
+        };
+        let race = match (self.mode, self.config.mode) {
+            (Mode::Race(n), _) | (_, Mode::Race(n)) => n,
+            _ => 15,
+        };
+        let timed = match (self.mode, self.config.mode) {
+            (Mode::Timed(secs), _) | (_, Mode::Timed(secs)) => secs,
+            _ => 30,
+        };
+        let numbers = match (self.mode, self.config.mode) {
+            (Mode::Numbers(n), _) | (_, Mode::Numbers(n)) => n,
+            _ => 10,
+        };
+        let symbols = match (self.mode, self.config.mode) {
+            (Mode::Symbols(n), _) | (_, Mode::Symbols(n)) => n,
+            _ => 10,
+        };
+        [
+            Mode::Random,
+            Mode::Chars(chars),
+            Mode::Words(words),
+            Mode::Quote,
+            Mode::Race(race),
+            Mode::Timed(timed),
+            Mode::Numbers(numbers),
+            Mode::Symbols(symbols),
+        ]
+    }
+
+    /// switches to `mode` and starts over, asking first if that loses the
+    /// test in progress
+    pub(crate) fn switch_mode(&mut self, mode: Mode) -> Result<()> {
+        if self.test_in_progress() && self.config.quit_protection != QuitProtection::Off {
+            self.confirm(
+                "Start over and lose this test?",
+                ConfirmAction::Discard(mode),
+            );
+            return Ok(());
+        }
+        self.mode = mode;
+        self.new_session()
+    }
+
+    /// switches to the mode of the tab at `index`, counted from 0
+    pub(crate) fn select_tab(&mut self, index: usize) -> Result<()> {
+        match self.tab_modes().get(index) {
+            Some(mode) => self.switch_mode(*mode),
+            None => Ok(()),
+        }
+    }
+
+    /// switches to the tab `offset` tabs right of the current one, wrapping
+    /// around. Modes without a tab go to the first one.
+    pub(crate) fn next_tab(&mut self, offset: isize) -> Result<()> {
+        let tabs = self.tab_modes();
+        let index = match tabs.iter().position(|m| m.name() == self.mode.name()) {
+            Some(current) => (current as isize + offset).rem_euclid(tabs.len() as isize),
+            None => 0,
+        };
+        self.select_tab(index as usize)
+    }
+
+    /// everything left of the mode tabs
+    fn header_prefix(&self) -> String {
+        let header = &self.config.header;
+        let mut parts = vec![header.title.clone()];
+        if header.show_profile {
+            parts.push(format!("profile: {}", self.config.profile));
+        }
+        if self.config.practice {
+            parts.push("practice".into());
+        }
+        match self.config.strict {
+            Strict::Off => {}
+            Strict::Round => parts.push("strict round".into()),
+            Strict::Session => parts.push("strict session".into()),
+        }
+        format!(" {}{SEPARATOR}", parts.join(SEPARATOR))
+    }
+
+    /// everything right of the mode tabs: the session time or the way back,
+    /// and the clock. It keeps its width, the tabs make room for it.
+    fn header_status(&self) -> String {
+        let header = &self.config.header;
+        // nested screens show the way back instead of the session time
+        let mut status = if self.screen_stack.is_empty() {
+            self.header_timer(self.draw_time())
+        } else {
+            self.breadcrumb()
+        };
+        if header.show_clock {
+            status.push_str(SEPARATOR);
+            let now = self.config.timezone.datetime(Utc::now());
+            status.push_str(&now.format(&header.clock_format).to_string());
+        }
+        format!("{status} ")
+    }
+
+    /// the mode tabs and their clickable areas inside of the header `area`
+    pub(crate) fn header_tabs(&self, area: Rect) -> Vec<(Mode, Rect)> {
+        self.tab_window(area).0
+    }
+
+    /// the tabs around the current one that fit between the prefix and the
+    /// status, and whether tabs are hidden to the left and to the right
+    fn tab_window(&self, area: Rect) -> (Vec<(Mode, Rect)>, bool, bool) {
+        // stacked screens have no mode to switch, the breadcrumb takes the room
+        if !self.config.header.show_mode || !self.screen_stack.is_empty() {
+            return (vec![], false, false);
+        }
+
+        let modes = self.tab_modes();
+        let widths: Vec<u16> = modes
+            .iter()
+            .map(|mode| self.tab_label(*mode).chars().count() as u16 + 1)
+            .collect();
+        let start = area.x + self.header_prefix().chars().count() as u16;
+        let status = self.header_status().chars().count() as u16;
+        let space = area.right().saturating_sub(status).saturating_sub(start);
+        let (mut first, mut last) = (0, modes.len());
+        if widths.iter().sum::<u16>() > space {
+            // grow from the current tab, leaving room for the markers
+            let space = space.saturating_sub(TAB_MARKERS);
+            let current = modes
+                .iter()
+                .position(|mode| mode.name() == self.mode.name())
+                .unwrap_or(0);
+            (first, last) = (current, current + 1);
+            let mut used = widths[current];
+            // one to the right, one to the left, while they fit
+            let mut grown = true;
+            while grown {
+                grown = false;
+                if last < modes.len() && used + widths[last] <= space {
+                    used += widths[last];
+                    last += 1;
+                    grown = true;
+                }
+                if first > 0 && used + widths[first - 1] <= space {
+                    first -= 1;
+                    used += widths[first];
+                    grown = true;
+                }
+            }
+        }
+
+        let mut x = start + if first > 0 { TAB_MARKERS / 2 } else { 0 };
+        let tabs = (first..last)
+            .map(|i| {
+                let tab = Rect::new(x, area.y, widths[i] - 1, 1).intersection(area);
+                x += widths[i];
+                (modes[i], tab)
+            })
+            .collect();
+        (tabs, first > 0, last < modes.len())
+    }
+
+    /// the active tab shows the options of the mode too
+    fn tab_label(&self, mode: Mode) -> String {
+        if mode.name() == self.mode.name() {
+            format!(" {} ", self.mode)
+        } else {
+            format!(" {} ", mode.name())
+        }
+    }
+
+    /// time left of a timed session, otherwise the time since the session
+    /// started
+    pub(crate) fn header_timer(&self, now: Instant) -> String {
+        let time = match self.time_limit() {
+            Some(limit) => self.session.time_left(limit, now),
+            None => self.session.elapsed(now).unwrap_or(Duration::ZERO),
+        };
+        let secs = time.as_secs_f64().ceil() as u64;
+        let suffix = if self.time_limit().is_some() {
+            " left"
+        } else {
+            ""
+        };
+        let time = format!("{}:{:02}{suffix}", secs / 60, secs % 60);
+        match self.round_limit() {
+            Some(limit) => {
+                let round = (self.rounds_played() + 1).min(limit);
+                format!("round {round}/{limit}  {time}")
+            }
+            None => time,
+        }
+    }
+
+    /// the screens Esc goes back through, e.g. `results › stats`
+    pub(crate) fn breadcrumb(&self) -> String {
+        self.screen_stack
+            .iter()
+            .chain([&self.screen])
+            .map(Screen::name)
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+
+    pub(crate) fn render_header(&self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![Span::raw(self.header_prefix())];
+        let (tabs, left, right) = self.tab_window(area);
+        if left {
+            spans.push(Span::raw("‹ "));
+        }
+        for (mode, _) in tabs {
+            let style = if mode.name() == self.mode.name() {
+                Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+            } else {
+                Style::new()
+            };
+            spans.push(Span::styled(self.tab_label(mode), style));
+            spans.push(Span::raw(" "));
+        }
+        if right {
+            spans.push(Span::raw("› "));
+        }
+
+        let status = self.header_status();
+        let used: usize = spans.iter().map(|s| s.width()).sum();
+        let fill = (area.width as usize).saturating_sub(used + status.chars().count());
+        spans.push(Span::raw(format!("{}{status}", " ".repeat(fill))));
+
+        Paragraph::new(Line::from(spans))
+            .style(self.theme.header)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn show_the_current_tab_in_a_narrow_header() {
+        let mut app = App::default();
+        app.config.header.show_clock = false;
+        let area = Rect::new(0, 0, 80, 1);
+        for _ in app.tab_modes() {
+            let tabs = app.header_tabs(area);
+            assert!(tabs.iter().any(|(mode, _)| mode.name() == app.mode.name()));
+            assert!(tabs.iter().all(|(_, tab)| tab.right() < 80 - 4));
+            app.next_tab(1).unwrap();
+        }
+        let mut buf = Buffer::empty(area);
+        app.render_header(area, &mut buf);
+        let line: String = (0..80).map(|x| buf.get(x, 0).symbol()).collect();
+        assert!(
+            line.contains(" random ") && line.contains("›") && line.ends_with("0:00 "),
+            "{line}"
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::WrapErr, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    env, profile,
+    score::ScoreFormula,
+    stats::{BigramLatencies, ClassTallies, KeyLatencies, RoundRecord, SessionStats},
+    Mode,
+};
+
+/// Name of the history file inside the data directory
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// The result of a finished session as stored in the history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub mode: Mode,
+    /// the word list of modes that practice words
+    #[serde(default)]
+    pub word_list: Option<String>,
+    pub rounds: usize,
+    pub chars: usize,
+    pub misses: usize,
+    /// misses left wrong in the output, see [`RoundRecord::uncorrected`]
+    #[serde(default)]
+    pub uncorrected: usize,
+    pub duration_ms: u64,
+    pub wpm: f64,
+    pub normalized_wpm: f64,
+    /// speed counting the missed keystrokes too, 0 in older entries
+    #[serde(default)]
+    pub raw_wpm: f64,
+    /// see [`crate::stats::consistency`], 0 in older entries
+    #[serde(default)]
+    pub consistency: f64,
+    /// keystrokes per class of characters
+    #[serde(default)]
+    pub classes: ClassTallies,
+    /// time to press each key, see [`KeyLatencies`]
+    #[serde(default)]
+    pub latencies: KeyLatencies,
+    /// time to press the second key of each pair of keys inside a word
+    #[serde(default)]
+    pub bigrams: BigramLatencies,
+    /// the number of misses of each expected key
+    #[serde(default)]
+    pub key_misses: BTreeMap<char, usize>,
+    /// speed of each round in WPM
+    #[serde(default)]
+    pub curve: Vec<f64>,
+    /// labels given in the history browser
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: String,
+    /// the item of the training plan the session was run for
+    #[serde(default)]
+    pub plan: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(stats: &SessionStats, mode: Mode, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            mode,
+            word_list: None,
+            rounds: stats.rounds.len(),
+            chars: stats.chars(),
+            misses: stats.misses(),
+            uncorrected: stats.uncorrected(),
+            duration_ms: stats.duration().as_millis() as u64,
+            wpm: stats.wpm(),
+            normalized_wpm: stats.normalized_wpm(),
+            raw_wpm: stats.raw_wpm(),
+            consistency: stats.consistency(),
+            classes: stats.classes(),
+            latencies: stats.latencies(),
+            bigrams: stats.bigrams(),
+            key_misses: stats.key_misses(),
+            curve: stats.rounds.iter().map(RoundRecord::wpm).collect(),
+            tags: vec![],
+            note: String::new(),
+            plan: None,
+        }
+    }
+
+    /// mode and word list, results are only comparable within a category
+    pub fn category(&self) -> String {
+        match &self.word_list {
+            Some(list) => format!("{}, {list}", self.mode),
+            None => self.mode.to_string(),
+        }
+    }
+
+    /// one line with the mode, speed, accuracy and duration
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {:.1} WPM, {:.1}% accuracy, {:.1}s",
+            self.category(),
+            self.wpm,
+            self.accuracy() * 100.0,
+            self.duration_ms as f64 / 1000.0
+        )
+    }
+
+    /// recomputes what the entry derives from its measurements, the speeds
+    /// from the keystrokes and the time and the consistency from the key
+    /// latencies. The measurements themselves, the misses per key, the
+    /// latencies and the bigrams, are kept as they are. `true` if anything
+    /// changed.
+    pub fn recompute(&mut self) -> bool {
+        let duration = Duration::from_millis(self.duration_ms);
+        let wpm = crate::stats::wpm(self.chars, duration);
+        let keystrokes = (self.chars + self.misses).saturating_sub(self.uncorrected);
+        let raw_wpm = crate::stats::wpm(keystrokes, duration);
+        // the difficulty of the rounds is only kept as the ratio of the
+        // normalized speed to the speed, lost if the speed was 0
+        let normalized_wpm = if self.wpm > 0.0 && wpm != self.wpm {
+            self.normalized_wpm / self.wpm * wpm
+        } else {
+            self.normalized_wpm
+        };
+        // older entries have no latencies to tell it from
+        let intervals: Vec<f64> = self.latencies.samples().map(f64::from).collect();
+        let consistency = if intervals.is_empty() {
+            self.consistency
+        } else {
+            crate::stats::consistency(&intervals)
+        };
+        let derived = (wpm, raw_wpm, normalized_wpm, consistency);
+        let changed = derived
+            != (
+                self.wpm,
+                self.raw_wpm,
+                self.normalized_wpm,
+                self.consistency,
+            );
+        (
+            self.wpm,
+            self.raw_wpm,
+            self.normalized_wpm,
+            self.consistency,
+        ) = derived;
+        changed
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        crate::stats::accuracy(self.chars, self.misses)
+    }
+
+    /// misses that were typed over, the expected key was pressed after them
+    pub fn corrected(&self) -> usize {
+        self.misses.saturating_sub(self.uncorrected)
+    }
+
+    /// the speed on the final output, without the characters left wrong
+    pub fn output_wpm(&self) -> f64 {
+        crate::stats::wpm(
+            self.chars.saturating_sub(self.uncorrected),
+            Duration::from_millis(self.duration_ms),
+        )
+    }
+
+    /// misses per 100 words of 5 characters
+    pub fn errors_per_100_words(&self) -> f64 {
+        if self.chars == 0 {
+            return 0.0;
+        }
+        self.misses as f64 * 500.0 / self.chars as f64
+    }
+
+    pub fn score(&self, formula: ScoreFormula) -> f64 {
+        let minutes = self.duration_ms as f64 / 60_000.0;
+        let per_minute = |count: usize| {
+            if minutes > 0.0 {
+                count as f64 / minutes
+            } else {
+                0.0
+            }
+        };
+        formula.score(
+            self.wpm,
+            self.accuracy(),
+            per_minute(self.misses),
+            per_minute(self.uncorrected),
+        )
+    }
+}
+
+/// File formats the history can be exported to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/// All past sessions, oldest first. Stored as one JSON document per line so
+/// appending a session never rewrites the file.
+#[derive(Debug, Default)]
+pub struct History {
+    /// `None` keeps the history in memory only
+    path: Option<PathBuf>,
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Directory holding the history file of `profile`, `None` if the
+    /// platform has no data directory
+    pub fn dir(profile: &str) -> Option<PathBuf> {
+        env::data_dir().map(|dir| profile::dir(&dir, profile))
+    }
+
+    /// Loads the history of `profile` from the default location
+    pub fn load(profile: &str) -> Result<Self> {
+        match Self::dir(profile) {
+            Some(dir) => Self::open(dir.join(HISTORY_FILE)),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the history stored at `path`, a missing file is an empty history
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let mut entries = vec![];
+        if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+            for (i, line) in raw
+                .lines()
+                .enumerate()
+                .filter(|(_, l)| !l.trim().is_empty())
+            {
+                let entry = serde_json::from_str(line)
+                    .wrap_err_with(|| format!("parsing {}:{} failed", path.display(), i + 1))?;
+                entries.push(entry);
+            }
+        }
+        Ok(Self {
+            path: Some(path),
+            entries,
+        })
+    }
+
+    /// keystrokes per class of characters over all sessions
+    pub fn classes(&self) -> ClassTallies {
+        let mut classes = ClassTallies::default();
+        self.entries.iter().for_each(|e| classes.merge(&e.classes));
+        classes
+    }
+
+    /// time to press each key over all sessions
+    pub fn latencies(&self) -> KeyLatencies {
+        let mut latencies = KeyLatencies::default();
+        self.entries
+            .iter()
+            .for_each(|e| latencies.merge(&e.latencies));
+        latencies
+    }
+
+    /// misses of each key over all sessions
+    pub fn key_misses(&self) -> BTreeMap<char, usize> {
+        let mut misses = BTreeMap::new();
+        for (key, count) in self.entries.iter().flat_map(|e| &e.key_misses) {
+            *misses.entry(*key).or_default() += count;
+        }
+        misses
+    }
+
+    /// time to press each pair of keys over all sessions
+    pub fn bigrams(&self) -> BigramLatencies {
+        let mut bigrams = BigramLatencies::default();
+        self.entries.iter().for_each(|e| bigrams.merge(&e.bigrams));
+        bigrams
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Writes all entries to `writer`, CSV has one row per session with the
+    /// accuracy added
+    pub fn export(&self, format: ExportFormat, mut writer: impl Write) -> Result<()> {
+        match format {
+            ExportFormat::Json => serde_json::to_writer_pretty(&mut writer, &self.entries)?,
+            ExportFormat::Csv => {
+                writeln!(
+                    writer,
+                    "timestamp,mode,word_list,rounds,chars,misses,duration_ms,wpm,normalized_wpm,accuracy,uncorrected,raw_wpm,consistency"
+                )?;
+                for e in &self.entries {
+                    writeln!(
+                        writer,
+                        "{},{},{},{},{},{},{},{:.2},{:.2},{:.4},{},{:.2},{:.1}",
+                        e.timestamp.to_rfc3339(),
+                        csv_field(&e.mode.to_string()),
+                        csv_field(e.word_list.as_deref().unwrap_or_default()),
+                        e.rounds,
+                        e.chars,
+                        e.misses,
+                        e.duration_ms,
+                        e.wpm,
+                        e.normalized_wpm,
+                        e.accuracy(),
+                        e.uncorrected,
+                        e.raw_wpm,
+                        e.consistency
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// the entry at `index` to change its tags or note, call [`Self::save`]
+    /// to keep the changes
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut HistoryEntry> {
+        self.entries.get_mut(index)
+    }
+
+    /// Recomputes every entry, see [`HistoryEntry::recompute`], puts them in
+    /// order of time and rewrites the history file. Returns the number of entries that changed.
+    pub fn recompute(&mut self) -> Result<usize> {
+        self.entries.sort_by_key(|e| e.timestamp);
+        let changed = self
+            .entries
+            .iter_mut()
+            .map(|e| e.recompute())
+            .filter(|c| *c)
+            .count();
+        self.save()?;
+        Ok(changed)
+    }
+
+    /// The same entries, changes aren't written to the file anymore
+    pub fn in_memory(self) -> Self {
+        Self { path: None, ..self }
+    }
+
+    /// Removes the entry at `index` and rewrites the history file
+    pub fn remove(&mut self, index: usize) -> Result<Option<HistoryEntry>> {
+        if index >= self.entries.len() {
+            return Ok(None);
+        }
+        let entry = self.entries.remove(index);
+        self.save()?;
+        Ok(Some(entry))
+    }
+
+    /// Rewrites the history file with all entries. The new file replaces the
+    /// old one only once it's complete.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("jsonl.tmp");
+        let mut lines = String::new();
+        for entry in &self.entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        fs::write(&tmp, lines).wrap_err_with(|| format!("writing {} failed", tmp.display()))?;
+        fs::rename(&tmp, path).wrap_err_with(|| format!("replacing {} failed", path.display()))
+    }
+
+    /// Adds an entry and appends it to the history file
+    pub fn push(&mut self, entry: HistoryEntry) -> Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .wrap_err_with(|| format!("opening {} failed", path.display()))?;
+            writeln!(file, "{}", serde_json::to_string(&entry)?)
+                .wrap_err_with(|| format!("writing {} failed", path.display()))?;
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    fn entry(wpm: f64, misses: usize) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Utc::now(),
+            mode: Mode::Words(10),
+            word_list: Some("english".into()),
+            rounds: 2,
+            chars: 90,
+            misses,
+            uncorrected: 0,
+            duration_ms: 60_000,
+            wpm,
+            normalized_wpm: wpm,
+            raw_wpm: 0.0,
+            consistency: 0.0,
+            classes: ClassTallies::default(),
+            latencies: KeyLatencies::default(),
+            bigrams: BigramLatencies::default(),
+            key_misses: BTreeMap::new(),
+            curve: vec![],
+            tags: vec![],
+            note: String::new(),
+            plan: None,
+        }
+    }
+
+    #[test]
+    fn score_uses_accuracy_and_errors() {
+        let entry = entry(18.0, 10);
+        assert_eq!(entry.accuracy(), 0.9);
+        assert!((entry.score(ScoreFormula::WpmAccuracySquared) - 14.58).abs() < 1e-9);
+        assert_eq!(entry.score(ScoreFormula::NetWpm), 8.0);
+        assert_eq!(entry.category(), "words 10, english");
+        assert_eq!(
+            entry.summary(),
+            "words 10, english: 18.0 WPM, 90.0% accuracy, 60.0s"
+        );
+
+        let mut fast = entry.clone();
+        fast.wpm = 99.0;
+        fast.normalized_wpm = 99.0;
+        assert!(fast.recompute());
+        assert_eq!(
+            fast,
+            HistoryEntry {
+                raw_wpm: 20.0,
+                ..entry
+            }
+        );
+    }
+
+    #[test]
+    fn recompute_every_speed() {
+        let mut entry = entry(18.0, 10);
+        entry.normalized_wpm = 27.0;
+        entry.raw_wpm = 0.0;
+        entry.duration_ms = 30_000;
+        assert!(entry.recompute());
+        assert_eq!(entry.wpm, 36.0);
+        assert_eq!(entry.raw_wpm, 40.0);
+        assert_eq!(entry.normalized_wpm, 54.0);
+        assert!(!entry.recompute());
+
+        // without a speed the difficulty is unknown
+        entry.wpm = 0.0;
+        assert!(entry.recompute());
+        assert_eq!(entry.normalized_wpm, 54.0);
+
+        for c in "abc".chars() {
+            entry.latencies.record(c, Duration::from_millis(150));
+        }
+        assert!(entry.recompute());
+        assert_eq!(entry.consistency, 100.0);
+    }
+
+    #[test]
+    fn push_appends_to_file() {
+        let path = temp_path("history.jsonl");
+
+        let mut history = History::open(path.clone()).unwrap();
+        assert!(history.entries().is_empty());
+        history.push(entry(40.0, 1)).unwrap();
+        history.push(entry(50.0, 0)).unwrap();
+
+        let reloaded = History::open(path.clone()).unwrap();
+        assert_eq!(reloaded.entries(), history.entries());
+
+        history.get_mut(1).unwrap().note = "new keyboard".into();
+        assert_eq!(history.remove(0).unwrap().unwrap().wpm, 40.0);
+        let reloaded = History::open(path.clone()).unwrap();
+        assert_eq!(reloaded.entries(), history.entries());
+        assert_eq!(reloaded.entries()[0].note, "new keyboard");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn export_csv() {
+        let mut history = History::default();
+        let mut quoted = entry(40.0, 10);
+        quoted.word_list = Some("say \"hi\", all".into());
+        history.push(quoted).unwrap();
+
+        let mut csv = vec![];
+        history.export(ExportFormat::Csv, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.ends_with(
+            ",words 10,\"say \"\"hi\"\", all\",2,90,10,60000,40.00,40.00,0.9000,0,0.00,0.0"
+        ));
+    }
+}
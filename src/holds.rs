@@ -0,0 +1,127 @@
+//! How long keys are held down. Only terminals with the keyboard enhancement
+//! protocol (kitty, foot, WezTerm, ...) report releases and repeats, see
+//! [`crate::tui::init`].
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use color_eyre::Result;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+
+use crate::{App, InputMode};
+
+/// The holds of the keys typed in a session
+#[derive(Debug, Default)]
+pub struct KeyHolds {
+    /// when the keys held right now went down
+    down: HashMap<KeyCode, Instant>,
+    /// how long each released key was held
+    durations: Vec<Duration>,
+    /// auto-repeats of held keys, not typed
+    repeats: usize,
+}
+
+impl KeyHolds {
+    pub fn press(&mut self, code: KeyCode, now: Instant) {
+        self.down.insert(key(code), now);
+    }
+
+    /// ends the hold of `code`, unless it went down before the session
+    pub fn release(&mut self, code: KeyCode, now: Instant) {
+        if let Some(since) = self.down.remove(&key(code)) {
+            self.durations.push(now.duration_since(since));
+        }
+    }
+
+    pub fn repeat(&mut self) {
+        self.repeats += 1;
+    }
+
+    /// the average hold, `None` if no release was reported
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.durations.len())
+            .ok()
+            .filter(|n| *n > 0)?;
+        Some(self.durations.iter().sum::<Duration>() / count)
+    }
+
+    pub fn longest(&self) -> Option<Duration> {
+        self.durations.iter().max().copied()
+    }
+
+    pub fn repeats(&self) -> usize {
+        self.repeats
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// the key of a hold, shift may be let go before the letter
+fn key(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        code => code,
+    }
+}
+
+impl App<'_> {
+    /// handles a press, repeat or release of a key at `now`. Repeats of a
+    /// held character are counted instead of typed.
+    pub(crate) fn handle_key_kind_at(&mut self, key_event: KeyEvent, now: Instant) -> Result<()> {
+        let test = self.input_mode() == InputMode::Test;
+        match key_event.kind {
+            KeyEventKind::Press => {
+                if test {
+                    self.holds.press(key_event.code, now);
+                }
+                self.handle_key_event_at(key_event, now)
+            }
+            KeyEventKind::Repeat if test && matches!(key_event.code, KeyCode::Char(_)) => {
+                self.holds.repeat();
+                Ok(())
+            }
+            KeyEventKind::Repeat => self.handle_key_event_at(key_event, now),
+            KeyEventKind::Release => {
+                self.holds.release(key_event.code, now);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    #[test]
+    fn count_repeats_instead_of_typing_them() {
+        let mut app = App::default().with_text("aab");
+        app.next_round().unwrap();
+        let now = Instant::now();
+        let event = |c, kind| KeyEvent::new_with_kind(KeyCode::Char(c), KeyModifiers::NONE, kind);
+        let ms = |ms| now + Duration::from_millis(ms);
+
+        app.handle_key_kind_at(event('a', KeyEventKind::Press), now)
+            .unwrap();
+        app.handle_key_kind_at(event('a', KeyEventKind::Repeat), ms(500))
+            .unwrap();
+        app.handle_key_kind_at(event('A', KeyEventKind::Release), ms(600))
+            .unwrap();
+        assert_eq!(app.remainder.span.content, "ab");
+        assert_eq!(app.holds.repeats(), 1);
+
+        app.handle_key_kind_at(event('a', KeyEventKind::Press), ms(700))
+            .unwrap();
+        app.handle_key_kind_at(event('a', KeyEventKind::Release), ms(800))
+            .unwrap();
+        assert_eq!(app.remainder.span.content, "b");
+        assert_eq!(app.holds.mean(), Some(Duration::from_millis(350)));
+        assert_eq!(app.holds.longest(), Some(Duration::from_millis(600)));
+    }
+}
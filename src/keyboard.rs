@@ -0,0 +1,83 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Finger per column of the main block, 0-3 are the left pinky to index,
+/// 4-7 the right index to pinky
+const FINGERS: [u8; 10] = [0, 1, 2, 3, 3, 4, 4, 5, 6, 7];
+
+/// The physical keyboard layout the user types on
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Colemak,
+    Dvorak,
+}
+
+/// Position of a key on the main block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// 0 = top row, 1 = home row, 2 = bottom row
+    pub row: u8,
+    pub col: u8,
+}
+
+impl Key {
+    pub fn finger(self) -> u8 {
+        FINGERS[self.col as usize]
+    }
+
+    pub fn is_left_hand(self) -> bool {
+        self.finger() < 4
+    }
+}
+
+impl fmt::Display for KeyboardLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Qwerty => write!(f, "qwerty"),
+            Self::Colemak => write!(f, "colemak"),
+            Self::Dvorak => write!(f, "dvorak"),
+        }
+    }
+}
+
+impl KeyboardLayout {
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            Self::Qwerty => ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            Self::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            Self::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+        }
+    }
+
+    /// The key producing `c`, ignoring case. `None` for keys outside of the
+    /// three letter rows.
+    pub fn key(self, c: char) -> Option<Key> {
+        let c = c.to_ascii_lowercase();
+        self.rows().iter().enumerate().find_map(|(row, keys)| {
+            keys.chars().position(|k| k == c).map(|col| Key {
+                row: row as u8,
+                col: col as u8,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_lookup() {
+        let key = KeyboardLayout::Qwerty.key('F').unwrap();
+        assert_eq!(key, Key { row: 1, col: 3 });
+        assert_eq!(key.finger(), 3);
+        assert!(key.is_left_hand());
+
+        assert_eq!(KeyboardLayout::Colemak.key('n').unwrap().finger(), 4);
+        assert!(KeyboardLayout::Dvorak.key('1').is_none());
+    }
+}
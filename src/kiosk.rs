@@ -0,0 +1,306 @@
+//! Kiosk mode for demo machines at events: nothing is saved, the settings
+//! are locked and quitting takes a chord and the passphrase of the config.
+//! An idle kiosk shows the attract screen, see [`crate::attract`]. People
+//! taking turns switch between users kept in memory.
+
+use std::{
+    collections::BTreeMap,
+    mem,
+    time::{Duration, Instant},
+};
+
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{badges::Badges, history::History, profile, App};
+
+/// Shown when something the kiosk locks is tried
+const LOCKED: &str = "locked in kiosk mode, Ctrl+Alt+Q to quit";
+
+/// How a kiosk quits and idles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KioskConfig {
+    /// asked for to quit, empty to quit with Ctrl+Alt+Q alone
+    pub passphrase: String,
+    /// seconds without a key until the attract screen shows, 0 for never
+    pub idle_secs: u16,
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        Self {
+            passphrase: String::new(),
+            idle_secs: 60,
+        }
+    }
+}
+
+/// The results of a user of the kiosk while someone else types
+#[derive(Debug, Default)]
+pub struct KioskUser {
+    history: History,
+    badges: Badges,
+}
+
+/// The state of the kiosk mode of an [`App`]
+#[derive(Debug, Default)]
+pub struct Kiosk {
+    pub config: KioskConfig,
+    /// what was typed since the chord, `None` if it wasn't pressed
+    entered: Option<String>,
+    /// when the last key was pressed, `None` before the first tick
+    pub last_input: Option<Instant>,
+    /// since when the attract screen is shown, `None` if it isn't
+    pub attract: Option<Instant>,
+    /// everyone but the current user, by name
+    users: BTreeMap<String, KioskUser>,
+}
+
+impl Kiosk {
+    pub fn new(config: &KioskConfig) -> Self {
+        Self {
+            config: config.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// whether the kiosk went idle at `now` and the attract screen is due
+    pub fn idle(&self, now: Instant) -> bool {
+        let idle = Duration::from_secs(self.config.idle_secs.into());
+        self.attract.is_none()
+            && !idle.is_zero()
+            && self
+                .last_input
+                .is_some_and(|last| now.saturating_duration_since(last) >= idle)
+    }
+
+    pub fn start_attract(&mut self, now: Instant) {
+        self.entered = None;
+        self.attract = Some(now);
+    }
+
+    /// the names of the users with `current` among them, sorted
+    pub fn users(&self, current: &str) -> Vec<String> {
+        let mut names: Vec<String> = self.users.keys().cloned().collect();
+        names.push(current.into());
+        names.sort();
+        names
+    }
+}
+
+/// the chord that quits a kiosk
+fn is_chord(key_event: KeyEvent) -> bool {
+    key_event
+        .modifiers
+        .contains(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && matches!(key_event.code, KeyCode::Char('q' | 'Q'))
+}
+
+impl App<'_> {
+    /// locks the app for a demo machine, the history and the progress are
+    /// kept in memory only
+    pub fn with_kiosk(mut self) -> Self {
+        self.kiosk = Some(Kiosk::new(&self.config.kiosk));
+        self.history = self.history.in_memory();
+        self.config_path = None;
+        self.resume_path = None;
+        self.lesson_path = None;
+        self.badges_path = None;
+        self.course_path = None;
+        self
+    }
+
+    /// puts the results of the current user aside and continues with a
+    /// fresh session of `name`, a new user if there is none of that name
+    pub(crate) fn switch_kiosk_user(&mut self, name: &str) -> Result<String> {
+        if let Err(error) = profile::validate(name) {
+            return Ok(error);
+        }
+        let Some(kiosk) = &mut self.kiosk else {
+            return Ok(String::new());
+        };
+        let user = kiosk.users.remove(name).unwrap_or_default();
+        let previous = KioskUser {
+            history: mem::replace(&mut self.history, user.history),
+            badges: mem::replace(&mut self.badges, user.badges),
+        };
+        kiosk.users.insert(self.config.profile.clone(), previous);
+        // shown in the header, nothing is saved under it
+        self.config.profile = name.into();
+        self.new_session()?;
+        Ok(format!("user: {name}"))
+    }
+
+    /// tells that `action` is locked if in kiosk mode, returns whether it is
+    pub(crate) fn kiosk_locks(&mut self, action: &str) -> bool {
+        if self.kiosk.is_none() {
+            return false;
+        }
+        self.status = Some(format!("{action}: {LOCKED}"));
+        true
+    }
+
+    /// handles the keys of the chord and the passphrase and wakes up from
+    /// the attract screen, returns whether the key was handled
+    pub(crate) fn handle_kiosk_key(&mut self, key_event: KeyEvent, now: Instant) -> Result<bool> {
+        let Some(kiosk) = &mut self.kiosk else {
+            return Ok(false);
+        };
+        kiosk.last_input = Some(now);
+        if kiosk.attract.take().is_some() {
+            self.new_session()?;
+            return Ok(true);
+        }
+        let Some(entered) = &mut kiosk.entered else {
+            if !is_chord(key_event) {
+                return Ok(false);
+            }
+            if kiosk.config.passphrase.is_empty() {
+                self.exit = true;
+            } else {
+                kiosk.entered = Some(String::new());
+            }
+            return Ok(true);
+        };
+        match key_event.code {
+            KeyCode::Char(c) => entered.push(c),
+            KeyCode::Backspace => {
+                entered.pop();
+            }
+            KeyCode::Esc => kiosk.entered = None,
+            KeyCode::Enter => {
+                if *entered == kiosk.config.passphrase {
+                    self.exit = true;
+                } else {
+                    self.status = Some("wrong passphrase".into());
+                }
+                kiosk.entered = None;
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// the passphrase prompt over `area`, masked
+    pub(crate) fn render_kiosk(&self, area: Rect, buf: &mut Buffer) {
+        let Some(entered) = self.kiosk.as_ref().and_then(|k| k.entered.as_ref()) else {
+            return;
+        };
+        let width = 32.min(area.width);
+        let height = 3.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        Clear.render(popup, buf);
+        Paragraph::new(Line::from(vec![
+            Span::raw("passphrase: "),
+            Span::styled("*".repeat(entered.chars().count()), self.theme.value),
+        ]))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .title(Span::styled(" QUIT KIOSK ", self.theme.title))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(self.theme.border),
+        )
+        .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, Screen};
+
+    #[test]
+    fn take_turns_as_users() {
+        let mut app = App::default().with_kiosk();
+        app.next_round().unwrap();
+        app.history
+            .push(crate::history::HistoryEntry::new(
+                &Default::default(),
+                crate::Mode::Random,
+                chrono::Utc::now(),
+            ))
+            .unwrap();
+
+        assert_eq!(app.switch_kiosk_user("anna").unwrap(), "user: anna");
+        assert_eq!(app.config.profile, "anna");
+        assert!(app.history.entries().is_empty());
+        assert_eq!(
+            app.switch_kiosk_user("a b").unwrap(),
+            "profile names are made of letters, digits, - and _"
+        );
+
+        app.open_profiles();
+        assert_eq!(app.profiles, ["anna", "default"]);
+        app.switch_kiosk_user("default").unwrap();
+        assert_eq!(app.history.entries().len(), 1);
+    }
+
+    #[test]
+    fn write_no_certificates() {
+        let mut app = App::default().with_text("abc").with_kiosk();
+        app.next_round().unwrap();
+        for c in "abc".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app.finish_session(Instant::now()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        app.handle_key_event(KeyCode::Char('c').into()).unwrap();
+        assert_eq!(
+            app.status.as_deref(),
+            Some(&*format!("certificates: {LOCKED}"))
+        );
+    }
+
+    #[test]
+    fn quit_only_with_the_chord_and_passphrase() {
+        let mut config = Config::default();
+        config.kiosk.passphrase = "open".into();
+        let mut app = App {
+            config,
+            ..Default::default()
+        }
+        .with_kiosk();
+        app.next_round().unwrap();
+        let chord = KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        let press = |app: &mut App, key: KeyEvent| app.handle_key_event(key).unwrap();
+
+        press(&mut app, KeyCode::Esc.into());
+        assert!(!app.exit);
+        let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        press(&mut app, ctrl_s);
+        assert_ne!(app.screen, crate::Screen::Settings);
+
+        press(&mut app, chord);
+        for c in "opem".chars() {
+            press(&mut app, KeyCode::Char(c).into());
+        }
+        press(&mut app, KeyCode::Enter.into());
+        assert_eq!(app.status.as_deref(), Some("wrong passphrase"));
+        assert!(!app.exit);
+
+        press(&mut app, chord);
+        for c in "open".chars() {
+            press(&mut app, KeyCode::Char(c).into());
+        }
+        press(&mut app, KeyCode::Enter.into());
+        assert!(app.exit);
+    }
+}
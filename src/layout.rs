@@ -0,0 +1,139 @@
+//! Where the parts of the typing screen go, depending on the size of the
+//! terminal
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Bodies lower than this get the compact layout
+const REGULAR_HEIGHT: u16 = 13;
+/// Bodies from this height on get a chart of the session
+const TALL_HEIGHT: u16 = 30;
+/// Rows of the chart of the session
+const CHART_HEIGHT: u16 = 10;
+/// Widest column of stats and text, it's centered on wider terminals
+const MAX_COLUMN_WIDTH: u16 = 100;
+
+/// How much room the typing screen has
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Breakpoint {
+    /// the wins and fails in one line
+    Compact,
+    /// the wins and fails in blocks
+    Regular,
+    /// the blocks and a chart of the session below the text
+    Tall,
+}
+
+impl Breakpoint {
+    /// the layout for a body `height` rows high
+    pub fn of(height: u16) -> Self {
+        if height < REGULAR_HEIGHT {
+            Self::Compact
+        } else if height < TALL_HEIGHT {
+            Self::Regular
+        } else {
+            Self::Tall
+        }
+    }
+
+    /// rows of the wins and fails or the race
+    fn stats_height(self) -> u16 {
+        match self {
+            Self::Compact => 1,
+            Self::Regular | Self::Tall => 7,
+        }
+    }
+}
+
+/// The areas of the typing screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypingLayout {
+    pub breakpoint: Breakpoint,
+    pub header: Rect,
+    /// the wins and fails or the race
+    pub stats: Rect,
+    pub input: Rect,
+    /// the difficulty and the time left
+    pub info: Rect,
+    pub restart: Rect,
+    /// `Some` with [`Breakpoint::Tall`]
+    pub chart: Option<Rect>,
+    pub help: Rect,
+}
+
+impl TypingLayout {
+    /// the areas inside `area`, the first row is the header if `header`
+    pub fn new(area: Rect, header: bool) -> Self {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(u16::from(header)),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let breakpoint = Breakpoint::of(rows[1].height + rows[2].height);
+        let column = centered(rows[1], MAX_COLUMN_WIDTH);
+
+        let mut constraints = vec![
+            Constraint::Length(breakpoint.stats_height()),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ];
+        if breakpoint == Breakpoint::Tall {
+            constraints.extend([Constraint::Length(1), Constraint::Length(CHART_HEIGHT)]);
+        }
+        constraints.push(Constraint::Min(0));
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(column);
+
+        Self {
+            breakpoint,
+            header: rows[0],
+            stats: parts[0],
+            input: parts[1],
+            info: parts[2],
+            restart: parts[3],
+            chart: (breakpoint == Breakpoint::Tall).then(|| parts[5]),
+            help: rows[2],
+        }
+    }
+}
+
+/// `area` narrowed to at most `width` columns in the middle
+fn centered(area: Rect, width: u16) -> Rect {
+    let width = width.min(area.width);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y,
+        width,
+        area.height,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapt_to_the_terminal_size() {
+        let short = TypingLayout::new(Rect::new(0, 0, 60, 9), true);
+        assert_eq!(short.breakpoint, Breakpoint::Compact);
+        assert_eq!(short.stats, Rect::new(0, 1, 60, 1));
+        assert_eq!(short.input, Rect::new(0, 2, 60, 3));
+        assert_eq!(short.help, Rect::new(0, 8, 60, 1));
+
+        let regular = TypingLayout::new(Rect::new(0, 0, 80, 24), true);
+        assert_eq!(regular.breakpoint, Breakpoint::Regular);
+        assert_eq!(regular.stats.height, 7);
+        assert_eq!(regular.chart, None);
+
+        let big = TypingLayout::new(Rect::new(0, 0, 160, 40), false);
+        assert_eq!(big.breakpoint, Breakpoint::Tall);
+        assert_eq!(big.input, Rect::new(30, 7, 100, 3));
+        assert_eq!(big.chart, Some(Rect::new(30, 13, 100, CHART_HEIGHT)));
+        assert_eq!(big.help, Rect::new(0, 39, 160, 1));
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{history::HistoryEntry, zone::Zone};
+
+/// Number of entries per leaderboard
+pub const TOP: usize = 10;
+
+/// Which results make it into the leaderboard
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DateFilter {
+    #[default]
+    All,
+    Today,
+    Week,
+    Month,
+}
+
+impl DateFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Today,
+            Self::Today => Self::Week,
+            Self::Week => Self::Month,
+            Self::Month => Self::All,
+        }
+    }
+
+    /// whether `timestamp` is in the range, today starts at midnight in `zone`
+    pub fn matches(self, timestamp: DateTime<Utc>, now: DateTime<Utc>, zone: Zone) -> bool {
+        match self {
+            Self::All => true,
+            Self::Today => zone.date(timestamp) == zone.date(now),
+            Self::Week => now - timestamp <= Duration::days(7),
+            Self::Month => now - timestamp <= Duration::days(30),
+        }
+    }
+}
+
+impl fmt::Display for DateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all time"),
+            Self::Today => write!(f, "today"),
+            Self::Week => write!(f, "last 7 days"),
+            Self::Month => write!(f, "last 30 days"),
+        }
+    }
+}
+
+/// All categories present in the history, in order of first appearance
+pub fn categories(entries: &[HistoryEntry]) -> Vec<String> {
+    let mut categories: Vec<String> = vec![];
+    for category in entries.iter().map(HistoryEntry::category) {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    categories
+}
+
+/// The fastest [`TOP`] results of a category matching `filter`
+pub fn top<'a>(
+    entries: &'a [HistoryEntry],
+    category: &str,
+    filter: DateFilter,
+    now: DateTime<Utc>,
+    zone: Zone,
+) -> Vec<&'a HistoryEntry> {
+    let mut top: Vec<&HistoryEntry> = entries
+        .iter()
+        .filter(|e| e.category() == category && filter.matches(e.timestamp, now, zone))
+        .collect();
+    top.sort_by(|a, b| b.wpm.total_cmp(&a.wpm));
+    top.truncate(TOP);
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mode;
+
+    fn entry(mode: Mode, wpm: f64, days_ago: i64, now: DateTime<Utc>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: now - Duration::days(days_ago),
+            mode,
+            word_list: None,
+            rounds: 1,
+            chars: 10,
+            misses: 0,
+            uncorrected: 0,
+            duration_ms: 1000,
+            wpm,
+            normalized_wpm: wpm,
+            raw_wpm: 0.0,
+            consistency: 0.0,
+            classes: Default::default(),
+            latencies: Default::default(),
+            bigrams: Default::default(),
+            key_misses: Default::default(),
+            curve: vec![],
+            tags: vec![],
+            note: String::new(),
+            plan: None,
+        }
+    }
+
+    #[test]
+    fn top_results_per_category() {
+        let now = Utc::now();
+        let mut entries = vec![
+            entry(Mode::Random, 20.0, 0, now),
+            entry(Mode::Random, 50.0, 10, now),
+            entry(Mode::Chars(5), 90.0, 0, now),
+        ];
+        entries.extend((0..12).map(|i| entry(Mode::Random, 30.0 + i as f64, 0, now)));
+
+        assert_eq!(categories(&entries), vec!["random", "chars 5"]);
+
+        let top_all = top(&entries, "random", DateFilter::All, now, Zone::Local);
+        assert_eq!(top_all.len(), TOP);
+        assert_eq!(top_all[0].wpm, 50.0);
+        assert_eq!(top_all[1].wpm, 41.0);
+
+        let top_week = top(&entries, "random", DateFilter::Week, now, Zone::Local);
+        assert_eq!(top_week[0].wpm, 41.0);
+        assert!(top(&entries, "words 10", DateFilter::All, now, Zone::Local).is_empty());
+    }
+}
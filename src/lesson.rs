@@ -0,0 +1,279 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{generator, history::History, stats::RoundRecord, App, Mode};
+
+/// Letters in the order lessons introduce them, the most common first
+pub const LETTER_ORDER: &str = "etaoinshrdlcumwfgypbvkjxqz";
+/// Name of the file of the progress inside the data directory
+const LESSON_FILE: &str = "lesson.json";
+/// Weight of the latest round in the smoothed speed and accuracy of a letter
+const SMOOTHING: f64 = 0.3;
+/// Presses of a letter before it can count as learned
+const MIN_SAMPLES: usize = 10;
+
+/// When a letter counts as learned and the next one is unlocked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LessonConfig {
+    /// letters to start with
+    pub start_letters: usize,
+    /// speed every unlocked letter needs
+    pub target_wpm: f64,
+    /// share of the presses of every unlocked letter that have to be right
+    pub target_accuracy: f64,
+}
+
+impl Default for LessonConfig {
+    fn default() -> Self {
+        Self {
+            start_letters: 6,
+            target_wpm: 35.0,
+            target_accuracy: 0.95,
+        }
+    }
+}
+
+/// How well a letter is typed, smoothed over the rounds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyProgress {
+    pub latency_ms: f64,
+    pub accuracy: f64,
+    /// correct presses so far
+    pub samples: usize,
+}
+
+impl KeyProgress {
+    /// the speed of typing only this letter
+    pub fn wpm(&self) -> f64 {
+        if self.latency_ms > 0.0 {
+            12_000.0 / self.latency_ms
+        } else {
+            0.0
+        }
+    }
+
+    /// how close the letter is to the targets, 1 once they are met
+    pub fn progress(&self, config: &LessonConfig) -> f64 {
+        if self.samples < MIN_SAMPLES {
+            return 0.0;
+        }
+        (self.wpm() / config.target_wpm).min(1.0)
+            * (self.accuracy / config.target_accuracy).min(1.0)
+    }
+}
+
+/// The letters unlocked so far and how well each is typed, stored per
+/// profile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progression {
+    /// number of letters of [`LETTER_ORDER`] unlocked, at least
+    /// [`LessonConfig::start_letters`]
+    pub unlocked: usize,
+    pub keys: BTreeMap<char, KeyProgress>,
+}
+
+impl Progression {
+    /// Default location of the progress of `profile`, `None` if the
+    /// platform has no data directory
+    pub fn path(profile: &str) -> Option<PathBuf> {
+        History::dir(profile).map(|dir| dir.join(LESSON_FILE))
+    }
+
+    /// Loads the progress stored at `path`, nothing unlocked yet if there
+    /// is no file
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        serde_json::from_str(&raw).wrap_err_with(|| format!("parsing {} failed", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    /// the unlocked letters in the order they were introduced
+    pub fn letters(&self, config: &LessonConfig) -> Vec<char> {
+        LETTER_ORDER
+            .chars()
+            .take(self.unlocked.max(config.start_letters))
+            .collect()
+    }
+
+    /// the unlocked letter furthest from the targets, `None` once all of
+    /// them meet them
+    pub fn focus(&self, config: &LessonConfig) -> Option<char> {
+        self.letters(config)
+            .into_iter()
+            .map(|c| (c, self.keys.get(&c).map_or(0.0, |k| k.progress(config))))
+            .filter(|(_, progress)| *progress < 1.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(c, _)| c)
+    }
+
+    /// counts the presses of the unlocked letters in `round`, returns the
+    /// letter unlocked once all of them meet the targets
+    pub fn record(&mut self, round: &RoundRecord, config: &LessonConfig) -> Option<char> {
+        let letters = self.letters(config);
+        let latencies = round.latencies.keys(1);
+        let target: Vec<char> = round.target.chars().collect();
+        for &letter in &letters {
+            let latency = latencies.iter().find(|k| k.key == letter);
+            let hits = latency.map_or(0, |k| k.count);
+            let misses = round
+                .miss_positions
+                .iter()
+                .filter(|&&i| target.get(i) == Some(&letter))
+                .count();
+            if hits + misses == 0 {
+                continue;
+            }
+            let accuracy = hits as f64 / (hits + misses) as f64;
+            let key = self.keys.entry(letter).or_default();
+            let weight = if key.samples == 0 { 1.0 } else { SMOOTHING };
+            key.accuracy += weight * (accuracy - key.accuracy);
+            if let Some(latency) = latency {
+                key.latency_ms += weight * (latency.mean_ms - key.latency_ms);
+            }
+            key.samples += hits;
+        }
+
+        if self.focus(config).is_some() || letters.len() == LETTER_ORDER.len() {
+            return None;
+        }
+        self.unlocked = letters.len() + 1;
+        LETTER_ORDER.chars().nth(letters.len())
+    }
+}
+
+impl App<'_> {
+    /// practices the unlocked letters with made up words of them
+    pub(crate) fn update_lesson_generator(&mut self) {
+        let config = &self.config.lesson;
+        self.generators.register(
+            "lesson",
+            generator::Lesson::new(self.lesson.letters(config), self.lesson.focus(config)),
+        );
+    }
+
+    /// counts the last round towards the progression of lessons and tells
+    /// when a letter was unlocked
+    pub(crate) fn record_lesson_round(&mut self) {
+        if !matches!(self.mode, Mode::Lesson(_)) || self.config.practice {
+            return;
+        }
+        let Some(round) = self.stats.rounds.last() else {
+            return;
+        };
+        if let Some(letter) = self.lesson.record(round, &self.config.lesson) {
+            self.status = Some(format!("unlocked: {letter}"));
+        }
+        self.update_lesson_generator();
+    }
+
+    pub(crate) fn save_lesson(&self) -> Result<()> {
+        match &self.lesson_path {
+            Some(path) => self
+                .lesson
+                .save(path)
+                .wrap_err("saving the lesson progress failed"),
+            None => Ok(()),
+        }
+    }
+
+    /// all letters, the unlocked ones in the value style, the focus
+    /// highlighted and the locked ones dimmed
+    pub(crate) fn render_lesson_letters(&self, area: Rect, buf: &mut Buffer) {
+        let config = &self.config.lesson;
+        let unlocked = self.lesson.letters(config).len();
+        let focus = self.lesson.focus(config);
+        let spans: Vec<Span> = LETTER_ORDER
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let style = if Some(c) == focus {
+                    self.theme.hit.add_modifier(Modifier::REVERSED)
+                } else if i < unlocked {
+                    self.theme.value
+                } else {
+                    self.theme.border
+                };
+                Span::styled(format!("{c} "), style)
+            })
+            .collect();
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::stats::KeyLatencies;
+
+    fn round(target: &str, ms: u64, miss_positions: Vec<usize>) -> RoundRecord {
+        let mut latencies = KeyLatencies::default();
+        for c in target.chars() {
+            latencies.record(c, Duration::from_millis(ms));
+        }
+        RoundRecord {
+            missed: !miss_positions.is_empty(),
+            misses: miss_positions.len(),
+            latencies,
+            miss_positions,
+            ..RoundRecord::typed(target, Duration::default())
+        }
+    }
+
+    #[test]
+    fn unlock_a_letter_once_all_meet_the_targets() {
+        let config = LessonConfig {
+            start_letters: 2,
+            ..Default::default()
+        };
+        let mut progression = Progression::default();
+        assert_eq!(progression.letters(&config), ['e', 't']);
+        assert_eq!(progression.focus(&config), Some('e'));
+
+        // fast, but t is missed too often
+        let sloppy = round("eeeeettttt", 200, vec![5, 6]);
+        let clean = round("eeeeettttt", 200, vec![]);
+        assert_eq!(progression.record(&sloppy, &config), None);
+        assert_eq!(progression.record(&clean, &config), None);
+        assert_eq!(progression.focus(&config), Some('t'));
+
+        for _ in 0..10 {
+            if let Some(letter) = progression.record(&clean, &config) {
+                assert_eq!(letter, 'a');
+                break;
+            }
+        }
+        assert_eq!(progression.letters(&config), ['e', 't', 'a']);
+        assert_eq!(progression.focus(&config), Some('a'));
+    }
+}
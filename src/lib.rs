@@ -0,0 +1,2488 @@
+//! The typing test of metyping as a library. [`App`] runs without a
+//! terminal: feed it keys with [`App::handle_key_event_at`] and read the
+//! session back with [`App::session_entry`].
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//!
+//! use metyping::App;
+//! use ratatui::crossterm::event::KeyCode;
+//!
+//! let mut app = App::default().with_text("the quick brown fox");
+//! app.new_session()?;
+//! let mut now = Instant::now();
+//! for c in app.target().to_string().chars() {
+//!     now += Duration::from_millis(150);
+//!     app.handle_key_event_at(KeyCode::Char(c).into(), now)?;
+//! }
+//! app.handle_key_event_at(KeyCode::Enter.into(), now)?;
+//! let entry = app.session_entry().expect("a round was typed");
+//! assert_eq!(entry.misses, 0);
+//! # Ok::<(), color_eyre::Report>(())
+//! ```
+//!
+//! The `examples` directory has more: a session without a terminal, the app
+//! as a widget of another TUI and a text generator of your own.
+
+use std::{
+    fmt, mem,
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use clap::Parser;
+use color_eyre::{
+    eyre::{eyre, OptionExt, WrapErr},
+    Result,
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{
+        self, Event, KeyCode, KeyEvent, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::Modifier,
+    text::{Line, Span, Text},
+    widgets::{
+        block::Title, BarChart, Block, BorderType, Borders, Clear, Padding, Paragraph, Widget,
+    },
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+use anim::{Anim, Animations};
+use badges::Badges;
+use cli::{Cli, CourseAction, PlanAction, StyleAction, Subcommand};
+use color::ColorDepth;
+pub use config::Config;
+use config::{PanelConfig, QuitProtection, Strict};
+use confirm::{Confirm, ConfirmAction};
+use course::{Course, CourseProgress};
+use drill::Focus;
+use form::{Field, TextInput};
+use generator::Generators;
+pub use generator::{GeneratorConfig, TextGenerator};
+use guard::InputGuard;
+pub use history::{History, HistoryEntry};
+use holds::KeyHolds;
+use kiosk::Kiosk;
+use layout::{Breakpoint, TypingLayout};
+use leaderboard::DateFilter;
+use lesson::Progression;
+use phrases::PhraseList;
+use picker::Picker;
+use plan::PlanItem;
+use profile::DEFAULT_PROFILE;
+use race::{BotConfig, Race};
+use redraw::{Clocks, FrameStats};
+use resume::Snapshot;
+use rng::RoundRng;
+use screens::EntryField;
+use session::SessionState;
+use stats::{BigramLatencies, ClassTallies, KeyLatencies, RoundRecord, SessionStats, WordTiming};
+use style::StyleWatch;
+use theme::{Accessibility, Theme};
+use trends::Period;
+use words::WordList;
+use zone::Zone;
+
+mod anim;
+mod attract;
+mod badges;
+mod banner;
+mod certificate;
+mod cli;
+mod clipboard;
+mod color;
+mod command;
+mod compare;
+mod config;
+mod confirm;
+mod course;
+mod difficulty;
+mod digest;
+mod drill;
+mod env;
+mod errors;
+mod form;
+mod generator;
+mod guard;
+#[cfg(test)]
+mod harness;
+mod header;
+mod history;
+mod holds;
+mod keyboard;
+mod kiosk;
+mod layout;
+mod leaderboard;
+mod lesson;
+mod ngram;
+mod online;
+mod phrases;
+mod picker;
+mod plan;
+mod practice;
+mod profile;
+mod race;
+mod redraw;
+mod report;
+mod resume;
+mod review;
+mod rng;
+mod score;
+mod screens;
+mod session;
+mod stats;
+mod style;
+#[cfg(test)]
+mod testing;
+mod theme;
+mod trends;
+mod tui;
+mod words;
+mod zone;
+
+/// runs metyping with the command line of the process, all the binary does
+pub fn run() -> Result<()> {
+    let cli = Cli::parse();
+    let name = cli.profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+    profile::validate(name).map_err(|error| eyre!(error))?;
+    // the command line wins over the environment
+    let mut overrides = env::overrides()?;
+    overrides.extend(cli.overrides()?);
+    let config = Config::load(name, overrides)?;
+    let history = History::load(name)?;
+    match &cli.command {
+        Some(Subcommand::Recompute) => return recompute(history, config.timezone),
+        Some(Subcommand::Digest) => return digest::print(&history, config.timezone),
+        Some(Subcommand::Verify { path }) => {
+            return certificate::verify_file(path, &config.certificate.key)
+        }
+        Some(Subcommand::Style {
+            action: StyleAction::Check { path },
+        }) => return style::check_file(path),
+        Some(Subcommand::Course {
+            action: Some(CourseAction::Export { name, path }),
+        }) => return export_course(name, path, &config.profile),
+        Some(Subcommand::Course {
+            action: Some(CourseAction::Load { path }),
+        }) => {
+            let dir = Course::dir(&config.profile).ok_or_eyre("there is no data directory")?;
+            Course::load(path)?.install(&dir)?;
+        }
+        _ => {}
+    }
+    // a kiosk leaves nothing behind on the machine
+    if !cli.kiosk {
+        digest::write_last_week(&history, &config.digest, config.timezone)
+            .wrap_err("writing the weekly digest failed")?;
+    }
+    let mut app = App::new(config, history)?;
+    if let Some(seed) = cli.seed {
+        app = app.with_seed(seed);
+    }
+    if cli.clipboard {
+        app = app.with_text(&clipboard::read()?);
+    }
+    if cli.online {
+        let text = online::read(&app.config.online, &mut rand::thread_rng())?;
+        app = app.with_text(&text);
+    }
+    if cli.frame_stats {
+        app = app.with_frame_stats();
+    }
+    if cli.kiosk {
+        app = app.with_kiosk();
+    }
+    match cli.command {
+        Some(Subcommand::Stats) => app = app.with_screen(Screen::Trends),
+        // the profile to practice in is picked at launch once there are
+        // several
+        None if !cli.kiosk && cli.profile.is_none() && profile::list().len() > 1 => {
+            app = app.with_screen(Screen::Profiles)
+        }
+        Some(Subcommand::Plan { action: None }) => app = app.with_screen(Screen::Plan),
+        Some(Subcommand::Plan {
+            action: Some(PlanAction::Run),
+        }) => app = app.with_plan(),
+        Some(Subcommand::Course { .. }) => app = app.with_screen(Screen::Courses),
+        _ => {}
+    }
+    let mut terminal = tui::init(app.config.mouse, cli.inline || app.config.inline)?;
+    let res = app.run(&mut terminal);
+    drop(terminal);
+    res?;
+
+    if let Some(entry) = app.session_entry() {
+        if cli.json {
+            println!("{}", serde_json::to_string(&entry)?);
+        } else if cli.summary {
+            println!("{}", entry.summary());
+        }
+    }
+    Ok(())
+}
+
+/// writes the loaded course called `name` to `path`
+fn export_course(name: &str, path: &Path, profile: &str) -> Result<()> {
+    let courses = match Course::dir(profile) {
+        Some(dir) => Course::list(&dir)?,
+        None => vec![],
+    };
+    let course = courses
+        .iter()
+        .find(|course| course.name == name)
+        .ok_or_else(|| eyre!("there is no course called {name}"))?;
+    course.save(path)?;
+    println!("exported {name} to {}", path.display());
+    Ok(())
+}
+
+/// rewrites the history with recomputed sessions and prints the personal
+/// bests that follow from them
+fn recompute(mut history: History, zone: Zone) -> Result<()> {
+    let changed = history
+        .recompute()
+        .wrap_err("recomputing the history failed")?;
+    println!(
+        "recomputed {} sessions, {changed} changed",
+        history.entries().len()
+    );
+    let now = Utc::now();
+    for category in leaderboard::categories(history.entries()) {
+        if let Some(best) =
+            leaderboard::top(history.entries(), &category, DateFilter::All, now, zone).first()
+        {
+            println!("{category}: {:.1} WPM", best.wpm);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Random,
+    Chars(u8),
+    Words(u8),
+    /// a random quote per round
+    Quote,
+    /// race bots through a text of this many words
+    Race(u8),
+    /// type words for this many seconds
+    Timed(u16),
+    /// drill numbers like prices, dates and phone numbers
+    Numbers(u8),
+    /// drill code-like runs of symbols
+    Symbols(u8),
+    /// dictate a loaded text, this many words per round
+    Text(u8),
+    /// words with the slowest transitions and least accurate keys of the
+    /// history, this many per round
+    Drill(u8),
+    /// made up words of the letters unlocked so far, this many per round
+    Lesson(u8),
+    /// type the translation of a phrase per round, see [`PhraseList`]
+    Translate,
+}
+
+impl Mode {
+    /// whether the text is made of words from a word list
+    pub fn uses_words(&self) -> bool {
+        matches!(self, Self::Words(_) | Self::Race(_) | Self::Timed(_))
+    }
+
+    /// the name of the mode without its options
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Chars(_) => "chars",
+            Self::Words(_) => "words",
+            Self::Quote => "quote",
+            Self::Translate => "translate",
+            Self::Race(_) => "race",
+            Self::Timed(_) => "timed",
+            Self::Numbers(_) => "numbers",
+            Self::Symbols(_) => "symbols",
+            Self::Text(_) => "text",
+            Self::Drill(_) => "drill",
+            Self::Lesson(_) => "lesson",
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Random | Self::Quote | Self::Translate => write!(f, "{}", self.name()),
+            Self::Chars(n)
+            | Self::Words(n)
+            | Self::Race(n)
+            | Self::Numbers(n)
+            | Self::Symbols(n)
+            | Self::Text(n)
+            | Self::Drill(n)
+            | Self::Lesson(n) => {
+                write!(f, "{} {n}", self.name())
+            }
+            Self::Timed(secs) => write!(f, "{} {secs}s", self.name()),
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    /// parses the way modes are displayed, e.g. `words 25` or `timed 60s`,
+    /// a missing option takes the default
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let name = parts.next().unwrap_or_default();
+        let option = parts.next().map(|n| n.trim_end_matches('s'));
+        if parts.next().is_some() {
+            return Err(format!("too many options in '{s}'"));
+        }
+        let number = |default: u16| match option {
+            Some(n) => n
+                .parse::<u16>()
+                .map_err(|_| format!("'{n}' isn't a number")),
+            None => Ok(default),
+        };
+        let count = |default: u8| {
+            number(default.into()).and_then(|n| {
+                u8::try_from(n)
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("{name} takes 1 to {}", u8::MAX))
+            })
+        };
+        match name {
+            "random" => Ok(Self::Random),
+            "quote" => Ok(Self::Quote),
+            "translate" => Ok(Self::Translate),
+            "chars" => count(5).map(Self::Chars),
+            "words" => count(10).map(Self::Words),
+            "race" => count(15).map(Self::Race),
+            "timed" => number(30).map(Self::Timed),
+            "numbers" => count(10).map(Self::Numbers),
+            "symbols" => count(10).map(Self::Symbols),
+            "text" => count(10).map(Self::Text),
+            "drill" => count(10).map(Self::Drill),
+            "lesson" => count(10).map(Self::Lesson),
+            _ => Err(format!("unknown mode '{name}'")),
+        }
+    }
+}
+
+/// The screen currently shown
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Screen {
+    #[default]
+    Typing,
+    Results,
+    Stats,
+    Settings,
+    Leaderboard,
+    Trends,
+    History,
+    Compare,
+    Plan,
+    Profiles,
+    Badges,
+    Courses,
+}
+
+impl Screen {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Typing => "typing",
+            Self::Results => "results",
+            Self::Stats => "stats",
+            Self::Settings => "settings",
+            Self::Leaderboard => "leaderboard",
+            Self::Trends => "trends",
+            Self::History => "history",
+            Self::Compare => "compare",
+            Self::Plan => "plan",
+            Self::Profiles => "profiles",
+            Self::Badges => "badges",
+            Self::Courses => "courses",
+        }
+    }
+}
+
+/// How key presses are interpreted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputMode {
+    /// printable keys are keystrokes of the typing test
+    Test,
+    /// printable keys are text for the focused form field, no shortcuts
+    Form,
+    /// keys are shortcuts of the current screen
+    Menu,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SpanType {
+    #[default]
+    DEFAULT,
+    HIT,
+    MISS,
+}
+
+#[derive(Debug, Default)]
+pub struct TextSpan<'a> {
+    span_type: SpanType,
+    span: Span<'a>,
+}
+
+impl<'a> TextSpan<'a> {
+    pub fn new(span_type: SpanType, span: Span<'a>) -> Self {
+        Self { span_type, span }
+    }
+
+    pub fn default_with_text(text: String) -> Self {
+        let mut def = Self::default();
+        def.span.content = text.into();
+        def
+    }
+
+    /// The span styled according to its type
+    fn styled(&self, theme: &Theme) -> Span<'a> {
+        let style = match self.span_type {
+            SpanType::DEFAULT => theme.pending,
+            SpanType::HIT => theme.hit,
+            SpanType::MISS => theme.miss,
+        };
+        self.span.clone().style(style)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct App<'a> {
+    config: Config,
+    /// where settings changes are saved to, `None` to not save them
+    config_path: Option<PathBuf>,
+    theme: Theme,
+    /// the style file of the config laid over the theme
+    style: Option<StyleWatch>,
+    mode: Mode,
+    screen: Screen,
+    words: WordList,
+    wins: u8,
+    fails: u8,
+    /// the full text of the current round
+    target: String,
+    /// difficulty score of the current round
+    difficulty: f64,
+    remainder: TextSpan<'a>,
+    spans: Vec<TextSpan<'a>>,
+    exit: bool,
+    /// size of the terminal at the last draw
+    area: Rect,
+    /// the terminal was resized since the last draw
+    resized: bool,
+    miss_this_round: bool,
+    /// the expected character was missed at least once
+    miss_at_cursor: bool,
+    round_misses: usize,
+    /// characters of the current round skipped with Space
+    round_uncorrected: usize,
+    session: SessionState,
+    stats: SessionStats,
+    history: History,
+    /// the bots of the current round in race mode
+    race: Race,
+    /// selected row of the settings screen
+    settings_cursor: usize,
+    /// screens to go back to, the last one is below the current screen
+    screen_stack: Vec<Screen>,
+    /// field of the selected setting while it's edited
+    settings_field: Option<Field>,
+    /// why the edited setting can't be stored
+    settings_error: Option<String>,
+    /// the open jump-to popup
+    picker: Option<Picker>,
+    /// the open yes/no dialog
+    confirm: Option<Confirm>,
+    /// when Esc was pressed to quit a test with [`QuitProtection::DoubleEsc`]
+    quit_armed: Option<Instant>,
+    /// where a test quit while in progress is saved to, `None` to not save it
+    resume_path: Option<PathBuf>,
+    /// the test quit last time while it's offered for resuming
+    resumable: Option<Snapshot>,
+    /// when the test in progress was last saved for resuming, see
+    /// [`App::autosave`]
+    autosaved: Option<Instant>,
+    /// the open `:` command line
+    command_line: Option<TextInput>,
+    /// result of the last command, shown until the next key press
+    status: Option<String>,
+    /// commands run since `:macro record`
+    recording: Option<Vec<String>>,
+    /// selected category and date filter of the leaderboard
+    leaderboard_category: usize,
+    leaderboard_filter: DateFilter,
+    /// selected row of the history browser, counted from the newest entry
+    history_cursor: usize,
+    /// field of the selected history entry while it's edited
+    history_field: Option<(EntryField, TextInput)>,
+    /// the history browser shows practice sessions instead of single tests
+    history_grouped: bool,
+    /// indices into the history of the entries marked for the comparison
+    history_marked: Vec<usize>,
+    /// names of the profiles on the profile screen
+    profiles: Vec<String>,
+    /// selected row of the profile screen
+    profile_cursor: usize,
+    /// name of a new profile while it's entered
+    profile_input: Option<TextInput>,
+    /// sessions go on with the next item of today's plan
+    running_plan: bool,
+    /// the item of the plan being practiced
+    plan_item: Option<PlanItem>,
+    /// the loaded course packs, see [`course`]
+    courses: Vec<Course>,
+    course_progress: CourseProgress,
+    /// where the passed drills are saved to, `None` to not save them
+    course_path: Option<PathBuf>,
+    /// the course and the index of the drill being practiced
+    course_drill: Option<(usize, usize)>,
+    /// the selected drill of the courses screen
+    course_cursor: usize,
+    /// what the trends screen groups the history by
+    trend_period: Period,
+    /// screen to open on top of the typing test at launch
+    start_screen: Option<Screen>,
+    round_start: Option<Instant>,
+    word_start: Option<Instant>,
+    round_words: Vec<WordTiming>,
+    /// keystrokes per class of characters in the current round
+    round_classes: ClassTallies,
+    /// the last key typed came from the numeric keypad
+    keypad: bool,
+    /// the caret, the shake of a miss and the reveal of the results
+    animations: Animations,
+    /// how many colors the effects can use
+    colors: ColorDepth,
+    /// the best speed the last session beat, `None` if it beat none
+    personal_best: Option<f64>,
+    /// time to press each key in the current round
+    round_latencies: KeyLatencies,
+    /// time to press each pair of keys in the current round
+    round_bigrams: BigramLatencies,
+    /// positions of the characters missed in the current round
+    round_miss_positions: Vec<usize>,
+    /// the misses of the round just finished are shown until a key is
+    /// pressed, see [`SessionConfig::review_misses`]
+    reviewing: bool,
+    /// when the expected character became the expected one, that is when
+    /// the previous one was hit. The first key of a round has no latency.
+    expected_since: Option<Instant>,
+    /// the time frames are drawn at instead of the current time, to draw
+    /// the clocks at a chosen time in tests
+    draw_time: Option<Instant>,
+    /// something other than the clocks changed since the last frame, see
+    /// [`App::take_redraw`]
+    dirty: bool,
+    /// the clocks as of the last frame drawn
+    drawn_clocks: Clocks,
+    /// frame rate shown with `--frame-stats`, `None` to not count frames
+    frame_stats: Option<FrameStats>,
+    /// letters unlocked in the lessons and how well they're typed
+    lesson: Progression,
+    /// where the progress of the lessons is saved to, `None` to not save it
+    lesson_path: Option<PathBuf>,
+    /// milestones reached and when
+    badges: Badges,
+    /// where the badges are saved to, `None` to not save them
+    badges_path: Option<PathBuf>,
+    /// shown above the text of the round, see [`generator::TextGenerator::prompt`]
+    prompt: Option<String>,
+    /// source of all randomness of the rounds
+    rng: RoundRng,
+    generators: Generators,
+    /// flags pastes and impossibly fast typing in the current session
+    guard: InputGuard,
+    /// how long the keys of the current session are held
+    holds: KeyHolds,
+    /// `Some` in kiosk mode, see [`App::with_kiosk`]
+    kiosk: Option<Kiosk>,
+}
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+/// Number of words per round in timed mode
+const TIMED_ROUND_WORDS: usize = 10;
+/// Number of words per round of a loaded text
+const TEXT_ROUND_WORDS: u8 = 10;
+
+/// Longest time between the two presses of Esc that quit a test
+const DOUBLE_ESC: Duration = Duration::from_secs(1);
+
+/// Longest time between two draws
+const TICK_RATE: Duration = Duration::from_millis(100);
+
+const RESTART_LABEL: &str = "[ restart ]";
+
+/// Smallest terminal size any screen can be drawn in
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 7;
+
+const ALPHABET: [&str; 26] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+const SPECIALS: [&str; 31] = [
+    "!", "@", "#", "$", "%", "^", "&", "*", "(", ")", "-", "_", "+", "=", "{", "}", "[", "]", "|",
+    "\\", ":", ";", "\"", "'", "<", ">", ",", ".", "/", "?", "`",
+];
+
+impl App<'_> {
+    /// creates the app with the theme named in the config
+    pub fn new(config: Config, history: History) -> Result<Self> {
+        let theme = Theme::by_name(&config.theme).ok_or_else(|| {
+            eyre!(
+                "unknown theme '{}', available: {}",
+                config.theme,
+                Theme::NAMES.join(", ")
+            )
+        })?;
+        let style = match config.style.as_str() {
+            "" => None,
+            path => Some(StyleWatch::load(Path::new(path))?),
+        };
+        let mut generators = Generators::default();
+        if generators.get(&config.generator).is_none() {
+            return Err(eyre!(
+                "unknown generator '{}', available: {}",
+                config.generator,
+                generators.names().join(", ")
+            ));
+        }
+        generators.register(
+            "drill",
+            generator::Drill::new(Focus::from_history(&history)),
+        );
+        generators.register(
+            "translate",
+            generator::Translate::new(PhraseList::load(&config.phrases)?),
+        );
+        let list = |name: &str| {
+            WordList::by_name(name).ok_or_else(|| {
+                eyre!(
+                    "unknown word list '{name}', available: {}",
+                    WordList::NAMES.join(", ")
+                )
+            })
+        };
+        let mut words = list(&config.word_list)?;
+        if !config.mix.list.is_empty() {
+            words = words.mixed(list(&config.mix.list)?, config.mix.ratio);
+        }
+        let label = words.label();
+        let words = words
+            .filtered(&config.filter)
+            .ok_or_else(|| eyre!("no words of {label} pass the filter"))?;
+        let lesson_path = Progression::path(&config.profile);
+        let lesson = match &lesson_path {
+            Some(path) => Progression::load(path).wrap_err("loading the lesson progress failed")?,
+            None => Progression::default(),
+        };
+        let badges_path = Badges::path(&config.profile);
+        let badges = match &badges_path {
+            Some(path) => Badges::load(path).wrap_err("loading the badges failed")?,
+            None => Badges::default(),
+        };
+        let courses = match Course::dir(&config.profile) {
+            Some(dir) => Course::list(&dir).wrap_err("loading the courses failed")?,
+            None => vec![],
+        };
+        let course_path = CourseProgress::path(&config.profile);
+        let course_progress = match &course_path {
+            Some(path) => {
+                CourseProgress::load(path).wrap_err("loading the course progress failed")?
+            }
+            None => CourseProgress::default(),
+        };
+        let mut app = Self {
+            mode: config.mode,
+            config_path: Config::path(&config.profile),
+            courses,
+            course_progress,
+            course_path,
+            resume_path: Snapshot::path(&config.profile),
+            lesson,
+            lesson_path,
+            badges,
+            badges_path,
+            colors: ColorDepth::detect(),
+            config,
+            style,
+            history,
+            generators,
+            words,
+            ..Default::default()
+        };
+        app.use_theme(theme);
+        app.update_lesson_generator();
+        Ok(app)
+    }
+
+    /// makes the rounds reproducible, the same seed gives the same texts
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = RoundRng::seeded(seed);
+        self
+    }
+
+    /// practices `text` in rounds of words instead of generated text
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.generators.register("text", generator::Text::new(text));
+        if !matches!(self.mode, Mode::Text(_)) {
+            self.mode = Mode::Text(TEXT_ROUND_WORDS);
+        }
+        self
+    }
+
+    /// practices text of `generator` in rounds of words, registered as
+    /// `name`
+    ///
+    /// ```
+    /// use metyping::{App, GeneratorConfig, TextGenerator};
+    ///
+    /// #[derive(Debug)]
+    /// struct Countdown(usize);
+    ///
+    /// impl TextGenerator for Countdown {
+    ///     fn next(&mut self, config: GeneratorConfig) -> String {
+    ///         let words: Vec<String> = (0..config.count)
+    ///             .map(|_| {
+    ///                 self.0 = self.0.saturating_sub(1);
+    ///                 self.0.to_string()
+    ///             })
+    ///             .collect();
+    ///         words.join(" ")
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::default().with_generator("countdown", Countdown(100));
+    /// app.new_session()?;
+    /// assert!(app.target().starts_with("99 98 97"));
+    /// # Ok::<(), color_eyre::Report>(())
+    /// ```
+    pub fn with_generator(mut self, name: &str, generator: impl TextGenerator + 'static) -> Self {
+        self.generators.register(name, generator);
+        self.config.generator = name.into();
+        if !self.mode.uses_words() {
+            self.mode = Mode::Words(TEXT_ROUND_WORDS);
+        }
+        self
+    }
+
+    /// the text of the current round
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// opens `screen` on top of the typing test at launch
+    pub fn with_screen(mut self, screen: Screen) -> Self {
+        self.start_screen = Some(screen);
+        self
+    }
+
+    /// shows the frame rate and the time to draw a frame in a corner
+    pub fn with_frame_stats(mut self) -> Self {
+        self.frame_stats = Some(FrameStats::default());
+        self
+    }
+
+    /// starts with the first item of today's plan that isn't done yet
+    pub fn with_plan(mut self) -> Self {
+        self.running_plan = true;
+        self
+    }
+
+    /// runs the application's main loop until the user quits
+    pub fn run(&mut self, terminal: &mut tui::Tui) -> Result<()> {
+        let mut res = self.new_session();
+        if res.is_ok() && self.running_plan {
+            res = self.run_plan().map(|message| self.status = Some(message));
+        }
+        if res.is_err() {
+            println!("{:?}", res);
+            self.exit = true;
+        }
+        match self.start_screen.take() {
+            Some(Screen::Profiles) => self.open_profiles(),
+            Some(screen) => self.push_screen(screen),
+            None => {}
+        }
+        self.offer_resume()?;
+
+        self.dirty = true;
+        while !self.exit {
+            let now = Instant::now();
+            // idle until an event or a clock changes what's on the screen
+            if self.take_redraw(now) {
+                if mem::take(&mut self.resized) {
+                    // some terminals leave artifacts of the old size behind
+                    terminal.clear()?;
+                }
+                self.draw_time = Some(now);
+                // the viewport isn't at the top of the terminal when inline
+                self.area = terminal.draw(|frame| self.render_frame(frame))?.area;
+                if let Some(stats) = &mut self.frame_stats {
+                    stats.record(now, now.elapsed());
+                }
+            }
+            if event::poll(TICK_RATE)? {
+                self.handle_events().wrap_err("handle events failed")?;
+            }
+            self.tick(Instant::now())?;
+        }
+        let now = Instant::now();
+        // the reviewed round is done, resume with the next one
+        if self.reviewing {
+            self.end_review()?;
+        }
+        self.save_lesson()?;
+        self.save_snapshot(now)
+    }
+
+    /// the time the clocks are drawn at
+    fn draw_time(&self) -> Instant {
+        self.draw_time.unwrap_or_else(Instant::now)
+    }
+
+    fn render_frame(&self, frame: &mut Frame) {
+        frame.render_widget(self, frame.size());
+    }
+
+    fn handle_events(&mut self) -> Result<()> {
+        self.dirty = true;
+        match event::read()? {
+            Event::Key(key_event) => self
+                .handle_key_kind_at(key_event, Instant::now())
+                .wrap_err_with(|| format!("handling key event failed:\n{key_event:#?}")),
+            Event::Mouse(mouse_event) => self
+                .handle_mouse_event(mouse_event)
+                .wrap_err_with(|| format!("handling mouse event failed:\n{mouse_event:#?}")),
+            Event::Paste(text) => {
+                self.handle_paste(&text);
+                Ok(())
+            }
+            Event::Resize(_, _) => {
+                self.resized = true;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Result<()> {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(());
+        }
+        let click = Position::new(mouse_event.column, mouse_event.row);
+        let layout = self.typing_layout(self.area);
+
+        if self.config.header.show {
+            let tab = self
+                .header_tabs(layout.header)
+                .into_iter()
+                .find(|(_, area)| area.contains(click));
+            if let Some((mode, _)) = tab {
+                return self.switch_mode(mode);
+            }
+        }
+
+        match self.screen {
+            Screen::Typing if self.restart_button(layout.restart).contains(click) => {
+                self.switch_mode(self.mode)
+            }
+            Screen::Settings => self.click_settings(self.body_area(self.area), click),
+            _ => Ok(()),
+        }
+    }
+
+    /// pasted text is never typed, pasting during a test makes it practice
+    /// only
+    fn handle_paste(&mut self, text: &str) {
+        if let Some(input) = &mut self.command_line {
+            text.chars().for_each(|c| {
+                input.handle_key(KeyCode::Char(c).into());
+            });
+        } else if self.input_mode() == InputMode::Test {
+            self.guard.paste();
+            self.status = Some("pasting isn't typing, this session won't be recorded".into());
+        }
+    }
+
+    #[cfg(test)]
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
+        self.handle_key_event_at(key_event, Instant::now())
+    }
+
+    /// how the keys are interpreted on the current screen
+    fn input_mode(&self) -> InputMode {
+        match (self.screen, self.session) {
+            _ if self.confirm.is_some() || self.picker.is_some() || self.command_line.is_some() => {
+                InputMode::Form
+            }
+            (Screen::Typing, SessionState::Running { .. }) => InputMode::Test,
+            (Screen::Settings, _) if self.settings_field.is_some() => InputMode::Form,
+            (Screen::History, _) if self.history_field.is_some() => InputMode::Form,
+            (Screen::Profiles, _) if self.profile_input.is_some() => InputMode::Form,
+            _ => InputMode::Menu,
+        }
+    }
+
+    /// handles a key event that happened at `now`
+    pub fn handle_key_event_at(&mut self, key_event: KeyEvent, now: Instant) -> Result<()> {
+        let input_mode = self.input_mode();
+        let control = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        self.status = None;
+        if key_event.code != KeyCode::Esc {
+            self.quit_armed = None;
+        }
+        if self.handle_kiosk_key(key_event, now)? {
+            return Ok(());
+        }
+        if self.confirm.is_some() {
+            return self.handle_confirm_key(key_event);
+        }
+        if self.picker.is_some() {
+            return self.handle_picker_key(key_event);
+        }
+        if self.command_line.is_some() {
+            return self.handle_command_key(key_event);
+        }
+        if input_mode == InputMode::Menu && key_event.code == KeyCode::Char(':') {
+            self.open_command_line();
+            return Ok(());
+        }
+        if input_mode != InputMode::Form {
+            if control && key_event.code == KeyCode::Char('s') && self.screen != Screen::Settings {
+                self.open_settings();
+                return Ok(());
+            }
+            if control && key_event.code == KeyCode::Char('p') {
+                self.open_picker();
+                return Ok(());
+            }
+            if control && key_event.code == KeyCode::Char('u') && self.screen != Screen::Profiles {
+                self.open_profiles();
+                return Ok(());
+            }
+            if let (Screen::Typing, true, KeyCode::Char(c @ '1'..='9')) = (
+                self.screen,
+                key_event.modifiers.contains(KeyModifiers::ALT),
+                key_event.code,
+            ) {
+                return self.select_tab(c as usize - '1' as usize);
+            }
+            // shortcuts that aren't bound must not end up as typed text
+            if input_mode == InputMode::Test
+                && key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            {
+                return Ok(());
+            }
+        }
+
+        match self.screen {
+            Screen::Typing => self.handle_typing_key(key_event, now),
+            Screen::Results => self.handle_results_key(key_event),
+            Screen::Stats => self.handle_stats_key(key_event),
+            Screen::Settings => self.handle_settings_key(key_event),
+            Screen::Leaderboard => self.handle_leaderboard_key(key_event),
+            Screen::Trends => self.handle_trends_key(key_event),
+            Screen::History => self.handle_history_key(key_event),
+            Screen::Compare => self.handle_compare_key(key_event),
+            Screen::Plan => self.handle_plan_key(key_event),
+            Screen::Badges => self.handle_badges_key(key_event),
+            Screen::Courses => self.handle_courses_key(key_event),
+            Screen::Profiles => self.handle_profiles_key(key_event),
+        }
+    }
+
+    fn handle_typing_key(&mut self, key_event: KeyEvent, now: Instant) -> Result<()> {
+        if self.reviewing {
+            return self.handle_review_key(key_event, now);
+        }
+        match (self.session, key_event.code) {
+            (_, KeyCode::Esc) => self.quit_test(now),
+            (_, KeyCode::Tab) => self.switch_mode(self.mode)?,
+            (_, KeyCode::Left) => self.next_tab(-1)?,
+            (_, KeyCode::Right) => self.next_tab(1)?,
+            (SessionState::Ready, KeyCode::Char(' ') | KeyCode::Enter) => self.start_countdown(now),
+            (SessionState::Ready, KeyCode::Char(c @ '1'..='9')) => {
+                self.select_tab(c as usize - '1' as usize)?
+            }
+            (SessionState::Ready | SessionState::Countdown { .. }, _) => {}
+            (_, KeyCode::Enter) => self.finish_session(now)?,
+            (_, KeyCode::Char(' ')) if self.skips_word() => {
+                self.guard.keystroke(now);
+                let content = &self.remainder.span.content;
+                let rest: String = content.chars().take_while(|c| *c != ' ').collect();
+                let ends_round = rest.len() == content.len();
+                // the skipped characters are misses, not typed right
+                for c in rest.chars() {
+                    self.record_miss();
+                    self.round_uncorrected += 1;
+                    self.type_hit(c, now)?;
+                }
+                if !ends_round {
+                    self.round_classes.hit_key(' ', self.keypad);
+                    self.type_hit(' ', now)?;
+                }
+            }
+            (_, KeyCode::Char(v)) => {
+                self.keypad =
+                    key_event.state.contains(KeyEventState::KEYPAD) || self.config.session.numpad;
+                if let SessionState::Running { started } = &mut self.session {
+                    started.get_or_insert(now);
+                }
+                self.round_start.get_or_insert(now);
+                self.word_start.get_or_insert(now);
+                self.guard.keystroke(now);
+                if self.remainder.span.content.starts_with(v) {
+                    if let Some(since) = self.expected_since {
+                        self.record_latency(v, now.duration_since(since));
+                    }
+                    self.expected_since = Some(now);
+                    self.round_classes.hit_key(v, self.keypad);
+                    return self.type_hit(v, now);
+                }
+                self.record_miss();
+                self.animate(Anim::Shake, 1.0, 0.0, now);
+                if self.config.strict != Strict::Off {
+                    return self.strict_miss(now);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// records the time it took to hit the expected character `v`, for the
+    /// key and for the transition from the previous key in the same word
+    fn record_latency(&mut self, v: char, latency: Duration) {
+        self.round_latencies.record(v, latency);
+        let content = &self.remainder.span.content;
+        let typed = &self.target[..self.target.len() - content.len()];
+        match typed.chars().last() {
+            Some(prev) if !prev.is_whitespace() && !v.is_whitespace() => {
+                self.round_bigrams.record(format!("{prev}{v}"), latency);
+            }
+            _ => {}
+        }
+    }
+
+    /// Space is pressed in the middle of a word that it should skip
+    fn skips_word(&self) -> bool {
+        let content = &self.remainder.span.content;
+        let typed = &self.target[..self.target.len() - content.len()];
+        self.config.session.skip_on_space
+            && (self.mode.uses_words()
+                || matches!(
+                    self.mode,
+                    Mode::Text(_) | Mode::Drill(_) | Mode::Lesson(_) | Mode::Translate
+                ))
+            && self.config.strict == Strict::Off
+            && !content.is_empty()
+            && !content.starts_with(' ')
+            && !typed.is_empty()
+            && !typed.ends_with(' ')
+    }
+
+    /// advances past the expected character `v`
+    fn type_hit(&mut self, v: char, now: Instant) -> Result<()> {
+        let new_remainder = self.remainder.span.content.replacen(v, "", 1);
+
+        if v != ' ' && (new_remainder.is_empty() || new_remainder.starts_with(' ')) {
+            self.finish_word(new_remainder.len(), now);
+        }
+
+        if new_remainder.is_empty() {
+            self.finish_round(now);
+            let res = self.count(self.miss_this_round);
+            if res.is_err() {
+                self.exit_error("Counting up failed. Exiting");
+            }
+
+            if matches!(self.mode, Mode::Race(_)) || self.rounds_done() {
+                return self.finish_session(now);
+            }
+
+            let res = self.review_or_next_round();
+            if res.is_err() {
+                self.exit_error("Generating the next round failed");
+            }
+
+            return Ok(());
+        }
+
+        let span_type = if self.miss_at_cursor {
+            SpanType::MISS
+        } else {
+            SpanType::HIT
+        };
+        self.push_typed(v, span_type);
+        self.miss_at_cursor = false;
+
+        // I don't get why this is considered a "move out of the span"
+        // I'm trying to replace the contents of the span with a cloned
+        // String?
+        // self.remainder
+        //     .span
+        //     .content(self.remainder.span.content.replacen(v, "", 1));
+
+        //     Creating a new object and not just modifying the
+        //     existing one works, but is is best practice?
+        self.remainder.span = Span::default().content(new_remainder);
+        let typed = self.caret() as f64;
+        self.animate(Anim::Caret, typed - 1.0, typed, now);
+        Ok(())
+    }
+
+    /// the index of the expected character in the round
+    fn caret(&self) -> usize {
+        self.target.chars().count() - self.remainder.span.content.chars().count()
+    }
+
+    /// counts a wrong key press at the cursor
+    fn record_miss(&mut self) {
+        let position = self.target.chars().count() - self.remainder.span.content.chars().count();
+        if self.round_miss_positions.last() != Some(&position) {
+            self.round_miss_positions.push(position);
+        }
+        self.miss_this_round = true;
+        self.round_misses += 1;
+        self.miss_at_cursor = true;
+        match self.remainder.span.content.chars().next() {
+            Some(expected) if !self.config.practice => {
+                self.round_classes.miss_key(expected, self.keypad)
+            }
+            _ => {}
+        }
+    }
+
+    /// appends a typed character to the completed spans, extending the last
+    /// span if it is of the same type
+    fn push_typed(&mut self, c: char, span_type: SpanType) {
+        match self.spans.last_mut() {
+            Some(last) if last.span_type == span_type => {
+                last.span.content.to_mut().push(c);
+            }
+            _ => {
+                self.spans
+                    .push(TextSpan::new(span_type, Span::raw(c.to_string())));
+            }
+        }
+    }
+
+    /// records the timing of the word that ends `remaining` bytes before the
+    /// end of the round
+    fn finish_word(&mut self, remaining: usize, now: Instant) {
+        let typed = &self.target[..self.target.len() - remaining];
+        let word = typed.rsplit(' ').next().unwrap_or_default();
+        let leading_space = usize::from(typed.len() > word.len());
+        let start = self.word_start.replace(now).unwrap_or(now);
+        self.round_words.push(WordTiming {
+            word: word.to_string(),
+            chars: word.chars().count() + leading_space,
+            duration: now.duration_since(start),
+        });
+    }
+
+    /// ends the round or session on a miss in strict mode
+    fn strict_miss(&mut self, now: Instant) -> Result<()> {
+        self.finish_partial_round(now);
+        self.count(true)?;
+        if self.config.strict == Strict::Session
+            || matches!(self.mode, Mode::Race(_))
+            || self.rounds_done()
+        {
+            return self.finish_session(now);
+        }
+        self.round_start = None;
+        self.word_start = None;
+        self.review_or_next_round()
+    }
+
+    /// records the typed part of the current round when the time is up
+    fn finish_partial_round(&mut self, now: Instant) {
+        let typed = self.target.len() - self.remainder.span.content.len();
+        if typed > 0 {
+            self.target.truncate(typed);
+            self.finish_round(now);
+        }
+    }
+
+    /// records the finished round, the next one starts right away
+    fn finish_round(&mut self, now: Instant) {
+        let start = self.round_start.replace(now).unwrap_or(now);
+        self.stats.push(RoundRecord {
+            target: self.target.clone(),
+            duration: now.duration_since(start),
+            missed: self.miss_this_round,
+            misses: if self.config.practice {
+                0
+            } else {
+                self.round_misses
+            },
+            difficulty: self.difficulty,
+            words: mem::take(&mut self.round_words),
+            classes: mem::take(&mut self.round_classes),
+            latencies: mem::take(&mut self.round_latencies),
+            bigrams: mem::take(&mut self.round_bigrams),
+            miss_positions: mem::take(&mut self.round_miss_positions),
+            uncorrected: if self.config.practice {
+                0
+            } else {
+                mem::take(&mut self.round_uncorrected)
+            },
+        });
+        self.record_lesson_round();
+    }
+
+    /// opens `screen` on top of the current one, Esc goes back
+    fn push_screen(&mut self, screen: Screen) {
+        if screen != self.screen {
+            self.screen_stack.push(self.screen);
+            self.screen = screen;
+        }
+    }
+
+    /// goes back to the previous screen
+    fn pop_screen(&mut self) {
+        self.screen = self.screen_stack.pop().unwrap_or_default();
+    }
+
+    /// shows `screen` with nothing to go back to
+    fn show_root(&mut self, screen: Screen) {
+        self.screen_stack.clear();
+        self.screen = screen;
+    }
+
+    /// shows the results and stores the session in the history
+    fn finish_session(&mut self, now: Instant) -> Result<()> {
+        self.show_root(Screen::Results);
+        self.animate(Anim::Reveal, 0.0, 1.0, now);
+        self.session = SessionState::Finished;
+        self.save_lesson()?;
+        if self.config.practice || self.guard.flag().is_some() {
+            return Ok(());
+        }
+        let Some(entry) = self.session_entry() else {
+            return Ok(());
+        };
+        self.personal_best = self
+            .history
+            .entries()
+            .iter()
+            .filter(|best| best.category() == entry.category())
+            .map(|best| best.wpm)
+            .max_by(f64::total_cmp)
+            .filter(|best| entry.wpm > *best);
+        if self.personal_best.is_some() && self.config.effects.celebrate {
+            self.animate(Anim::Celebrate, 0.0, 1.0, now);
+        }
+        self.send_report(&entry);
+        self.history
+            .push(entry.clone())
+            .wrap_err("saving the session to the history failed")?;
+        self.record_badges()?;
+        self.record_course_drill(&entry)
+    }
+
+    /// the current or last session as stored in the history, `None` before
+    /// the first round is done
+    pub fn session_entry(&self) -> Option<HistoryEntry> {
+        if self.stats.rounds.is_empty() {
+            return None;
+        }
+        let mut entry = self.history_entry(&self.stats, self.mode, &self.config.generator);
+        entry.plan = self.plan_attribution();
+        Some(entry)
+    }
+
+    /// the rounds of `stats` as stored in the history, for a session of
+    /// `mode` with words of `generator`
+    fn history_entry(&self, stats: &SessionStats, mode: Mode, generator: &str) -> HistoryEntry {
+        let mut entry = HistoryEntry::new(stats, mode, Utc::now());
+        if mode.uses_words() {
+            entry.word_list = Some(match generator {
+                "words" => self.words.label(),
+                generator => format!("{generator} {}", self.words.label()),
+            });
+        }
+        entry
+    }
+
+    /// resets all counters and starts over with a fresh round
+    pub fn new_session(&mut self) -> Result<()> {
+        self.wins = 0;
+        self.fails = 0;
+        self.stats.clear();
+        self.round_start = None;
+        self.word_start = None;
+        self.reviewing = false;
+        self.guard.reset();
+        self.holds.reset();
+        self.animations.clear();
+        self.personal_best = None;
+        self.generators.reset();
+        self.show_root(Screen::Typing);
+        self.session = match self.mode {
+            Mode::Timed(_) => SessionState::Ready,
+            _ => SessionState::default(),
+        };
+        self.next_round()
+    }
+
+    /// counts down before a timed session
+    fn start_countdown(&mut self, now: Instant) {
+        match self.config.session.countdown {
+            0 => self.start_running(now),
+            secs => {
+                self.session = SessionState::Countdown {
+                    until: now + Duration::from_secs(secs.into()),
+                }
+            }
+        }
+    }
+
+    fn start_running(&mut self, now: Instant) {
+        if self.config.session.start_on_keypress {
+            self.session = SessionState::Running { started: None };
+        } else {
+            self.session = SessionState::Running { started: Some(now) };
+            self.round_start = Some(now);
+            self.word_start = Some(now);
+        }
+    }
+
+    /// quits unless a test is in progress and [`QuitProtection`] asks to be
+    /// sure
+    fn quit_test(&mut self, now: Instant) {
+        if !self.test_in_progress() || self.kiosk.is_some() {
+            return self.exit();
+        }
+        match self.config.quit_protection {
+            QuitProtection::Dialog => {
+                // races and flagged tests can't be resumed
+                let saved = self.resume_path.is_some() && self.snapshot(now).is_some();
+                let question = if saved {
+                    "Quit? The test is saved to resume."
+                } else {
+                    "Quit and lose this test?"
+                };
+                self.confirm(question, ConfirmAction::Quit)
+            }
+            QuitProtection::DoubleEsc => {
+                let armed = self
+                    .quit_armed
+                    .take()
+                    .is_some_and(|at| now.duration_since(at) <= DOUBLE_ESC);
+                if armed {
+                    self.exit();
+                } else {
+                    self.quit_armed = Some(now);
+                    self.status = Some("press Esc again to quit".into());
+                }
+            }
+            QuitProtection::Off => self.exit(),
+        }
+    }
+
+    /// something was typed in a test that isn't over yet
+    fn test_in_progress(&self) -> bool {
+        matches!(self.session, SessionState::Running { started: Some(_) })
+    }
+
+    /// the number of rounds the session ends after, `None` if it doesn't
+    fn round_limit(&self) -> Option<u16> {
+        match (self.mode, self.config.session.rounds) {
+            (Mode::Timed(_) | Mode::Race(_), _) | (_, 0) => None,
+            (_, rounds) => Some(rounds.into()),
+        }
+    }
+
+    /// rounds played in the current session
+    fn rounds_played(&self) -> u16 {
+        u16::from(self.wins) + u16::from(self.fails)
+    }
+
+    fn rounds_done(&self) -> bool {
+        self.round_limit()
+            .is_some_and(|limit| self.rounds_played() >= limit)
+    }
+
+    /// the session's time limit in timed mode
+    fn time_limit(&self) -> Option<Duration> {
+        match self.mode {
+            Mode::Timed(secs) => Some(Duration::from_secs(secs.into())),
+            _ => None,
+        }
+    }
+
+    /// advances the session state, called on every tick of the main loop
+    pub fn tick(&mut self, now: Instant) -> Result<()> {
+        self.tick_attract(now);
+        self.reload_style(now);
+        self.autosave(now)?;
+        match self.session {
+            SessionState::Countdown { until } if now >= until => {
+                self.start_running(now);
+                self.dirty = true;
+            }
+            SessionState::Running { .. } => {
+                let time_up = self
+                    .time_limit()
+                    .is_some_and(|limit| self.session.time_left(limit, now).is_zero());
+                if time_up {
+                    self.dirty = true;
+                    self.finish_partial_round(now);
+                    self.finish_session(now)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn exit(&mut self) {
+        if self.kiosk_locks("quitting") {
+            return;
+        }
+        self.exit = true;
+    }
+
+    fn exit_error(&mut self, msg: &str) {
+        println!("Exiting with error: {}", msg);
+        self.exit = true;
+    }
+    fn count(&mut self, fail: bool) -> Result<()> {
+        if fail {
+            // warming up, misses don't count
+            if !self.config.practice {
+                self.fails += 1;
+            }
+        } else {
+            self.wins += 1;
+        }
+        Ok(())
+    }
+
+    fn next_round(&mut self) -> Result<()> {
+        let (generator, count) = match self.mode {
+            Mode::Random => ("chars", 2),
+            Mode::Chars(n) => ("chars", n.max(1).into()),
+            Mode::Words(n) | Mode::Race(n) => (self.config.generator.as_str(), n.max(1).into()),
+            Mode::Quote => ("quotes", 1),
+            Mode::Translate => ("translate", 1),
+            Mode::Timed(_) => (self.config.generator.as_str(), TIMED_ROUND_WORDS),
+            Mode::Numbers(n) => ("numbers", n.max(1).into()),
+            Mode::Symbols(n) => ("symbols", n.max(1).into()),
+            Mode::Text(n) => ("text", n.max(1).into()),
+            Mode::Drill(n) => ("drill", n.max(1).into()),
+            Mode::Lesson(n) => ("lesson", n.max(1).into()),
+        };
+        let generator = self
+            .generators
+            .get_mut(generator)
+            .ok_or_else(|| eyre!("unknown generator '{generator}'"))?;
+        let text = generator.next(GeneratorConfig {
+            rng: &mut *self.rng,
+            words: &self.words,
+            count,
+        });
+        self.prompt = generator.prompt().map(String::from);
+        if let Mode::Race(_) = self.mode {
+            let mut bots = self.config.race.bots.clone();
+            if self.config.race.ghost {
+                bots.extend(BotConfig::ghost(self.history.entries()));
+            }
+            self.race = Race::new(&bots, text.chars().count(), &mut *self.rng);
+        }
+        self.spans.clear();
+        self.round_words.clear();
+        self.round_classes = ClassTallies::default();
+        self.round_latencies = KeyLatencies::default();
+        self.round_bigrams = BigramLatencies::default();
+        self.round_miss_positions.clear();
+        self.expected_since = None;
+        self.difficulty = difficulty::score(&text, self.config.layout);
+        self.target = text.clone();
+        self.remainder = TextSpan::default_with_text(text);
+        self.miss_this_round = false;
+        self.miss_at_cursor = false;
+        self.round_misses = 0;
+        self.round_uncorrected = 0;
+        self.animations.stop(Anim::Caret);
+        Ok(())
+    }
+
+    /// everything below the header
+    fn body_area(&self, area: Rect) -> Rect {
+        let header = u16::from(self.config.header.show).min(area.height);
+        Rect::new(area.x, area.y + header, area.width, area.height - header)
+    }
+
+    /// the terminal size needed to draw the current screen
+    fn min_size(&self) -> (u16, u16) {
+        let header = u16::from(self.config.header.show);
+        match self.screen {
+            Screen::Typing => {
+                let text = self.target.chars().count() as u16 + 4;
+                (MIN_WIDTH.max(text), MIN_HEIGHT + header)
+            }
+            Screen::Results | Screen::Compare => (MIN_WIDTH, 20 + header),
+            Screen::Stats
+            | Screen::Settings
+            | Screen::Leaderboard
+            | Screen::Trends
+            | Screen::History
+            | Screen::Plan
+            | Screen::Profiles
+            | Screen::Badges
+            | Screen::Courses => (MIN_WIDTH, 14 + header),
+        }
+    }
+
+    fn render_too_small(&self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = self.min_size();
+        let text = vec![
+            Line::from("terminal too small"),
+            Line::from(format!("need {width}x{height}")),
+            Line::from(format!("have {}x{}", area.width, area.height)),
+        ];
+        let top = area.height.saturating_sub(text.len() as u16) / 2;
+        let area = Rect::new(area.x, area.y + top, area.width, area.height - top);
+        Paragraph::new(text)
+            .style(self.theme.miss)
+            .centered()
+            .render(area, buf);
+    }
+
+    fn typing_layout(&self, area: Rect) -> TypingLayout {
+        TypingLayout::new(area, self.config.header.show)
+    }
+
+    fn build_stats_layout(area: Rect) -> Rc<[Rect]> {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Percentage(45),
+                Constraint::Percentage(10),
+                Constraint::Percentage(45),
+            ])
+            .margin(2)
+            .split(area)
+    }
+
+    /// a block with the border and title configured for the panel
+    fn panel_block<'b>(&self, panel: &'b PanelConfig) -> Block<'b> {
+        let title = Title::from(Span::styled(panel.title.as_str(), self.theme.title));
+        Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(panel.border.borders())
+            .border_type(panel.border.border_type())
+            .border_style(self.theme.border)
+    }
+
+    /// the value of the wins or fails panel, led by `symbol` if symbols are on
+    fn render_stats_block(
+        &self,
+        layout: Rect,
+        buf: &mut Buffer,
+        panel: &PanelConfig,
+        value: &u8,
+        symbol: &str,
+    ) {
+        let value = if self.config.accessibility.symbols {
+            format!("{symbol} {value}")
+        } else {
+            value.to_string()
+        };
+        let text = Text::from(vec![Line::styled(value, self.theme.value)]);
+        Paragraph::new(text)
+            .centered()
+            .block(self.panel_block(panel))
+            .render(layout, buf);
+    }
+
+    /// the wins and fails in a line, for short terminals
+    fn render_compact_stats(&self, area: Rect, buf: &mut Buffer) {
+        let panels = &self.config.panels;
+        let mut spans = vec![];
+        for (panel, value, symbol) in [
+            (&panels.wins, self.wins, Accessibility::HIT),
+            (&panels.fails, self.fails, Accessibility::MISS),
+        ] {
+            if self.config.accessibility.symbols {
+                spans.push(Span::styled(format!("{symbol} "), self.theme.value));
+            }
+            spans.push(Span::styled(
+                format!("{} ", panel.title.trim()),
+                self.theme.title,
+            ));
+            spans.push(Span::styled(format!("{value}    "), self.theme.value));
+        }
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, buf);
+    }
+
+    /// the speed of each round of the session, the latest ones if they
+    /// don't all fit
+    fn render_round_chart(&self, area: Rect, buf: &mut Buffer) {
+        const BAR_WIDTH: u16 = 4;
+        let fit = (area.width.saturating_sub(2) / (BAR_WIDTH + 1)) as usize;
+        let rounds = &self.stats.rounds;
+        let labels: Vec<String> = (1..=rounds.len()).map(|n| n.to_string()).collect();
+        let bars: Vec<(&str, u64)> = labels
+            .iter()
+            .zip(rounds)
+            .map(|(label, round)| (label.as_str(), round.wpm().round() as u64))
+            .skip(rounds.len().saturating_sub(fit))
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(" WPM PER ROUND ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.theme.border);
+        BarChart::default()
+            .block(block)
+            .data(&bars)
+            .bar_width(BAR_WIDTH)
+            .bar_gap(1)
+            .bar_style(self.theme.hit)
+            .value_style(self.theme.value.add_modifier(Modifier::REVERSED))
+            .render(area, buf);
+    }
+
+    /// the phrase to translate, in a block if there's room
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let prompt = self.prompt.as_deref().unwrap_or_default();
+        let mut paragraph = Paragraph::new(prompt).style(self.theme.value).centered();
+        if area.height >= 3 {
+            let block = Block::default()
+                .title(Span::styled(" TRANSLATE ", self.theme.title))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(self.theme.border);
+            // the phrase in the middle of the block
+            let inner = block.inner(area);
+            let top = inner.height.saturating_sub(1) / 2;
+            paragraph = paragraph.block(block.padding(Padding::top(top)));
+        }
+        paragraph.render(area, buf);
+    }
+
+    /// one progress bar per racer
+    fn render_race(&self, area: Rect, buf: &mut Buffer) {
+        let total = self.target.chars().count().max(1);
+        let elapsed = self
+            .round_start
+            .map(|s| self.draw_time().saturating_duration_since(s))
+            .unwrap_or_default();
+        let typed = total - self.remainder.span.content.chars().count();
+
+        let mut racers = vec![("you", typed, self.theme.value)];
+        for bot in &self.race.bots {
+            racers.push((&bot.name, bot.progress(elapsed), self.theme.pending));
+        }
+
+        // too short for the bars, just the shares of the text typed
+        if (area.height as usize) < racers.len() + 2 {
+            let spans: Vec<Span> = racers
+                .into_iter()
+                .flat_map(|(name, progress, style)| {
+                    [
+                        Span::styled(format!("{name} "), style),
+                        Span::styled(format!("{}%  ", 100 * progress / total), self.theme.value),
+                    ]
+                })
+                .collect();
+            return Paragraph::new(Line::from(spans))
+                .centered()
+                .render(area, buf);
+        }
+
+        let bar_width = (area.width as usize).saturating_sub(16);
+        let lines: Vec<Line> = racers
+            .into_iter()
+            .map(|(name, progress, style)| {
+                let done = bar_width * progress / total;
+                Line::from(vec![
+                    Span::styled(format!("{name:>8} "), style),
+                    Span::styled("█".repeat(done), self.theme.hit),
+                    Span::styled("░".repeat(bar_width - done), self.theme.border),
+                    Span::raw(format!(" {:>3}%", 100 * progress / total)),
+                ])
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(Span::styled(" RACE ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    fn render_input_box(&self, area: Rect, buf: &mut Buffer) {
+        if self.reviewing {
+            return self.render_review(area, buf);
+        }
+        let mut sspans: Vec<Span> = vec![];
+        // tells whether the round is still clean without relying on color
+        if self.config.accessibility.symbols {
+            sspans.push(if self.miss_this_round {
+                Span::styled(format!("{} ", Accessibility::MISS), self.theme.miss)
+            } else {
+                Span::styled(format!("{} ", Accessibility::HIT), self.theme.hit)
+            });
+        }
+        let marker = sspans.first().map_or(0, Span::width) as u16;
+        self.spans.iter().for_each(|line| {
+            sspans.push(line.styled(&self.theme));
+        });
+        let remainder = self.remainder.styled(&self.theme);
+        match remainder.content.chars().next() {
+            // keep the missed character marked until it's typed correctly
+            Some(next) if self.miss_at_cursor => {
+                let rest = remainder.content[next.len_utf8()..].to_string();
+                sspans.push(Span::styled(next.to_string(), self.theme.miss));
+                sspans.push(Span::styled(rest, remainder.style));
+            }
+            _ => sspans.push(remainder),
+        }
+
+        let text = Line::from(sspans);
+        let panel = &self.config.panels.input;
+        let frame = if panel.border == config::BorderStyle::None {
+            0
+        } else {
+            2
+        };
+        let width = text.width().max(panel.title.chars().count()) + frame;
+
+        let h_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Min(1),
+                Constraint::Length(width as u16),
+                Constraint::Min(1),
+            ])
+            .split(area);
+
+        // a miss shakes the text a cell to either side
+        let mut input = h_layout[1];
+        let shake = self.animated(Anim::Shake, 0.0).round() as i32;
+        input.x = (i32::from(input.x) + shake).clamp(
+            i32::from(area.x),
+            i32::from(area.right() - input.width.min(area.width)),
+        ) as u16;
+        let block = self.panel_block(panel);
+        // a title takes the top row even without borders
+        let inner = block.inner(input);
+        Paragraph::new(text).block(block).render(input, buf);
+
+        let typed = Rect {
+            x: inner.x + marker,
+            width: inner.width.saturating_sub(marker),
+            ..inner
+        };
+        self.render_text_effect(typed, buf);
+        let caret = self.animated(Anim::Caret, self.caret() as f64).round() as u16 + marker;
+        if caret < marker + self.target.chars().count() as u16 && caret < inner.width {
+            buf.get_mut(inner.x + caret, inner.y)
+                .set_style(self.theme.caret);
+        }
+        if let Some(pace) = self.pace_position(self.draw_time()) {
+            let pace = pace as u16 + marker;
+            if pace < marker + self.target.chars().count() as u16 && pace < inner.width {
+                buf.get_mut(inner.x + pace, inner.y)
+                    .set_style(self.theme.pace);
+            }
+        }
+    }
+
+    /// index of the character a typist at the configured pace would be at,
+    /// `None` without a pace
+    fn pace_position(&self, now: Instant) -> Option<usize> {
+        let wpm = self.config.session.pace_wpm;
+        if wpm == 0 || !matches!(self.session, SessionState::Running { .. }) {
+            return None;
+        }
+        let elapsed = self.round_start.map_or(0.0, |start| {
+            now.saturating_duration_since(start).as_secs_f64()
+        });
+        Some((elapsed * f64::from(wpm) * 5.0 / 60.0) as usize)
+    }
+
+    /// the start prompt and countdown of timed sessions, drawn over `area`
+    fn render_session_overlay(&self, area: Rect, buf: &mut Buffer) {
+        let text = match self.session {
+            SessionState::Ready => "press Space to start".to_string(),
+            SessionState::Countdown { .. } => self
+                .session
+                .countdown_left(self.draw_time())
+                .unwrap_or_default()
+                .to_string(),
+            _ => return,
+        };
+
+        let width = (text.chars().count() as u16 + 6).min(area.width);
+        let height = 3.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        Clear.render(popup, buf);
+        Paragraph::new(text)
+            .style(self.theme.value)
+            .centered()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(self.theme.border),
+            )
+            .render(popup, buf);
+    }
+
+    /// the clickable area of the restart button inside of `area`
+    fn restart_button(&self, area: Rect) -> Rect {
+        let width = RESTART_LABEL.len() as u16;
+        let x = area.x + area.width.saturating_sub(width) / 2;
+        Rect::new(x, area.y, width.min(area.width), area.height.min(1))
+    }
+
+    fn render_restart_button(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(RESTART_LABEL)
+            .style(self.theme.title)
+            .render(self.restart_button(area), buf);
+    }
+
+    fn render_difficulty(&self, area: Rect, buf: &mut Buffer) {
+        // the letters tell more than the difficulty in a lesson
+        if let Mode::Lesson(_) = self.mode {
+            return self.render_lesson_letters(area, buf);
+        }
+        let mut text = format!("difficulty {:.2}", self.difficulty);
+        if let Some(limit) = self.time_limit() {
+            let left = self.session.time_left(limit, self.draw_time());
+            text.push_str(&format!(" · {}s left", left.as_secs_f64().ceil()));
+        }
+        Paragraph::new(text)
+            .style(self.theme.border)
+            .centered()
+            .render(area, buf);
+    }
+
+    /// the typing test in the areas of `layout`
+    fn render_typing(&self, layout: &TypingLayout, buf: &mut Buffer) {
+        match (self.mode, layout.breakpoint) {
+            (Mode::Race(_), _) => self.render_race(layout.stats, buf),
+            (Mode::Translate, _) => self.render_prompt(layout.stats, buf),
+            (_, Breakpoint::Compact) => self.render_compact_stats(layout.stats, buf),
+            _ => {
+                let layout_stats = App::build_stats_layout(layout.stats);
+                let panels = &self.config.panels;
+                let (wins, fails) = (&self.wins, &self.fails);
+                self.render_stats_block(
+                    layout_stats[0],
+                    buf,
+                    &panels.wins,
+                    wins,
+                    Accessibility::HIT,
+                );
+                self.render_stats_block(
+                    layout_stats[2],
+                    buf,
+                    &panels.fails,
+                    fails,
+                    Accessibility::MISS,
+                );
+            }
+        }
+
+        self.render_input_box(layout.input, buf);
+        self.render_difficulty(layout.info, buf);
+        self.render_restart_button(layout.restart, buf);
+        if let Some(chart) = layout.chart {
+            self.render_round_chart(chart, buf);
+        }
+        // the prompt needs three rows, it covers the text in a compact layout
+        let overlay = if layout.stats.height < 3 {
+            layout.stats.union(layout.input)
+        } else {
+            layout.stats
+        };
+        self.render_session_overlay(overlay, buf);
+        Paragraph::new(
+            " Esc: quit  Tab: restart  Enter: finish  ←/→: mode  ^S: settings  ^P: jump to",
+        )
+        .style(self.theme.title)
+        .render(layout.help, buf);
+    }
+}
+
+/// The whole app as a widget, to embed it in another TUI
+///
+/// ```
+/// use metyping::App;
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+///
+/// let app = App::default().with_text("hello");
+/// let area = Rect::new(0, 0, 80, 24);
+/// let mut buf = Buffer::empty(area);
+/// (&app).render(area, &mut buf);
+/// ```
+impl Widget for &App<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (min_width, min_height) = self.min_size();
+        if area.width < min_width || area.height < min_height {
+            return self.render_too_small(area, buf);
+        }
+
+        let layout = self.typing_layout(area);
+
+        if self.config.header.show {
+            self.render_header(layout.header, buf);
+        }
+
+        let body = self.body_area(area);
+        match self.screen {
+            Screen::Typing => self.render_typing(&layout, buf),
+            Screen::Results => self.render_results(body, buf),
+            Screen::Stats => self.render_stats(body, buf),
+            Screen::Settings => self.render_settings(body, buf),
+            Screen::Leaderboard => self.render_leaderboard(body, buf),
+            Screen::Trends => self.render_trends(body, buf),
+            Screen::History => self.render_history(body, buf),
+            Screen::Compare => self.render_compare(body, buf),
+            Screen::Plan => self.render_plan(body, buf),
+            Screen::Badges => self.render_badges(body, buf),
+            Screen::Courses => self.render_courses(body, buf),
+            Screen::Profiles => self.render_profiles(body, buf),
+        }
+        self.render_command_line(area, buf);
+        self.render_picker(area, buf);
+        self.render_confirm(area, buf);
+        self.render_kiosk(area, buf);
+        self.render_attract(area, buf);
+        self.render_frame_stats(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{CharClass, Tally};
+
+    #[test]
+    fn handle_key_event() {
+        let mut app = App::default();
+        let res = app.next_round();
+        assert!(res.is_ok());
+        assert!(app.remainder.span.content.len() == 2);
+
+        // Why does setting the content in the span move the value?
+        // Diagnostics:
+        // 1. `app.remainder.span` partially moved due to this method call [E0382]
+        // 2. you can `clone` the value and consume it, but this might not be your desired behavior: `.clone()` [E0382]
+        // let _ = app.remainder.span.content("ab");
+
+        // Replacing the whole object works
+        // Seems wasteful if I just want to replace existing content.
+        app.remainder.span = Span::default().content("ab");
+
+        // 1. borrow of partially moved value: `app`
+        //    partial move occurs because `app.remainder.span` has type `ratatui::prelude::Span<'_>`, which does not implement the `Copy` trait [E0382]
+        let _ = app.handle_key_event(KeyCode::Char('a').into());
+        assert!(!app.miss_this_round);
+        assert!(app.remainder.span.content == "b");
+
+        let _ = app.handle_key_event(KeyCode::Char('c').into());
+        assert!(app.miss_this_round);
+        assert!(app.remainder.span.content == "b");
+
+        let _ = app.handle_key_event(KeyCode::Char('b').into());
+        assert!(app.wins == 0);
+        assert!(app.fails == 1);
+        assert!(app.remainder.span.content.len() == 2);
+
+        // Can't get the value of content? Not even when I clone it?
+        // let c = app.remainder.span.content.to_string().clone();
+        // assert_eq!(c , "b");
+
+        let mut app = App::default();
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert!(app.exit);
+    }
+
+    #[test]
+    fn render_tiny_terminal() {
+        let mut app = App::default();
+        app.next_round().unwrap();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 5));
+        (&app).render(buf.area, &mut buf);
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("too small"));
+        assert!(text.contains("need 40x8"));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 14));
+        (&app).render(buf.area, &mut buf);
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("WINS"));
+    }
+
+    #[test]
+    fn translate_the_phrase_above() {
+        let mut app = App {
+            mode: Mode::Translate,
+            ..Default::default()
+        }
+        .with_seed(3);
+        app.next_round().unwrap();
+        let phrases = PhraseList::default();
+        let phrase = phrases
+            .phrases
+            .iter()
+            .find(|phrase| phrase.translation == app.target)
+            .unwrap();
+        assert_eq!(app.prompt.as_deref(), Some(phrase.source.as_str()));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 16));
+        (&app).render(buf.area, &mut buf);
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("TRANSLATE"));
+        assert!(text.contains(&phrase.source));
+    }
+
+    #[test]
+    fn click_tabs_and_restart() {
+        let mut app = App {
+            area: Rect::new(0, 0, 80, 24),
+            ..Default::default()
+        };
+        app.next_round().unwrap();
+        app.wins = 3;
+
+        let click = |x, y| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: x,
+            row: y,
+            modifiers: event::KeyModifiers::NONE,
+        };
+
+        let layout = app.typing_layout(app.area);
+        let (mode, tab) = app.header_tabs(layout.header)[2];
+        assert_eq!(mode, Mode::Words(10));
+        app.handle_mouse_event(click(tab.x + 1, tab.y)).unwrap();
+        assert_eq!(app.mode, Mode::Words(10));
+        assert_eq!(app.wins, 0);
+        assert_eq!(app.target.split(' ').count(), 10);
+
+        app.wins = 3;
+        app.handle_mouse_event(click(0, layout.restart.y)).unwrap();
+        assert_eq!(app.wins, 3);
+        let button = app.restart_button(layout.restart);
+        app.handle_mouse_event(click(button.x, button.y)).unwrap();
+        assert_eq!(app.wins, 0);
+    }
+
+    #[test]
+    fn switch_tabs_with_keys() {
+        let mut app = App::default();
+        app.next_round().unwrap();
+
+        app.handle_key_event(KeyCode::Right.into()).unwrap();
+        assert_eq!(app.mode, Mode::Chars(5));
+        app.handle_key_event(KeyCode::Left.into()).unwrap();
+        app.handle_key_event(KeyCode::Left.into()).unwrap();
+        assert_eq!(app.mode, Mode::Symbols(10));
+
+        // plain digits are typed, with Alt they pick a tab
+        app.handle_key_event(KeyCode::Char('4').into()).unwrap();
+        assert_eq!(app.mode, Mode::Symbols(10));
+        let alt = KeyEvent::new(KeyCode::Char('4'), KeyModifiers::ALT);
+        app.handle_key_event(alt).unwrap();
+        // the 4 started a test, so it's only lost when confirmed
+        assert_eq!(app.mode, Mode::Symbols(10));
+        app.handle_key_event(KeyCode::Tab.into()).unwrap();
+        app.handle_key_event(KeyCode::Char('n').into()).unwrap();
+        assert!(app.test_in_progress());
+        app.handle_key_event(alt).unwrap();
+        app.handle_key_event(KeyCode::Char('y').into()).unwrap();
+        assert_eq!(app.mode, Mode::Quote);
+        assert!(app.target.ends_with('.'));
+    }
+
+    #[test]
+    fn practice_mode_does_not_count_misses() {
+        let mut app = App::default();
+        app.config.practice = true;
+        app.target = "ab".into();
+        app.remainder = TextSpan::default_with_text(app.target.clone());
+
+        for c in "axb".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        assert_eq!(app.fails, 0);
+        assert_eq!(app.stats.accuracy(), 1.0);
+
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(app.history.entries().is_empty());
+    }
+
+    #[test]
+    fn timed_session_with_countdown() {
+        let mut app = App {
+            mode: Mode::Timed(10),
+            ..Default::default()
+        };
+        app.new_session().unwrap();
+        assert_eq!(app.session, SessionState::Ready);
+        assert_eq!(app.target.split(' ').count(), TIMED_ROUND_WORDS);
+
+        // typing is ignored until the countdown is over
+        let start = Instant::now();
+        let first = app.target.chars().next().unwrap();
+        app.handle_key_event_at(KeyCode::Char(first).into(), start)
+            .unwrap();
+        app.handle_key_event_at(KeyCode::Char(' ').into(), start)
+            .unwrap();
+        app.handle_key_event_at(KeyCode::Char(first).into(), start)
+            .unwrap();
+        assert!(app.spans.is_empty());
+        app.tick(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(
+            app.session.countdown_left(start + Duration::from_secs(2)),
+            Some(1)
+        );
+
+        app.tick(start + Duration::from_secs(3)).unwrap();
+        assert_eq!(app.session, SessionState::Running { started: None });
+
+        // the clock starts with the first keypress
+        let typing = start + Duration::from_secs(5);
+        app.handle_key_event_at(KeyCode::Char(first).into(), typing)
+            .unwrap();
+        app.tick(typing + Duration::from_secs(9)).unwrap();
+        assert_eq!(app.screen, Screen::Typing);
+
+        app.tick(typing + Duration::from_secs(10)).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        assert_eq!(app.session, SessionState::Finished);
+        assert_eq!(app.stats.rounds[0].target, first.to_string());
+        assert_eq!(app.history.entries().len(), 1);
+    }
+
+    #[test]
+    fn misses_stay_marked() {
+        let mut app = App::default();
+        app.target = "abcd".into();
+        app.remainder = TextSpan::default_with_text(app.target.clone());
+
+        for c in "axbxxc".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+
+        let spans: Vec<(SpanType, &str)> = app
+            .spans
+            .iter()
+            .map(|s| (s.span_type, s.span.content.as_ref()))
+            .collect();
+        assert_eq!(spans, vec![(SpanType::HIT, "a"), (SpanType::MISS, "bc")]);
+        assert!(!app.miss_at_cursor);
+        assert_eq!(app.remainder.span.content, "d");
+    }
+
+    #[test]
+    fn records_word_and_round_timings() {
+        let mut app = App {
+            mode: Mode::Words(2),
+            ..Default::default()
+        };
+        app.next_round().unwrap();
+        app.target = "ab cd".into();
+        app.remainder = TextSpan::default_with_text(app.target.clone());
+
+        let start = Instant::now();
+        for (i, c) in "ab cd".chars().enumerate() {
+            let at = start + Duration::from_millis(100 * i as u64);
+            app.handle_key_event_at(KeyCode::Char(c).into(), at)
+                .unwrap();
+        }
+
+        let round = &app.stats.rounds[0];
+        assert_eq!(round.target, "ab cd");
+        assert_eq!(round.duration, Duration::from_millis(400));
+        assert_eq!(round.words.len(), 2);
+        assert_eq!(round.words[0].word, "ab");
+        assert_eq!(round.words[0].duration, Duration::from_millis(100));
+        assert_eq!(round.words[1].word, "cd");
+        assert_eq!(round.words[1].chars, 3);
+        assert_eq!(round.words[1].duration, Duration::from_millis(300));
+        // the first key of the round has nothing to measure from
+        let latencies = round.latencies.slowest(5, 1);
+        assert_eq!(latencies.len(), 4);
+        assert!(latencies.iter().all(|key| key.mean_ms == 100.0));
+        // pairs across words are left out
+        let bigrams: Vec<String> = round.bigrams.keys(1).into_iter().map(|b| b.key).collect();
+        assert_eq!(bigrams, vec!["ab", "cd"]);
+
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        app.handle_key_event(KeyCode::Tab.into()).unwrap();
+        assert_eq!(app.screen, Screen::Typing);
+        assert!(app.stats.rounds.is_empty());
+    }
+
+    #[test]
+    fn seeded_rounds_repeat() {
+        let texts = |seed| {
+            let mut app = App {
+                mode: Mode::Words(5),
+                ..Default::default()
+            }
+            .with_seed(seed);
+            (0..3)
+                .map(|_| {
+                    app.next_round().unwrap();
+                    app.target.clone()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts(42), texts(42));
+        assert_ne!(texts(42), texts(43));
+    }
+
+    #[test]
+    fn shortcuts_are_not_typed() {
+        let mut app = App {
+            target: "ab".into(),
+            remainder: TextSpan::default_with_text("ab".into()),
+            ..Default::default()
+        };
+        assert_eq!(app.input_mode(), InputMode::Test);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL))
+            .unwrap();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT))
+            .unwrap();
+        assert_eq!((app.wins, app.fails), (0, 0));
+        assert_eq!(app.remainder.span.content, "ab");
+
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.input_mode(), InputMode::Menu);
+    }
+
+    #[test]
+    fn strict_miss_ends_the_round() {
+        let mut app = App::default();
+        app.config.strict = Strict::Round;
+        app.target = "ab".into();
+        app.remainder = TextSpan::default_with_text("ab".into());
+        app.handle_key_event(KeyCode::Char('a').into()).unwrap();
+        app.handle_key_event(KeyCode::Char('x').into()).unwrap();
+        assert_eq!(app.fails, 1);
+        assert_eq!(app.stats.rounds.len(), 1);
+        assert_eq!(app.stats.rounds[0].target, "a");
+        assert!(app.stats.rounds[0].missed);
+        assert_eq!(app.remainder.span.content.len(), 2);
+
+        app.config.strict = Strict::Session;
+        app.handle_key_event(KeyCode::Char('#').into()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+    }
+
+    #[test]
+    fn pace_caret_moves_with_time() {
+        let mut app = App::default();
+        assert_eq!(app.pace_position(Instant::now()), None);
+
+        app.config.session.pace_wpm = 60;
+        let start = Instant::now();
+        app.round_start = Some(start);
+        // 60 WPM are 5 characters per second
+        assert_eq!(app.pace_position(start + Duration::from_secs(2)), Some(10));
+    }
+
+    #[test]
+    fn esc_goes_back_through_screens() {
+        let mut app = App::default();
+        app.finish_session(Instant::now()).unwrap();
+        app.handle_key_event(KeyCode::Char('s').into()).unwrap();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(app.breadcrumb(), "results › stats › settings");
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+        app.config.header.show_clock = false;
+        app.render_header(area, &mut buf);
+        let header: String = (0..80).map(|x| buf.get(x, 0).symbol()).collect();
+        assert!(header.ends_with("results › stats › settings "));
+        assert!(app.header_tabs(area).is_empty());
+
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(app.screen, Screen::Stats);
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        assert!(!app.exit);
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert!(app.exit);
+    }
+
+    #[test]
+    fn double_esc_quits_a_test() {
+        let mut app = App::default();
+        app.config.quit_protection = QuitProtection::DoubleEsc;
+        app.next_round().unwrap();
+        let start = Instant::now();
+        let press = |app: &mut App, code: KeyCode, millis| {
+            app.handle_key_event_at(code.into(), start + Duration::from_millis(millis))
+                .unwrap();
+        };
+        press(&mut app, KeyCode::Char('#'), 0);
+        press(&mut app, KeyCode::Esc, 100);
+        press(&mut app, KeyCode::Esc, 1500);
+        assert!(!app.exit);
+        press(&mut app, KeyCode::Char('#'), 1600);
+        press(&mut app, KeyCode::Esc, 1700);
+        assert!(app.confirm.is_none());
+        assert!(!app.exit);
+        press(&mut app, KeyCode::Esc, 2000);
+        assert!(app.exit);
+    }
+
+    #[test]
+    fn tell_the_numpad_from_the_number_row() {
+        let mut app = App::default().with_text("12 34");
+        app.next_round().unwrap();
+        let keypad = |c| {
+            let mut key = KeyEvent::from(KeyCode::Char(c));
+            key.state = KeyEventState::KEYPAD;
+            key
+        };
+        app.handle_key_event(keypad('1')).unwrap();
+        app.handle_key_event(keypad('3')).unwrap();
+        app.handle_key_event(keypad('2')).unwrap();
+        for c in " 34".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        let classes: Vec<_> = app.stats.classes().iter().collect();
+        assert_eq!(
+            classes,
+            [
+                (
+                    CharClass::Digit,
+                    Tally {
+                        chars: 2,
+                        misses: 0
+                    }
+                ),
+                (
+                    CharClass::Numpad,
+                    Tally {
+                        chars: 2,
+                        misses: 1
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn space_skips_the_rest_of_the_word() {
+        let mut app = App {
+            mode: Mode::Words(2),
+            target: "abc de".into(),
+            remainder: TextSpan::default_with_text("abc de".into()),
+            ..Default::default()
+        };
+        app.config.session.skip_on_space = true;
+        // at the start of a word space is just a miss
+        for c in " a d".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        assert_eq!(app.remainder.span.content, "e");
+        assert_eq!(app.round_misses, 3);
+        assert_eq!(app.stats.rounds.len(), 0);
+
+        app.handle_key_event(KeyCode::Char(' ').into()).unwrap();
+        assert_eq!(app.stats.rounds.len(), 1);
+        assert_eq!(app.stats.rounds[0].misses, 4);
+        assert_eq!(app.stats.rounds[0].uncorrected, 3);
+        assert_eq!(app.stats.rounds[0].words.len(), 2);
+
+        // a and d typed right, b, c and e skipped
+        let tally = |app: &App| {
+            app.stats.rounds[0]
+                .classes
+                .iter()
+                .fold((0, 0), |(c, m), (_, t)| (c + t.chars, m + t.misses))
+        };
+        assert_eq!(tally(&app), (2, 4));
+        app.stats.rounds[0].duration = Duration::from_secs(60);
+        let entry = HistoryEntry::new(&app.stats, app.mode, Utc::now());
+        assert_eq!(entry.uncorrected, 3);
+        // 3 right, 1 wrong and 3 skipped keystrokes of 6 characters
+        assert_eq!(entry.raw_wpm, 7.0 / 5.0);
+        assert_eq!(entry.output_wpm(), 3.0 / 5.0);
+        let mut recomputed = entry.clone();
+        recomputed.recompute();
+        assert_eq!(recomputed.raw_wpm, entry.raw_wpm);
+    }
+
+    #[test]
+    fn session_ends_after_its_rounds() {
+        let mut app = App {
+            mode: Mode::Chars(1),
+            ..Default::default()
+        };
+        app.config.session.rounds = 2;
+        app.next_round().unwrap();
+        assert_eq!(app.header_timer(Instant::now()), "round 1/2  0:00");
+        for _ in 0..2 {
+            let c = app.remainder.span.content.chars().next().unwrap();
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        assert_eq!(app.screen, Screen::Results);
+        assert_eq!(app.stats.rounds.len(), 2);
+    }
+
+    #[test]
+    fn pasted_sessions_are_not_recorded() {
+        let mut app = App {
+            target: "abc".into(),
+            remainder: TextSpan::default_with_text("abc".into()),
+            ..Default::default()
+        };
+        app.handle_paste("abc");
+        assert_eq!(app.remainder.span.content, "abc");
+        assert!(app.status.is_some());
+
+        app.handle_key_event(KeyCode::Char('a').into()).unwrap();
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        assert!(app.history.entries().is_empty());
+    }
+}
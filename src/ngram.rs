@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::words::WordList;
+
+/// Marks the start and end of a word in the counts
+const BOUNDARY: char = '\0';
+
+/// How often each letter follows a letter and a pair of letters in the words
+/// of a list, to make up words that read like the language of the list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NgramModel {
+    /// label of the word list the counts are from, see [`WordList::label`]
+    pub list: String,
+    bigrams: BTreeMap<char, BTreeMap<char, u32>>,
+    trigrams: BTreeMap<(char, char), BTreeMap<char, u32>>,
+}
+
+impl NgramModel {
+    /// counts the letters of the words of `words` and of the list mixed in
+    pub fn build(words: &WordList) -> Self {
+        let mut model = Self {
+            list: words.label(),
+            ..Default::default()
+        };
+        let mixed = words.mix.iter().flat_map(|mix| &mix.list.words);
+        for word in words.words.iter().chain(mixed) {
+            let mut state = (BOUNDARY, BOUNDARY);
+            for c in word.to_lowercase().chars().chain([BOUNDARY]) {
+                *model
+                    .bigrams
+                    .entry(state.1)
+                    .or_default()
+                    .entry(c)
+                    .or_default() += 1;
+                *model
+                    .trigrams
+                    .entry(state)
+                    .or_default()
+                    .entry(c)
+                    .or_default() += 1;
+                state = (state.1, c);
+            }
+        }
+        model
+    }
+
+    /// every letter of the words, in order
+    pub fn letters(&self) -> Vec<char> {
+        self.bigrams
+            .keys()
+            .copied()
+            .filter(|c| *c != BOUNDARY)
+            .collect()
+    }
+
+    /// A made up word of `len.0` to `len.1` letters, all of them in
+    /// `letters`. The pair of letters before picks the next one, the letter
+    /// before if no allowed letter follows the pair and any allowed letter
+    /// if none follows that either.
+    pub fn word(&self, rng: &mut dyn RngCore, letters: &[char], len: (usize, usize)) -> String {
+        let mut word = String::new();
+        let mut state = (BOUNDARY, BOUNDARY);
+        for i in 0..len.1 {
+            let allowed = |c: &char| {
+                if *c == BOUNDARY {
+                    i >= len.0
+                } else {
+                    letters.contains(c)
+                }
+            };
+            let next = [self.trigrams.get(&state), self.bigrams.get(&state.1)]
+                .into_iter()
+                .flatten()
+                .map(|counts| {
+                    counts
+                        .iter()
+                        .filter(|(c, _)| allowed(c))
+                        .collect::<Vec<_>>()
+                })
+                .find(|candidates| !candidates.is_empty())
+                .and_then(|candidates| {
+                    candidates
+                        .choose_weighted(rng, |(_, count)| **count)
+                        .ok()
+                        .map(|(c, _)| **c)
+                })
+                .or_else(|| letters.choose(rng).copied());
+            match next {
+                Some(c) if c != BOUNDARY => {
+                    word.push(c);
+                    state = (state.1, c);
+                }
+                _ => break,
+            }
+        }
+        word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn made_up_words_use_the_letters_and_follow_the_list() {
+        let model = NgramModel::build(&WordList::from_text("test", "tea eat ate"));
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // only pairs of the list, every word starts like one of them
+        let words: Vec<String> = (0..50)
+            .map(|_| model.word(&mut rng, &['a', 'e', 't'], (3, 3)))
+            .collect();
+        assert!(words.iter().all(|w| w.len() == 3));
+        assert!(words
+            .iter()
+            .all(|w| ["te", "ea", "at"].iter().any(|start| w.starts_with(start))));
+
+        // letters the list doesn't follow up still make words
+        let word = model.word(&mut rng, &['t', 'x'], (4, 6));
+        assert!(word.len() >= 4 && word.chars().all(|c| c == 't' || c == 'x'));
+        assert_eq!(model.word(&mut rng, &[], (3, 6)), "");
+    }
+}
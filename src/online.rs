@@ -0,0 +1,391 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Name of the file of the fetched texts inside the cache directory
+const CACHE_FILE: &str = "online.json";
+/// Texts kept in the cache, the oldest are dropped first
+const MAX_CACHED: usize = 50;
+/// Shortest article worth a practice text
+const MIN_LEN: usize = 80;
+
+/// Where the practice texts of `--online` come from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnlineConfig {
+    /// url of an RSS or Atom feed to practice its articles, a random
+    /// wikipedia article if empty
+    pub feed: String,
+    /// language of the wikipedia, e.g. `en` or `de`
+    pub wikipedia: String,
+    /// minutes the fetched texts are practiced before fetching new ones
+    pub refresh_minutes: u32,
+}
+
+impl Default for OnlineConfig {
+    fn default() -> Self {
+        Self {
+            feed: String::new(),
+            wikipedia: "en".into(),
+            refresh_minutes: 60,
+        }
+    }
+}
+
+/// The part of a wikipedia page summary with the text
+#[derive(Debug, Deserialize)]
+struct Summary {
+    extract: String,
+}
+
+impl OnlineConfig {
+    /// the url the texts are fetched from
+    pub fn source(&self) -> String {
+        if self.feed.is_empty() {
+            format!(
+                "https://{}.wikipedia.org/api/rest_v1/page/random/summary",
+                self.wikipedia
+            )
+        } else {
+            self.feed.clone()
+        }
+    }
+
+    /// the practice texts in `body`, fetched from [`Self::source`]
+    fn texts(&self, body: &str) -> Result<Vec<String>> {
+        if self.feed.is_empty() {
+            let summary: Summary =
+                serde_json::from_str(body).wrap_err("parsing the wikipedia article failed")?;
+            return Ok(vec![clean(&summary.extract)]);
+        }
+        let texts = articles(body);
+        if texts.is_empty() {
+            return Err(eyre!("{} has no articles", self.feed));
+        }
+        Ok(texts)
+    }
+}
+
+/// A text fetched before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedText {
+    source: String,
+    fetched: DateTime<Utc>,
+    text: String,
+}
+
+/// The texts fetched lately, so repeated runs don't fetch again
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    texts: Vec<CachedText>,
+}
+
+impl Cache {
+    /// Default location of the cache, `None` if the platform has no cache
+    /// directory
+    pub fn path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("metyping").join(CACHE_FILE))
+    }
+
+    /// Loads the cache stored at `path`, empty if there is no file
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        serde_json::from_str(&raw).wrap_err_with(|| format!("parsing {} failed", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    fn texts(&self, source: &str) -> Vec<&str> {
+        self.texts
+            .iter()
+            .filter(|t| t.source == source)
+            .map(|t| t.text.as_str())
+            .collect()
+    }
+
+    /// whether a text was fetched from `source` less than `refresh` before
+    /// `now`
+    fn fresh(&self, source: &str, refresh: Duration, now: DateTime<Utc>) -> bool {
+        self.texts
+            .iter()
+            .any(|t| t.source == source && now - t.fetched < refresh)
+    }
+
+    /// keeps the new ones of `texts`, dropping the oldest texts beyond
+    /// [`MAX_CACHED`]
+    fn add(&mut self, source: &str, texts: Vec<String>, now: DateTime<Utc>) {
+        for text in texts {
+            self.texts.retain(|t| t.text != text);
+            self.texts.push(CachedText {
+                source: source.into(),
+                fetched: now,
+                text,
+            });
+        }
+        let excess = self.texts.len().saturating_sub(MAX_CACHED);
+        self.texts.drain(..excess);
+    }
+}
+
+/// A practice text from the source of `config`. The texts fetched are
+/// practiced again until they are [`OnlineConfig::refresh_minutes`] old or
+/// the source can't be reached.
+pub fn read(config: &OnlineConfig, rng: &mut dyn RngCore) -> Result<String> {
+    let path = Cache::path();
+    let mut cache = match &path {
+        Some(path) => Cache::load(path)?,
+        None => Cache::default(),
+    };
+    let source = config.source();
+    let now = Utc::now();
+    let refresh = Duration::minutes(config.refresh_minutes.into());
+    if !cache.fresh(&source, refresh, now) {
+        match fetch(&source).and_then(|body| config.texts(&body)) {
+            Ok(texts) => {
+                cache.add(&source, texts, now);
+                if let Some(path) = &path {
+                    cache.save(path)?;
+                }
+            }
+            // offline the texts of earlier runs have to do
+            Err(_) if !cache.texts(&source).is_empty() => {}
+            Err(error) => return Err(error),
+        }
+    }
+    cache
+        .texts(&source)
+        .choose(rng)
+        .map(|text| text.to_string())
+        .ok_or_else(|| eyre!("{source} has no text to practice"))
+}
+
+/// The body of the response to a GET of `url`
+#[cfg(feature = "net")]
+fn fetch(url: &str) -> Result<String> {
+    ureq::get(url)
+        .set(
+            "User-Agent",
+            concat!("metyping/", env!("CARGO_PKG_VERSION")),
+        )
+        .call()
+        .wrap_err_with(|| format!("fetching {url} failed"))?
+        .into_string()
+        .wrap_err_with(|| format!("reading the response of {url} failed"))
+}
+
+/// The body of the response to a GET of `url`
+#[cfg(not(feature = "net"))]
+fn fetch(_url: &str) -> Result<String> {
+    Err(eyre!(
+        "built without network support, rebuild with `--features net`"
+    ))
+}
+
+/// The title and the description of each item of an RSS or each entry of
+/// an Atom feed, long enough to practice
+fn articles(xml: &str) -> Vec<String> {
+    let mut texts = vec![];
+    for tag in ["item", "entry"] {
+        let mut rest = xml;
+        while let Some((inner, after)) = element(rest, tag) {
+            let parts: Vec<String> = ["title", "description", "summary"]
+                .into_iter()
+                .filter_map(|name| element(inner, name))
+                .map(|(text, _)| clean(text))
+                .filter(|text| !text.is_empty())
+                .collect();
+            let text = parts
+                .into_iter()
+                .reduce(|title, text| {
+                    if title.ends_with(['.', '!', '?', ':']) {
+                        format!("{title} {text}")
+                    } else {
+                        format!("{title}. {text}")
+                    }
+                })
+                .unwrap_or_default();
+            if text.chars().count() >= MIN_LEN {
+                texts.push(text);
+            }
+            rest = after;
+        }
+    }
+    texts
+}
+
+/// the content of the first element `name` in `xml` and what follows it
+fn element<'a>(xml: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut from = 0;
+    loop {
+        let start = from + xml[from..].find(&open)?;
+        let after_name = start + open.len();
+        // `<title` but not `<titles`
+        if xml[after_name..].starts_with(['>', ' ', '\t', '\n', '\r', '/']) {
+            let content = after_name + xml[after_name..].find('>')? + 1;
+            if xml[..content].ends_with("/>") {
+                return Some(("", &xml[content..]));
+            }
+            let end = content + xml[content..].find(&close)?;
+            return Some((&xml[content..end], &xml[end + close.len()..]));
+        }
+        from = after_name;
+    }
+}
+
+/// the plain text of the content of an element: without CDATA markers,
+/// markup and entities, typographic punctuation typed as ascii and the white
+/// space collapsed
+fn clean(content: &str) -> String {
+    let content = content.trim();
+    let content = content
+        .strip_prefix("<![CDATA[")
+        .and_then(|c| c.strip_suffix("]]>"))
+        .unwrap_or(content);
+    // markup in descriptions comes escaped
+    let text = decode(&strip_tags(&decode(content)));
+    text.chars()
+        .map(|c| match c {
+            '“' | '”' | '„' | '«' | '»' => '"',
+            '‘' | '’' | '‚' => '\'',
+            '–' | '—' => '-',
+            '\u{a0}' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .replace('…', "...")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            // tags separate words like `<br>`
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            }
+            c if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    plain
+}
+
+/// replaces the entities in `text` by their characters
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((entity(&rest[1..end])?, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// the character of the entity `&name;`
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn practice_the_articles_of_a_feed() {
+        let config = OnlineConfig {
+            feed: "https://example.com/feed.xml".into(),
+            ..Default::default()
+        };
+        let xml = r#"<?xml version="1.0"?>
+            <rss><channel><title>News</title>
+            <item>
+              <title>Rust 2.0 released</title>
+              <description><![CDATA[<p>The “new” edition brings <b>faster</b>
+                builds &amp; better errors… to everyone who upgrades today.</p>]]></description>
+            </item>
+            <item><title>Too short</title><description>Nothing</description></item>
+            <item>
+              <title>Typing is fun!</title>
+              <description>&lt;p&gt;Practice every day and your speed goes up, at least that&#39;s what they say.&lt;/p&gt;</description>
+            </item>
+            </channel></rss>"#;
+        assert_eq!(
+            config.texts(xml).unwrap(),
+            [
+                "Rust 2.0 released. The \"new\" edition brings faster builds & better errors... \
+                 to everyone who upgrades today.",
+                "Typing is fun! Practice every day and your speed goes up, at least that's what \
+                 they say."
+            ]
+        );
+        assert!(config.texts("<html></html>").is_err());
+
+        let start = Utc::now();
+        let mut cache = Cache::default();
+        cache.add("feed", vec!["one".into(), "two".into()], start);
+        cache.add("feed", vec!["two".into(), "three".into()], start);
+        assert_eq!(cache.texts("feed"), ["one", "two", "three"]);
+        let hour = Duration::hours(1);
+        assert!(cache.fresh("feed", hour, start + Duration::minutes(59)));
+        assert!(!cache.fresh("feed", hour, start + hour));
+        assert!(!cache.fresh("other", hour, start));
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+
+use color_eyre::{
+    eyre::{eyre, WrapErr},
+    Result,
+};
+
+/// German phrases and their English translation
+const GERMAN_ENGLISH: &str = include_str!("../assets/phrases/german-english.tsv");
+
+/// A phrase and its translation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phrase {
+    pub source: String,
+    pub translation: String,
+}
+
+/// Phrases to translate in the translate mode. The text of a list has a
+/// phrase, a tab and its translation per line, empty lines and lines
+/// starting with `#` are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhraseList {
+    pub name: String,
+    pub phrases: Vec<Phrase>,
+}
+
+impl Default for PhraseList {
+    fn default() -> Self {
+        Self::parse("german-english", GERMAN_ENGLISH).expect("the built-in phrases parse")
+    }
+}
+
+impl PhraseList {
+    /// Names of the built-in lists
+    pub const NAMES: [&'static str; 1] = ["german-english"];
+
+    pub fn parse(name: &str, text: &str) -> Result<Self> {
+        let phrases = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| match line.split_once('\t') {
+                Some((source, translation)) if !translation.trim().is_empty() => Ok(Phrase {
+                    source: source.trim().into(),
+                    translation: translation.trim().into(),
+                }),
+                _ => Err(eyre!(
+                    "line {} has no tab between the phrase and its translation",
+                    i + 1
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if phrases.is_empty() {
+            return Err(eyre!("there are no phrases in {name}"));
+        }
+        Ok(Self {
+            name: name.into(),
+            phrases,
+        })
+    }
+
+    /// The built-in list called `name`, or the list in the file at `name`
+    pub fn load(name: &str) -> Result<Self> {
+        if name == Self::NAMES[0] {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(name).wrap_err_with(|| {
+            format!(
+                "'{name}' is neither a file nor one of the phrase lists: {}",
+                Self::NAMES.join(", ")
+            )
+        })?;
+        Self::parse(name, &text).wrap_err_with(|| format!("parsing {name} failed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_phrases_and_their_translation() {
+        let list = PhraseList::parse("test", "# de\tde\n\nHallo Welt\t Hello world \n").unwrap();
+        assert_eq!(
+            list.phrases,
+            [Phrase {
+                source: "Hallo Welt".into(),
+                translation: "Hello world".into(),
+            }]
+        );
+        assert_eq!(
+            PhraseList::parse("test", "Hallo\tHello\nTschüss")
+                .unwrap_err()
+                .to_string(),
+            "line 2 has no tab between the phrase and its translation"
+        );
+        assert!(PhraseList::default().phrases.len() >= 50);
+    }
+}
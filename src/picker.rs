@@ -0,0 +1,276 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    form::{FieldEvent, TextInput},
+    theme::Theme,
+    App, Mode, Screen,
+};
+
+/// Modes with common options offered by the picker
+const PRESETS: [Mode; 20] = [
+    Mode::Random,
+    Mode::Chars(3),
+    Mode::Chars(5),
+    Mode::Chars(8),
+    Mode::Words(10),
+    Mode::Words(25),
+    Mode::Words(50),
+    Mode::Quote,
+    Mode::Translate,
+    Mode::Race(10),
+    Mode::Race(15),
+    Mode::Race(25),
+    Mode::Timed(15),
+    Mode::Timed(30),
+    Mode::Timed(60),
+    Mode::Numbers(10),
+    Mode::Numbers(25),
+    Mode::Symbols(10),
+    Mode::Symbols(25),
+    Mode::Lesson(10),
+];
+
+/// What picking an item does
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerAction {
+    Mode(Mode),
+    Generator(String),
+    Theme(&'static str),
+    Drill,
+    Screen(Screen),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickerItem {
+    pub label: String,
+    pub action: PickerAction,
+}
+
+/// A popup to jump to any mode, generator, theme or screen by typing a part
+/// of its name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Picker {
+    query: TextInput,
+    items: Vec<PickerItem>,
+    /// index into the matching items
+    selected: usize,
+}
+
+/// Scores how well `query` matches `candidate`, `None` if the characters of
+/// the query don't all appear in order. Runs of consecutive characters and
+/// matches at the start of words score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let i = next + candidate[next..].iter().position(|&c| c == q)?;
+        score += 1;
+        if last.is_some_and(|last| last + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        last = Some(i);
+        next = i + 1;
+    }
+    // shorter candidates are closer matches
+    Some(score * 100 - candidate.len() as i64)
+}
+
+impl Picker {
+    pub fn new(items: Vec<PickerItem>) -> Self {
+        Self {
+            items,
+            ..Default::default()
+        }
+    }
+
+    /// the items matching the query, best match first
+    pub fn matches(&self) -> Vec<&PickerItem> {
+        let mut scored: Vec<(i64, &PickerItem)> = self
+            .items
+            .iter()
+            .filter_map(|item| Some((fuzzy_score(self.query.value(), &item.label)?, item)))
+            .collect();
+        // stable, so equal scores keep the order of the items
+        scored.sort_by_key(|(score, _)| -score);
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+impl App<'_> {
+    pub(crate) fn open_picker(&mut self) {
+        let mut items: Vec<PickerItem> = PRESETS
+            .iter()
+            .map(|&mode| PickerItem {
+                label: format!("mode: {mode}"),
+                action: PickerAction::Mode(mode),
+            })
+            .collect();
+        items.extend(self.generators.names().into_iter().map(|name| PickerItem {
+            label: format!("generator: {name}"),
+            action: PickerAction::Generator(name.into()),
+        }));
+        items.extend(Theme::NAMES.into_iter().map(|name| PickerItem {
+            label: format!("theme: {name}"),
+            action: PickerAction::Theme(name),
+        }));
+        items.push(PickerItem {
+            label: "drill from my data".into(),
+            action: PickerAction::Drill,
+        });
+        items.extend(
+            [
+                ("stats", Screen::Stats),
+                ("badges", Screen::Badges),
+                ("leaderboard", Screen::Leaderboard),
+                ("trends", Screen::Trends),
+                ("history", Screen::History),
+                ("plan", Screen::Plan),
+                ("courses", Screen::Courses),
+                ("profiles", Screen::Profiles),
+                ("settings", Screen::Settings),
+            ]
+            .into_iter()
+            .map(|(name, screen)| PickerItem {
+                label: format!("screen: {name}"),
+                action: PickerAction::Screen(screen),
+            }),
+        );
+        self.picker = Some(Picker::new(items));
+    }
+
+    fn pick(&mut self, action: PickerAction) -> Result<()> {
+        match action {
+            PickerAction::Mode(mode) => {
+                self.mode = mode;
+                self.new_session()?;
+            }
+            PickerAction::Generator(name) => {
+                self.config.generator = name;
+                self.new_session()?;
+            }
+            PickerAction::Theme(name) => {
+                if let Some(theme) = Theme::by_name(name) {
+                    self.use_theme(theme);
+                    self.config.theme = name.into();
+                }
+            }
+            PickerAction::Drill => self.status = Some(self.start_drill()?),
+            PickerAction::Screen(Screen::Settings) => self.open_settings(),
+            PickerAction::Screen(Screen::Profiles) => self.open_profiles(),
+            PickerAction::Screen(screen) => self.push_screen(screen),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn handle_picker_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some(picker) = &mut self.picker else {
+            return Ok(());
+        };
+        match key_event.code {
+            // the command line can't be opened with `:` while typing, so
+            // it's reachable from the picker
+            KeyCode::Char(':') if picker.query.value().is_empty() => {
+                self.picker = None;
+                self.open_command_line();
+            }
+            KeyCode::Up => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Down => {
+                let last = picker.matches().len().saturating_sub(1);
+                picker.selected = (picker.selected + 1).min(last);
+            }
+            _ => match picker.query.handle_key(key_event) {
+                FieldEvent::Submit => {
+                    let action = picker
+                        .matches()
+                        .get(picker.selected)
+                        .map(|item| item.action.clone());
+                    self.picker = None;
+                    if let Some(action) = action {
+                        self.pick(action)?;
+                    }
+                }
+                FieldEvent::Cancel => self.picker = None,
+                FieldEvent::Edited => picker.selected = 0,
+                FieldEvent::Ignored => {}
+            },
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_picker(&self, area: Rect, buf: &mut Buffer) {
+        let Some(picker) = &self.picker else {
+            return;
+        };
+        let width = 50.min(area.width.saturating_sub(4));
+        let height = 14.min(area.height.saturating_sub(2));
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        let mut query = vec![Span::styled("> ", self.theme.title)];
+        query.extend(picker.query.spans(self.theme.value));
+        let mut lines = vec![Line::from(query)];
+        let rows = height.saturating_sub(3) as usize;
+        // keep the selected item in view
+        let skip = (picker.selected + 1).saturating_sub(rows);
+        for (i, item) in picker.matches().iter().enumerate().skip(skip).take(rows) {
+            let style = if i == picker.selected {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+            lines.push(Line::from(Span::styled(item.label.as_str(), style)));
+        }
+
+        Clear.render(popup, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(Span::styled(" JUMP TO ", self.theme.title))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(self.theme.border),
+            )
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_rank_runs_first() {
+        assert_eq!(fuzzy_score("xyz", "mode: words 10"), None);
+        assert!(fuzzy_score("", "anything").is_some());
+        assert!(fuzzy_score("grv", "theme: gruvbox") > fuzzy_score("grv", "generator: markov"));
+
+        let mut app = App::default();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL))
+            .unwrap();
+        for c in "tim60".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(app.picker.is_none());
+        assert_eq!(app.mode, Mode::Timed(60));
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{history::HistoryEntry, zone::Zone, App, Mode, Screen};
+
+/// A session of the training plan, configured as `[[plan]]` tables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanItem {
+    /// name the results are recorded under in the history
+    pub name: String,
+    pub mode: Mode,
+    /// days of the week it's scheduled on like `["mon", "thu"]`, every day
+    /// if empty
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+}
+
+impl PlanItem {
+    pub fn scheduled_on(&self, date: NaiveDate) -> bool {
+        self.days.is_empty() || self.days.contains(&date.weekday())
+    }
+
+    /// the last session recorded for this item on `date`
+    pub fn result<'a>(
+        &self,
+        entries: &'a [HistoryEntry],
+        date: NaiveDate,
+        zone: Zone,
+    ) -> Option<&'a HistoryEntry> {
+        entries.iter().rev().find(|e| {
+            e.plan.as_deref() == Some(self.name.as_str()) && zone.date(e.timestamp) == date
+        })
+    }
+}
+
+/// the items of `plan` scheduled on `date`
+pub fn scheduled(plan: &[PlanItem], date: NaiveDate) -> Vec<&PlanItem> {
+    plan.iter().filter(|item| item.scheduled_on(date)).collect()
+}
+
+/// how many of the items scheduled on `date` are done, and how many there are
+pub fn progress(
+    plan: &[PlanItem],
+    entries: &[HistoryEntry],
+    date: NaiveDate,
+    zone: Zone,
+) -> (usize, usize) {
+    let items = scheduled(plan, date);
+    let done = items
+        .iter()
+        .filter(|item| item.result(entries, date, zone).is_some())
+        .count();
+    (done, items.len())
+}
+
+/// the days from monday to sunday of the week of `date`
+pub fn week(date: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday().into());
+    (0..7).map(move |day| monday + Duration::days(day))
+}
+
+impl App<'_> {
+    /// the day it is in the configured time zone
+    pub(crate) fn today(&self) -> NaiveDate {
+        self.config.timezone.date(Utc::now())
+    }
+
+    /// starts the first item of today's plan that isn't done yet, shows the
+    /// plan once all of them are. Returns a message for the status line.
+    pub(crate) fn run_plan(&mut self) -> Result<String> {
+        self.running_plan = true;
+        let today = self.today();
+        let zone = self.config.timezone;
+        let (done, total) = progress(&self.config.plan, self.history.entries(), today, zone);
+        let next = scheduled(&self.config.plan, today)
+            .into_iter()
+            .find(|item| item.result(self.history.entries(), today, zone).is_none())
+            .cloned();
+        let Some(item) = next else {
+            self.running_plan = false;
+            self.plan_item = None;
+            self.push_screen(Screen::Plan);
+            return Ok(if total == 0 {
+                "nothing scheduled for today, add [[plan]] items to the config".into()
+            } else {
+                "today's plan is done".into()
+            });
+        };
+        let message = format!("plan {}/{total}: {}", done + 1, item.name);
+        self.mode = item.mode;
+        self.plan_item = Some(item);
+        self.new_session()?;
+        Ok(message)
+    }
+
+    /// the name of the plan item the current session counts for, `None` if
+    /// the mode was changed away from the item's
+    pub(crate) fn plan_attribution(&self) -> Option<String> {
+        self.plan_item
+            .as_ref()
+            .filter(|item| self.running_plan && item.mode == self.mode)
+            .map(|item| item.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::TextSpan;
+
+    #[test]
+    fn run_the_items_scheduled_for_today() {
+        let mut app = App::default();
+        let today = app.today();
+        app.config.session.rounds = 1;
+        app.config.plan = vec![
+            PlanItem {
+                name: "warm-up".into(),
+                mode: Mode::Chars(1),
+                days: vec![],
+            },
+            PlanItem {
+                name: "tomorrow".into(),
+                mode: Mode::Words(5),
+                days: vec![today.succ_opt().unwrap().weekday()],
+            },
+            PlanItem {
+                name: "sprint".into(),
+                mode: Mode::Words(1),
+                days: vec![today.weekday()],
+            },
+        ];
+        assert_eq!(scheduled(&app.config.plan, today).len(), 2);
+
+        let message = app.run_plan().unwrap();
+        assert_eq!(message, "plan 1/2: warm-up");
+        assert_eq!(app.mode, Mode::Chars(1));
+        app.target = "a".into();
+        app.remainder = TextSpan::default_with_text("a".into());
+        app.handle_key_event(KeyCode::Char('a').into()).unwrap();
+        assert_eq!(app.screen, Screen::Results);
+        assert_eq!(app.history.entries()[0].plan.as_deref(), Some("warm-up"));
+
+        app.handle_key_event(KeyCode::Tab.into()).unwrap();
+        assert_eq!(app.status.as_deref(), Some("plan 2/2: sprint"));
+        assert_eq!(app.mode, Mode::Words(1));
+        assert_eq!(
+            progress(
+                &app.config.plan,
+                app.history.entries(),
+                today,
+                app.config.timezone
+            ),
+            (1, 2)
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::history::HistoryEntry;
+
+/// Longest break between two tests of the same practice session
+pub const GAP: Duration = Duration::minutes(30);
+
+/// Tests taken with short breaks in between
+#[derive(Debug, Clone, PartialEq)]
+pub struct Practice {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub tests: usize,
+    /// average speed of the tests
+    pub wpm: f64,
+    /// how much faster the later tests were than the first one
+    pub warm_up: f64,
+    /// how much slower the last test was than the fastest one
+    pub fatigue: f64,
+}
+
+/// Groups `entries` into practice sessions, a break longer than `gap` starts
+/// a new one. Entries have to be in order of time, like in the history.
+pub fn sessions(entries: &[HistoryEntry], gap: Duration) -> Vec<Practice> {
+    let mut groups: Vec<Vec<&HistoryEntry>> = vec![];
+    for entry in entries {
+        let start = entry.timestamp - Duration::milliseconds(entry.duration_ms as i64);
+        match groups.last_mut() {
+            Some(group)
+                if group
+                    .last()
+                    .is_some_and(|last| start - last.timestamp <= gap) =>
+            {
+                group.push(entry);
+            }
+            _ => groups.push(vec![entry]),
+        }
+    }
+    groups.into_iter().map(|group| practice(&group)).collect()
+}
+
+fn practice(tests: &[&HistoryEntry]) -> Practice {
+    let wpm: Vec<f64> = tests.iter().map(|e| e.wpm).collect();
+    let mean = |wpm: &[f64]| wpm.iter().sum::<f64>() / wpm.len().max(1) as f64;
+    let (first, last) = (wpm[0], wpm[wpm.len() - 1]);
+    let best = wpm.iter().copied().fold(f64::MIN, f64::max);
+    Practice {
+        start: tests[0].timestamp - Duration::milliseconds(tests[0].duration_ms as i64),
+        end: tests[tests.len() - 1].timestamp,
+        tests: tests.len(),
+        wpm: mean(&wpm),
+        warm_up: if wpm.len() > 1 {
+            mean(&wpm[1..]) - first
+        } else {
+            0.0
+        },
+        fatigue: best - last,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::{stats::SessionStats, Mode};
+
+    fn entry(minute: u32, wpm: f64) -> HistoryEntry {
+        let timestamp = Utc.with_ymd_and_hms(2024, 5, 3, 12, minute, 0).unwrap();
+        let mut entry = HistoryEntry::new(&SessionStats::default(), Mode::Words(10), timestamp);
+        entry.wpm = wpm;
+        entry.duration_ms = 60_000;
+        entry
+    }
+
+    #[test]
+    fn split_at_long_breaks() {
+        let entries = [
+            entry(0, 40.0),
+            entry(5, 50.0),
+            entry(10, 60.0),
+            entry(15, 52.0),
+            entry(59, 30.0),
+        ];
+        let practice = sessions(&entries, GAP);
+        assert_eq!(practice.len(), 2);
+        assert_eq!(practice[0].tests, 4);
+        assert_eq!(
+            practice[0].start,
+            entries[0].timestamp - Duration::minutes(1)
+        );
+        assert_eq!(practice[0].wpm, 50.5);
+        assert_eq!(practice[0].warm_up, 14.0);
+        assert_eq!(practice[0].fatigue, 8.0);
+        assert_eq!(practice[1].tests, 1);
+        assert_eq!(practice[1].warm_up, 0.0);
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    fs, mem,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+
+use crate::{config::Config, env, history::History, App, Screen};
+
+/// Profile used without `--profile`, its files are where they were before
+/// there were profiles
+pub const DEFAULT_PROFILE: &str = "default";
+/// Directory of the other profiles inside the config and data directories
+const PROFILES_DIR: &str = "profiles";
+/// Longest profile name
+pub const MAX_PROFILE_LEN: usize = 20;
+
+/// Where the files of `profile` are inside `base`, the config or the data
+/// directory
+pub fn dir(base: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        base.to_path_buf()
+    } else {
+        base.join(PROFILES_DIR).join(profile)
+    }
+}
+
+/// tells why `name` can't be the name of a profile, it's used as a directory
+pub fn validate(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("the profile needs a name".into());
+    }
+    if name.chars().count() > MAX_PROFILE_LEN {
+        return Err(format!(
+            "profile names are at most {MAX_PROFILE_LEN} characters"
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("profile names are made of letters, digits, - and _".into());
+    }
+    Ok(())
+}
+
+/// the profiles with files in any of `bases`, the default one first
+pub fn list_in(bases: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = bases
+        .iter()
+        .filter_map(|base| fs::read_dir(base.join(PROFILES_DIR)).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate(name).is_ok() && name != DEFAULT_PROFILE)
+        .collect();
+    names.sort();
+    names.dedup();
+    names.insert(0, DEFAULT_PROFILE.into());
+    names
+}
+
+/// the profiles in the config and the data directory
+pub fn list() -> Vec<String> {
+    let config = dirs::config_dir().map(|dir| dir.join("metyping"));
+    let bases: Vec<PathBuf> = [config, env::data_dir()].into_iter().flatten().collect();
+    list_in(&bases)
+}
+
+impl App<'_> {
+    pub(crate) fn open_profiles(&mut self) {
+        self.profiles = match &self.kiosk {
+            Some(kiosk) => kiosk.users(&self.config.profile),
+            None => list(),
+        };
+        self.profile_cursor = self
+            .profiles
+            .iter()
+            .position(|name| *name == self.config.profile)
+            .unwrap_or_default();
+        self.push_screen(Screen::Profiles);
+    }
+
+    /// continues with a fresh session of `name`, a user of the kiosk or a
+    /// profile
+    pub(crate) fn switch_user(&mut self, name: &str) -> Result<String> {
+        match self.kiosk {
+            Some(_) => self.switch_kiosk_user(name),
+            None => self.switch_profile(name),
+        }
+    }
+
+    /// continues with the config and the history of `name`, creating the
+    /// profile if there is none of that name. The settings and the test in
+    /// progress are kept for the current profile.
+    pub(crate) fn switch_profile(&mut self, name: &str) -> Result<String> {
+        if let Err(error) = validate(name) {
+            return Ok(error);
+        }
+        self.save_snapshot(Instant::now())?;
+        self.save_config()?;
+        self.save_lesson()?;
+        // what was given on the command line holds for every profile
+        let config = Config::load(name, self.config.overrides().clone())?;
+        let history = History::load(name)?;
+        let mut app = Self::new(config, history)?;
+        if let Some(kiosk) = self.kiosk.take() {
+            app = app.with_kiosk();
+            app.kiosk = Some(kiosk);
+        }
+        if let Some(path) = app.config_path.as_ref().filter(|path| !path.exists()) {
+            // so the new profile shows up in the list
+            app.config
+                .save(path)
+                .wrap_err_with(|| format!("creating the profile {name} failed"))?;
+        }
+        let previous = mem::replace(self, app);
+        self.area = previous.area;
+        self.rng = previous.rng;
+        self.frame_stats = previous.frame_stats;
+        self.running_plan = previous.running_plan;
+        // the text of `--clipboard` or `--online` and the generators added
+        // by an embedding app, with those made of the profile's own files
+        let generators = mem::replace(&mut self.generators, previous.generators);
+        self.generators
+            .adopt(generators, &["drill", "translate", "lesson"]);
+        self.new_session()?;
+        self.offer_resume()?;
+        Ok(format!("profile: {name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn profiles_live_in_their_own_directories() {
+        let base = temp_path("profiles");
+        assert_eq!(dir(&base, DEFAULT_PROFILE), base);
+        assert_eq!(dir(&base, "colemak"), base.join("profiles/colemak"));
+
+        assert!(validate("colemak_2").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("../up").is_err());
+
+        fs::create_dir_all(dir(&base, "qwerty")).unwrap();
+        fs::create_dir_all(dir(&base, "colemak")).unwrap();
+        assert_eq!(
+            list_in(std::slice::from_ref(&base)),
+            ["default", "colemak", "qwerty"]
+        );
+        fs::remove_dir_all(&base).unwrap();
+        assert_eq!(list_in(&[base]), ["default"]);
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::history::HistoryEntry;
+
+/// Number of recent sessions the ghost bot is modeled from
+const GHOST_SESSIONS: usize = 10;
+
+/// How a bot racer types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BotConfig {
+    pub name: String,
+    /// average speed
+    pub wpm: f64,
+    /// how much a single keystroke may deviate from the average interval,
+    /// 0.2 means up to 20% faster or slower
+    pub variance: f64,
+    /// chance of a mistake per keystroke
+    pub error_rate: f64,
+    /// time lost per mistake
+    pub error_pause_ms: u64,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self::new("bot", 40.0)
+    }
+}
+
+impl BotConfig {
+    pub fn new(name: &str, wpm: f64) -> Self {
+        Self {
+            name: name.into(),
+            wpm,
+            variance: 0.3,
+            error_rate: 0.03,
+            error_pause_ms: 500,
+        }
+    }
+
+    /// A bot typing like the user did in their last sessions, `None` without
+    /// any history
+    pub fn ghost(history: &[HistoryEntry]) -> Option<Self> {
+        let recent = &history[history.len().saturating_sub(GHOST_SESSIONS)..];
+        if recent.is_empty() {
+            return None;
+        }
+
+        let wpm = recent.iter().map(|e| e.wpm).sum::<f64>() / recent.len() as f64;
+        let chars: usize = recent.iter().map(|e| e.chars).sum();
+        let misses: usize = recent.iter().map(|e| e.misses).sum();
+        Some(Self {
+            error_rate: misses as f64 / (chars + misses).max(1) as f64,
+            ..Self::new("ghost", wpm)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaceConfig {
+    pub bots: Vec<BotConfig>,
+    /// add a bot modeled from the own history
+    pub ghost: bool,
+}
+
+impl Default for RaceConfig {
+    fn default() -> Self {
+        Self {
+            bots: vec![
+                BotConfig::new("sloth", 25.0),
+                BotConfig::new("fox", 45.0),
+                BotConfig::new("cheetah", 70.0),
+            ],
+            ghost: true,
+        }
+    }
+}
+
+/// A bot racing through a text of a fixed length
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bot {
+    pub name: String,
+    /// time since the start at which each character is typed
+    schedule: Vec<Duration>,
+}
+
+impl Bot {
+    /// Plans every keystroke of the bot for a text of `chars` characters
+    pub fn new<R: Rng>(config: &BotConfig, chars: usize, rng: &mut R) -> Self {
+        let interval = 60.0 / (config.wpm.max(1.0) * 5.0);
+        let variance = config.variance.clamp(0.0, 0.9);
+        let mut elapsed = 0.0;
+        let schedule = (0..chars)
+            .map(|_| {
+                elapsed += interval * (1.0 + variance * rng.gen_range(-1.0..=1.0));
+                if rng.gen_bool(config.error_rate.clamp(0.0, 1.0)) {
+                    elapsed += config.error_pause_ms as f64 / 1000.0;
+                }
+                Duration::from_secs_f64(elapsed)
+            })
+            .collect();
+        Self {
+            name: config.name.clone(),
+            schedule,
+        }
+    }
+
+    /// number of characters typed after `elapsed`
+    pub fn progress(&self, elapsed: Duration) -> usize {
+        self.schedule.partition_point(|t| *t <= elapsed)
+    }
+
+    pub fn finish_time(&self) -> Duration {
+        self.schedule.last().copied().unwrap_or_default()
+    }
+}
+
+/// The bots of the current race
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Race {
+    pub bots: Vec<Bot>,
+}
+
+impl Race {
+    pub fn new<R: Rng>(configs: &[BotConfig], chars: usize, rng: &mut R) -> Self {
+        Self {
+            bots: configs.iter().map(|c| Bot::new(c, chars, rng)).collect(),
+        }
+    }
+
+    /// Final placement of the user, 1 is the winner
+    pub fn place(&self, time: Duration) -> usize {
+        1 + self.bots.iter().filter(|b| b.finish_time() < time).count()
+    }
+
+    /// The bot that finished first
+    pub fn fastest_bot(&self) -> Option<&Bot> {
+        self.bots.iter().min_by_key(|b| b.finish_time())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bot_types_at_its_speed() {
+        let config = BotConfig {
+            variance: 0.0,
+            error_rate: 0.0,
+            ..BotConfig::new("steady", 60.0)
+        };
+        // 60 WPM are 5 chars per second
+        let bot = Bot::new(&config, 10, &mut rand::thread_rng());
+        assert_eq!(bot.progress(Duration::ZERO), 0);
+        assert_eq!(bot.progress(Duration::from_millis(1010)), 5);
+        assert_eq!(bot.progress(Duration::from_secs(5)), 10);
+        assert!((bot.finish_time().as_secs_f64() - 2.0).abs() < 1e-9);
+
+        let race = Race { bots: vec![bot] };
+        assert_eq!(race.place(Duration::from_secs(1)), 1);
+        assert_eq!(race.place(Duration::from_secs(3)), 2);
+    }
+
+    #[test]
+    fn mistakes_slow_bots_down() {
+        let config = BotConfig {
+            variance: 0.0,
+            error_rate: 1.0,
+            error_pause_ms: 1000,
+            ..BotConfig::new("clumsy", 60.0)
+        };
+        let bot = Bot::new(&config, 10, &mut rand::thread_rng());
+        assert!((bot.finish_time().as_secs_f64() - 12.0).abs() < 1e-9);
+    }
+}
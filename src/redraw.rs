@@ -0,0 +1,162 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{App, Mode};
+
+/// What changes on the screen with time alone. The app is drawn again when
+/// this differs from the last frame or after an event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Clocks {
+    /// session time in the header, or the time left
+    timer: String,
+    /// time of day in the header
+    wall: String,
+    countdown: Option<u64>,
+    pace: Option<usize>,
+    /// characters typed by each bot of a race
+    bots: Vec<usize>,
+    /// frame of the attract screen of an idle kiosk
+    attract: Option<u64>,
+    /// the running animations, see [`crate::anim`]
+    animations: Vec<i64>,
+}
+
+/// Frames drawn in the last second and how long the last one took, shown
+/// with `--frame-stats`
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    drawn: VecDeque<Instant>,
+    last: Duration,
+    total: u64,
+}
+
+impl FrameStats {
+    /// counts a frame drawn at `at` that took `took`
+    pub fn record(&mut self, at: Instant, took: Duration) {
+        self.drawn.push_back(at);
+        while self
+            .drawn
+            .front()
+            .is_some_and(|first| at.duration_since(*first) > Duration::from_secs(1))
+        {
+            self.drawn.pop_front();
+        }
+        self.last = took;
+        self.total += 1;
+    }
+
+    /// frames drawn in the second before the last one
+    pub fn fps(&self) -> usize {
+        self.drawn.len()
+    }
+}
+
+impl App<'_> {
+    /// the parts of the screen that move on with time at `now`
+    pub(crate) fn clocks(&self, now: Instant) -> Clocks {
+        let header = &self.config.header;
+        let elapsed = self
+            .round_start
+            .map(|start| now.saturating_duration_since(start))
+            .unwrap_or_default();
+        Clocks {
+            timer: self.header_timer(now),
+            wall: if header.show && header.show_clock {
+                self.config
+                    .timezone
+                    .datetime(Utc::now())
+                    .format(&header.clock_format)
+                    .to_string()
+            } else {
+                String::new()
+            },
+            countdown: self.session.countdown_left(now),
+            pace: self.pace_position(now),
+            bots: match self.mode {
+                Mode::Race(_) => self.race.bots.iter().map(|b| b.progress(elapsed)).collect(),
+                _ => vec![],
+            },
+            attract: self.attract_frame(now),
+            animations: self.animations.frame(now),
+        }
+    }
+
+    /// whether the screen at `now` differs from the last frame drawn, marks
+    /// it as drawn
+    pub(crate) fn take_redraw(&mut self, now: Instant) -> bool {
+        let clocks = self.clocks(now);
+        let redraw = std::mem::take(&mut self.dirty) || clocks != self.drawn_clocks;
+        self.drawn_clocks = clocks;
+        redraw
+    }
+
+    /// the frame rate and the time of the last frame in the bottom right
+    /// corner
+    pub(crate) fn render_frame_stats(&self, area: Rect, buf: &mut Buffer) {
+        let Some(stats) = &self.frame_stats else {
+            return;
+        };
+        let text = format!(
+            " {} fps  {:.1}ms  #{} ",
+            stats.fps(),
+            stats.last.as_secs_f64() * 1000.0,
+            stats.total
+        );
+        let width = (text.chars().count() as u16).min(area.width);
+        let corner = Rect::new(
+            area.right() - width,
+            area.bottom().saturating_sub(1),
+            width,
+            1.min(area.height),
+        );
+        Paragraph::new(text)
+            .style(self.theme.header)
+            .render(corner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+
+    #[test]
+    fn redraw_only_when_the_screen_changes() {
+        let mut app = App {
+            mode: Mode::Timed(30),
+            ..Default::default()
+        };
+        app.config.header.show_clock = false;
+        app.new_session().unwrap();
+        let start = Instant::now();
+        assert!(app.take_redraw(start));
+
+        // nothing moves while waiting for the start
+        assert!(!app.take_redraw(start + Duration::from_secs(5)));
+
+        app.handle_key_event_at(KeyCode::Char(' ').into(), start)
+            .unwrap();
+        app.dirty = true;
+        assert!(app.take_redraw(start));
+        assert!(!app.take_redraw(start + Duration::from_millis(500)));
+        // the countdown goes from 3 to 2
+        assert!(app.take_redraw(start + Duration::from_millis(1100)));
+
+        let mut stats = FrameStats::default();
+        for ms in [0, 500, 1200, 1300] {
+            stats.record(start + Duration::from_millis(ms), Duration::from_millis(2));
+        }
+        assert_eq!(stats.fps(), 3);
+        assert_eq!(stats.total, 4);
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    io::Write,
+    process::{Child, Command, Stdio},
+    thread,
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+
+use crate::{history::HistoryEntry, App};
+
+/// Starts `command` in the shell and writes `entry` to its input as JSON,
+/// the same document `--json` prints. The output of the command is
+/// discarded, it would garble the screen.
+pub fn pipe(command: &str, entry: &HistoryEntry) -> Result<Child> {
+    let json = serde_json::to_string(entry)?;
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .wrap_err_with(|| format!("starting `{command}` failed"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // the input is closed when dropped, so the command sees the end
+        writeln!(stdin, "{json}").wrap_err_with(|| format!("writing to `{command}` failed"))?;
+    }
+    Ok(child)
+}
+
+impl App<'_> {
+    /// pipes the report of the session into the report command of the
+    /// config, if there is one and not in kiosk mode, without waiting for it
+    pub(crate) fn send_report(&mut self, entry: &HistoryEntry) {
+        let command = self.config.report_command.trim();
+        // the results of a kiosk stay on it
+        if command.is_empty() || self.kiosk.is_some() {
+            return;
+        }
+        match pipe(command, entry) {
+            Ok(mut child) => {
+                thread::spawn(move || child.wait());
+            }
+            Err(error) => self.status = Some(format!("{error:#}")),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{stats::SessionStats, testing::temp_path, Mode};
+
+    #[test]
+    fn pipe_the_session_into_a_command() {
+        let path = temp_path("report.json");
+        let entry = HistoryEntry::new(&SessionStats::default(), Mode::Words(5), Utc::now());
+        let mut child = pipe(&format!("cat > {}", path.display()), &entry).unwrap();
+        assert!(child.wait().unwrap().success());
+
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let read: HistoryEntry = serde_json::from_str(&written).unwrap();
+        assert_eq!(read.mode, entry.mode);
+        assert_eq!(read.timestamp, entry.timestamp);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    confirm::ConfirmAction,
+    history::History,
+    session::SessionState,
+    stats::{BigramLatencies, ClassTallies, KeyLatencies, RoundRecord, SessionStats, WordTiming},
+    App, Mode, Screen, SpanType, TextSpan,
+};
+
+/// Name of the file of an interrupted test inside the data directory
+const RESUME_FILE: &str = "resume.json";
+/// How often a test in progress is saved, at most this much of it is lost
+/// when the terminal dies
+const AUTOSAVE: Duration = Duration::from_secs(5);
+
+/// Everything needed to continue a test that was quit while in progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub mode: Mode,
+    pub generator: String,
+    pub wins: u8,
+    pub fails: u8,
+    pub rounds: Vec<RoundRecord>,
+    /// the current round and how many bytes of it were typed
+    pub target: String,
+    pub typed: usize,
+    pub difficulty: f64,
+    pub round_misses: usize,
+    pub miss_this_round: bool,
+    pub round_words: Vec<WordTiming>,
+    pub round_classes: ClassTallies,
+    #[serde(default)]
+    pub round_latencies: KeyLatencies,
+    #[serde(default)]
+    pub round_bigrams: BigramLatencies,
+    #[serde(default)]
+    pub round_miss_positions: Vec<usize>,
+    #[serde(default)]
+    pub round_uncorrected: usize,
+    /// time on the clock of the session and of the current round
+    pub elapsed: Duration,
+    pub round_elapsed: Duration,
+}
+
+impl Snapshot {
+    /// Default location of the snapshot of `profile`, `None` if the
+    /// platform has no data directory
+    pub fn path(profile: &str) -> Option<PathBuf> {
+        History::dir(profile).map(|dir| dir.join(RESUME_FILE))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("creating {} failed", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .wrap_err_with(|| format!("writing {} failed", path.display()))
+    }
+
+    /// Loads the snapshot at `path` and removes the file, so a test is
+    /// offered for resuming only once
+    pub fn take(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        fs::remove_file(path).wrap_err_with(|| format!("removing {} failed", path.display()))?;
+        serde_json::from_str(&raw).wrap_err_with(|| format!("parsing {} failed", path.display()))
+    }
+}
+
+impl App<'_> {
+    /// the test in progress at `now`, `None` if there is none or it can't be
+    /// resumed
+    pub(crate) fn snapshot(&self, now: Instant) -> Option<Snapshot> {
+        // the bots wouldn't wait, and flagged tests aren't recorded anyway
+        if !self.test_in_progress()
+            || matches!(self.mode, Mode::Race(_))
+            || self.guard.flag().is_some()
+        {
+            return None;
+        }
+        Some(Snapshot {
+            mode: self.mode,
+            generator: self.config.generator.clone(),
+            wins: self.wins,
+            fails: self.fails,
+            rounds: self.stats.rounds.clone(),
+            target: self.target.clone(),
+            typed: self.target.len() - self.remainder.span.content.len(),
+            difficulty: self.difficulty,
+            round_misses: self.round_misses,
+            miss_this_round: self.miss_this_round,
+            round_words: self.round_words.clone(),
+            round_classes: self.round_classes.clone(),
+            round_latencies: self.round_latencies.clone(),
+            round_bigrams: self.round_bigrams.clone(),
+            round_miss_positions: self.round_miss_positions.clone(),
+            round_uncorrected: self.round_uncorrected,
+            elapsed: self.session.elapsed(now).unwrap_or_default(),
+            round_elapsed: self
+                .round_start
+                .map(|start| now.duration_since(start))
+                .unwrap_or_default(),
+        })
+    }
+
+    /// stores the test in progress to be resumed on the next launch
+    pub(crate) fn save_snapshot(&self, now: Instant) -> Result<()> {
+        match (&self.resume_path, self.snapshot(now)) {
+            (Some(path), Some(snapshot)) => snapshot
+                .save(path)
+                .wrap_err("saving the test for resuming failed"),
+            _ => Ok(()),
+        }
+    }
+
+    /// saves the test in progress every [`AUTOSAVE`] and removes the
+    /// snapshot once the test is over, so a crash doesn't lose it
+    pub(crate) fn autosave(&mut self, now: Instant) -> Result<()> {
+        if !self.test_in_progress() {
+            if self.autosaved.take().is_some() {
+                if let Some(path) = self.resume_path.as_ref().filter(|path| path.exists()) {
+                    fs::remove_file(path)
+                        .wrap_err_with(|| format!("removing {} failed", path.display()))?;
+                }
+            }
+            return Ok(());
+        }
+        if self
+            .autosaved
+            .is_some_and(|at| now.duration_since(at) < AUTOSAVE)
+        {
+            return Ok(());
+        }
+        self.autosaved = Some(now);
+        self.save_snapshot(now)
+    }
+
+    /// asks to resume the test that was quit or interrupted last time, if
+    /// there is one
+    pub(crate) fn offer_resume(&mut self) -> Result<()> {
+        let Some(path) = &self.resume_path else {
+            return Ok(());
+        };
+        self.resumable = Snapshot::take(path).wrap_err("loading the quit test failed")?;
+        if self.resumable.is_some() {
+            self.confirm(
+                "Resume the test you quit? If not, its rounds are recorded",
+                ConfirmAction::Resume,
+            );
+        }
+        Ok(())
+    }
+
+    /// records the rounds done in the test of `snapshot` to the history,
+    /// instead of losing them
+    pub(crate) fn record_snapshot(&mut self, snapshot: Snapshot) -> Result<()> {
+        if self.config.practice || snapshot.rounds.is_empty() {
+            return Ok(());
+        }
+        let stats = SessionStats {
+            rounds: snapshot.rounds,
+        };
+        let entry = self.history_entry(&stats, snapshot.mode, &snapshot.generator);
+        self.history
+            .push(entry)
+            .wrap_err("saving the quit test to the history failed")?;
+        self.status = Some(format!(
+            "recorded {} rounds of the test you quit",
+            stats.rounds.len()
+        ));
+        Ok(())
+    }
+
+    /// continues the test of `snapshot` with the clock running from `now`
+    pub(crate) fn resume(&mut self, snapshot: Snapshot, now: Instant) {
+        self.mode = snapshot.mode;
+        self.config.generator = snapshot.generator;
+        self.wins = snapshot.wins;
+        self.fails = snapshot.fails;
+        self.stats.rounds = snapshot.rounds;
+        self.show_root(Screen::Typing);
+        let ago = |elapsed| now.checked_sub(elapsed).unwrap_or(now);
+        self.session = SessionState::Running {
+            started: Some(ago(snapshot.elapsed)),
+        };
+        self.round_start = Some(ago(snapshot.round_elapsed));
+        self.word_start = None;
+
+        self.spans.clear();
+        let typed = snapshot.typed.min(snapshot.target.len());
+        let missed = &snapshot.round_miss_positions;
+        for (i, c) in snapshot.target[..typed].chars().enumerate() {
+            let span_type = if missed.contains(&i) {
+                SpanType::MISS
+            } else {
+                SpanType::HIT
+            };
+            self.push_typed(c, span_type);
+        }
+        // a miss at the expected character colors it once typed
+        self.miss_at_cursor = missed.last() == Some(&snapshot.target[..typed].chars().count());
+        self.remainder = TextSpan::default_with_text(snapshot.target[typed..].to_string());
+        self.target = snapshot.target;
+        self.difficulty = snapshot.difficulty;
+        self.round_misses = snapshot.round_misses;
+        self.miss_this_round = snapshot.miss_this_round;
+        self.round_words = snapshot.round_words;
+        self.round_classes = snapshot.round_classes;
+        self.round_latencies = snapshot.round_latencies;
+        self.round_bigrams = snapshot.round_bigrams;
+        self.round_miss_positions = snapshot.round_miss_positions;
+        self.round_uncorrected = snapshot.round_uncorrected;
+        self.expected_since = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn resume_where_the_test_was_quit() {
+        let mut app = App {
+            mode: Mode::Words(2),
+            ..Default::default()
+        };
+        app.target = "ab cd".into();
+        app.remainder = TextSpan::default_with_text("ab cd".into());
+        let start = Instant::now();
+        for (i, c) in "abx ".chars().enumerate() {
+            let at = start + Duration::from_secs(i as u64);
+            app.handle_key_event_at(KeyCode::Char(c).into(), at)
+                .unwrap();
+        }
+        let snapshot = app.snapshot(start + Duration::from_secs(5)).unwrap();
+        assert_eq!(snapshot.typed, 3);
+        assert_eq!(snapshot.elapsed, Duration::from_secs(5));
+
+        let mut resumed = App::default();
+        let later = start + Duration::from_secs(100);
+        resumed.resume(snapshot.clone(), later);
+        assert_eq!(resumed.remainder.span.content, "cd");
+        // the space was typed after the x
+        let spans: Vec<(SpanType, &str)> = resumed
+            .spans
+            .iter()
+            .map(|span| (span.span_type, span.span.content.as_ref()))
+            .collect();
+        assert_eq!(spans, [(SpanType::HIT, "ab"), (SpanType::MISS, " ")]);
+        assert_eq!(resumed.session.elapsed(later), Some(Duration::from_secs(5)));
+        assert_eq!(resumed.snapshot(later), Some(snapshot));
+    }
+
+    #[test]
+    fn autosave_and_record_the_rounds_when_not_resumed() {
+        let path = temp_path("resume.json");
+        let mut app = App {
+            mode: Mode::Words(1),
+            resume_path: Some(path.clone()),
+            ..Default::default()
+        };
+        app.target = "ab".into();
+        app.remainder = TextSpan::default_with_text("ab".into());
+        let start = Instant::now();
+        app.handle_key_event_at(KeyCode::Char('a').into(), start)
+            .unwrap();
+        app.autosave(start).unwrap();
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+        app.autosave(start + AUTOSAVE / 2).unwrap();
+        assert!(!path.exists());
+        app.autosave(start + AUTOSAVE).unwrap();
+        assert!(path.exists());
+
+        let mut snapshot = Snapshot::take(&path).unwrap().unwrap();
+        snapshot.rounds = vec![RoundRecord::typed("ab", Duration::from_secs(1))];
+        // the snapshot of a test that's over is removed
+        app.save_snapshot(start).unwrap();
+        app.session = SessionState::Finished;
+        app.autosave(start + AUTOSAVE * 2).unwrap();
+        assert!(!path.exists());
+
+        let mut fresh = App::default();
+        fresh.record_snapshot(snapshot).unwrap();
+        assert_eq!(fresh.history.entries().len(), 1);
+        assert_eq!(fresh.history.entries()[0].mode, Mode::Words(1));
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Instant;
+
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{config::BorderStyle, App, Mode};
+
+impl App<'_> {
+    /// shows where the misses of the finished round were if configured,
+    /// otherwise goes on with the next round
+    pub(crate) fn review_or_next_round(&mut self) -> Result<()> {
+        let misses = self
+            .stats
+            .rounds
+            .last()
+            .map_or(0, |round| round.miss_positions.len());
+        if !self.config.session.review_misses
+            || misses == 0
+            || matches!(self.mode, Mode::Timed(_) | Mode::Race(_))
+        {
+            return self.next_round();
+        }
+        self.reviewing = true;
+        let plural = if misses == 1 { "" } else { "es" };
+        self.status = Some(format!("{misses} miss{plural}, Space: continue"));
+        Ok(())
+    }
+
+    /// starts the next round, its clock starts with its first key
+    pub(crate) fn end_review(&mut self) -> Result<()> {
+        self.reviewing = false;
+        self.round_start = None;
+        self.word_start = None;
+        self.next_round()
+    }
+
+    pub(crate) fn handle_review_key(&mut self, key_event: KeyEvent, now: Instant) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.quit_test(now),
+            KeyCode::Tab => self.new_session()?,
+            KeyCode::Char(' ') | KeyCode::Enter => self.end_review()?,
+            // keep the hint up while other keys are pressed
+            _ => self.status = Some("Space: continue".into()),
+        }
+        Ok(())
+    }
+
+    /// the text of the finished round with its missed characters marked
+    pub(crate) fn render_review(&self, area: Rect, buf: &mut Buffer) {
+        let Some(round) = self.stats.rounds.last() else {
+            return;
+        };
+        let spans: Vec<Span> = round
+            .target
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let style = if round.miss_positions.contains(&i) {
+                    self.theme.miss
+                } else {
+                    self.theme.hit
+                };
+                Span::styled(c.to_string(), style)
+            })
+            .collect();
+        let line = Line::from(spans);
+        let panel = &self.config.panels.input;
+        let frame = if panel.border == BorderStyle::None {
+            0
+        } else {
+            2
+        };
+        let width = line.width().max(panel.title.chars().count()) as u16 + frame;
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Min(1),
+                Constraint::Length(width),
+                Constraint::Min(1),
+            ])
+            .split(area);
+        Paragraph::new(line)
+            .block(self.panel_block(panel))
+            .render(layout[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextSpan;
+
+    #[test]
+    fn review_misses_before_the_next_round() {
+        let mut app = App::default();
+        app.config.session.review_misses = true;
+        app.target = "abc".into();
+        app.remainder = TextSpan::default_with_text("abc".into());
+        for c in "axbyyc".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        assert!(app.reviewing);
+        assert_eq!(app.stats.rounds[0].miss_positions, vec![1, 2]);
+        assert_eq!(app.status.as_deref(), Some("2 misses, Space: continue"));
+
+        // typing doesn't go anywhere until the review is over
+        app.handle_key_event(KeyCode::Char('a').into()).unwrap();
+        assert_eq!(app.stats.rounds.len(), 1);
+        assert!(app.reviewing);
+        app.handle_key_event(KeyCode::Char(' ').into()).unwrap();
+        assert!(!app.reviewing);
+        assert!(app.round_start.is_none());
+        assert_ne!(app.target, "abc");
+    }
+}
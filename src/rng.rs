@@ -0,0 +1,48 @@
+use std::ops::{Deref, DerefMut};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The random number generator behind the rounds, seeded from the OS unless
+/// a seed is given
+#[derive(Debug, Clone)]
+pub struct RoundRng {
+    rng: StdRng,
+    seed: Option<u64>,
+}
+
+impl RoundRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed: Some(seed),
+        }
+    }
+
+    /// the seed given, `None` if seeded from the OS
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl Default for RoundRng {
+    fn default() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            seed: None,
+        }
+    }
+}
+
+impl Deref for RoundRng {
+    type Target = StdRng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rng
+    }
+}
+
+impl DerefMut for RoundRng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rng
+    }
+}
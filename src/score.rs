@@ -0,0 +1,89 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Words an uncorrected error costs in the transcription score
+const UNCORRECTED_PENALTY: f64 = 1.0;
+
+/// How speed and accuracy are combined into the skill score
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreFormula {
+    /// `wpm * accuracy²`, punishes mistakes harder than lost speed
+    #[default]
+    WpmAccuracySquared,
+    /// `wpm * accuracy`
+    WpmAccuracy,
+    /// `wpm - errors per minute`, the classic net WPM
+    NetWpm,
+    /// for transcription: the speed on the final output, with every
+    /// character left wrong costing another word
+    Transcription,
+}
+
+impl ScoreFormula {
+    pub const ALL: [Self; 4] = [
+        Self::WpmAccuracySquared,
+        Self::WpmAccuracy,
+        Self::NetWpm,
+        Self::Transcription,
+    ];
+
+    /// Combines the speed in WPM, the accuracy between 0.0 and 1.0, the
+    /// errors per minute and the uncorrected ones among them into a single
+    /// score, never below zero
+    pub fn score(
+        self,
+        wpm: f64,
+        accuracy: f64,
+        errors_per_minute: f64,
+        uncorrected_per_minute: f64,
+    ) -> f64 {
+        let score = match self {
+            Self::WpmAccuracySquared => wpm * accuracy * accuracy,
+            Self::WpmAccuracy => wpm * accuracy,
+            Self::NetWpm => wpm - errors_per_minute,
+            // the characters left wrong are 5 to a word of the output
+            Self::Transcription => {
+                wpm - uncorrected_per_minute / 5.0 - uncorrected_per_minute * UNCORRECTED_PENALTY
+            }
+        };
+        score.max(0.0)
+    }
+}
+
+impl fmt::Display for ScoreFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WpmAccuracySquared => write!(f, "WPM × accuracy²"),
+            Self::WpmAccuracy => write!(f, "WPM × accuracy"),
+            Self::NetWpm => write!(f, "net WPM"),
+            Self::Transcription => write!(f, "transcription"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formulas() {
+        assert_eq!(
+            ScoreFormula::WpmAccuracySquared.score(100.0, 0.9, 5.0, 0.0),
+            81.0
+        );
+        assert_eq!(ScoreFormula::WpmAccuracy.score(100.0, 0.9, 5.0, 0.0), 90.0);
+        assert_eq!(ScoreFormula::NetWpm.score(100.0, 0.9, 5.0, 0.0), 95.0);
+        assert_eq!(ScoreFormula::NetWpm.score(10.0, 0.1, 50.0, 0.0), 0.0);
+        // corrected errors only cost the time it took to correct them
+        assert_eq!(
+            ScoreFormula::Transcription.score(100.0, 0.9, 5.0, 0.0),
+            100.0
+        );
+        assert_eq!(
+            ScoreFormula::Transcription.score(100.0, 0.9, 5.0, 5.0),
+            94.0
+        );
+    }
+}
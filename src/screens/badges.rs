@@ -0,0 +1,82 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Row, Table, Widget},
+};
+
+use crate::{badges::BADGES, App};
+
+impl App<'_> {
+    pub(crate) fn handle_badges_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if key_event.code == KeyCode::Esc {
+            self.pop_screen();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_badges(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        Line::from(vec![
+            Span::raw(" unlocked: "),
+            Span::styled(
+                format!("{} of {}", self.badges.unlocked.len(), BADGES.len()),
+                self.theme.value,
+            ),
+        ])
+        .render(layout[0], buf);
+
+        let entries = self.history.entries();
+        let rows: Vec<Row> = BADGES
+            .iter()
+            .map(|badge| {
+                let (marker, style, state) = match self.badges.unlocked.get(badge.id) {
+                    Some(at) => (
+                        "★",
+                        self.theme.hit,
+                        self.config
+                            .timezone
+                            .datetime(*at)
+                            .format("%Y-%m-%d")
+                            .to_string(),
+                    ),
+                    None => (
+                        "",
+                        self.theme.border,
+                        format!("{:.0}%", badge.goal.progress(entries) * 100.0),
+                    ),
+                };
+                Row::new(vec![
+                    Line::styled(marker, style),
+                    Line::styled(badge.name, style),
+                    Line::raw(badge.goal.describe()),
+                    Line::raw(state),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(14),
+            Constraint::Min(20),
+            Constraint::Length(10),
+        ];
+        Table::new(rows, widths)
+            .header(Row::new(vec!["", "badge", "goal", "unlocked"]).style(self.theme.title))
+            .render(layout[1], buf);
+
+        Paragraph::new(" Esc: back")
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+}
@@ -0,0 +1,279 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Row, Table, Widget},
+};
+
+use super::stats::MIN_PRESSES;
+use crate::{compare, history::HistoryEntry, App};
+
+impl App<'_> {
+    /// the two marked entries, the older one first
+    fn compared(&self) -> Option<(&HistoryEntry, &HistoryEntry)> {
+        let mut marked = self.history_marked.clone();
+        marked.sort_unstable();
+        match marked[..] {
+            [a, b] => Some((
+                self.history.entries().get(a)?,
+                self.history.entries().get(b)?,
+            )),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn handle_compare_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if key_event.code == KeyCode::Esc {
+            self.pop_screen();
+        }
+        Ok(())
+    }
+
+    /// the style of a change, `better` tells if it's an improvement
+    fn change_style(&self, better: bool) -> Style {
+        if better {
+            self.theme.hit
+        } else {
+            self.theme.miss
+        }
+    }
+
+    pub(crate) fn render_compare(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Min(6),
+                Constraint::Length(7),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        Paragraph::new(" Esc: back")
+            .style(self.theme.title)
+            .render(layout[3], buf);
+        let Some((a, b)) = self.compared() else {
+            Paragraph::new("mark two sessions in the history to compare them")
+                .centered()
+                .render(layout[1], buf);
+            return;
+        };
+
+        self.render_compare_summary(layout[0], buf, a, b);
+        self.render_compare_curves(layout[1], buf, a, b);
+        let panels = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(layout[2]);
+        self.render_key_diffs(panels[0], buf, a, b);
+        self.render_class_diffs(panels[1], buf, a, b);
+    }
+
+    fn render_compare_summary(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        a: &HistoryEntry,
+        b: &HistoryEntry,
+    ) {
+        let row = |name: &'static str, e: &HistoryEntry| {
+            Row::new(vec![
+                name.to_string(),
+                self.config
+                    .timezone
+                    .datetime(e.timestamp)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                e.category(),
+                format!("{:.1}", e.wpm),
+                format!("{:.1}%", e.accuracy() * 100.0),
+                format!("{:.1}s", e.duration_ms as f64 / 1000.0),
+            ])
+        };
+        let wpm = b.wpm - a.wpm;
+        let accuracy = (b.accuracy() - a.accuracy()) * 100.0;
+        let change = Row::new(vec![
+            Line::raw("Δ"),
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(format!("{wpm:+.1}"), self.change_style(wpm >= 0.0)),
+            Line::styled(
+                format!("{accuracy:+.1}"),
+                self.change_style(accuracy >= 0.0),
+            ),
+            Line::raw(""),
+        ]);
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(16),
+            Constraint::Length(18),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Min(7),
+        ];
+        Table::new(vec![row("A", a), row("B", b), change], widths)
+            .header(
+                Row::new(vec!["", "date", "mode", "WPM", "accuracy", "time"])
+                    .style(self.theme.title),
+            )
+            .render(area, buf);
+    }
+
+    /// the speed of every round of both sessions on top of each other
+    fn render_compare_curves(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        a: &HistoryEntry,
+        b: &HistoryEntry,
+    ) {
+        let points = |e: &HistoryEntry| -> Vec<(f64, f64)> {
+            e.curve
+                .iter()
+                .enumerate()
+                .map(|(i, wpm)| ((i + 1) as f64, *wpm))
+                .collect()
+        };
+        let (curve_a, curve_b) = (points(a), points(b));
+        let rounds = a.curve.len().max(b.curve.len()).max(2);
+        let max_y = a
+            .curve
+            .iter()
+            .chain(&b.curve)
+            .fold(10.0, |max: f64, wpm| max.max(*wpm))
+            .ceil();
+
+        // different markers keep the curves apart without colors
+        let datasets = vec![
+            Dataset::default()
+                .name("A")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(self.theme.value)
+                .data(&curve_a),
+            Dataset::default()
+                .name("B")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(self.theme.hit)
+                .data(&curve_b),
+        ];
+        let block = Block::default()
+            .title(Span::styled(" WPM PER ROUND ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        // without the legend there's no telling which curve is which
+        Chart::new(datasets)
+            .block(block)
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Min(0)))
+            .x_axis(
+                Axis::default()
+                    .style(self.theme.border)
+                    .bounds([1.0, rounds as f64])
+                    .labels(vec!["1".into(), rounds.to_string().into()]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(self.theme.border)
+                    .bounds([0.0, max_y])
+                    .labels(vec!["0".into(), format!("{max_y:.0}").into()]),
+            )
+            .render(area, buf);
+    }
+
+    /// the keys whose speed changed the most
+    fn render_key_diffs(&self, area: Rect, buf: &mut Buffer, a: &HistoryEntry, b: &HistoryEntry) {
+        let lines: Vec<Line> = compare::key_diffs(a, b, MIN_PRESSES)
+            .into_iter()
+            .take(area.height.saturating_sub(2).into())
+            .map(|diff| {
+                let key = match diff.key {
+                    ' ' => "space".to_string(),
+                    c => c.to_string(),
+                };
+                Line::from(vec![
+                    Span::raw(format!(
+                        " {key:<5} {:>4.0} → {:>4.0}ms ",
+                        diff.before_ms, diff.after_ms
+                    )),
+                    Span::styled(
+                        format!("{:+.0}", diff.delta_ms()),
+                        self.change_style(diff.delta_ms() <= 0.0),
+                    ),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(" KEYS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    /// misses per class of characters
+    fn render_class_diffs(&self, area: Rect, buf: &mut Buffer, a: &HistoryEntry, b: &HistoryEntry) {
+        let lines: Vec<Line> = compare::class_diffs(a, b)
+            .into_iter()
+            .map(|diff| {
+                Line::from(vec![
+                    Span::raw(format!(
+                        " {:<8} {:>3} → {:>3} misses ",
+                        diff.class.to_string(),
+                        diff.before.misses,
+                        diff.after.misses
+                    )),
+                    Span::styled(
+                        format!("{:+.1}%", diff.delta()),
+                        self.change_style(diff.delta() >= 0.0),
+                    ),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(" ERRORS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{stats::SessionStats, Mode, Screen};
+
+    #[test]
+    fn compare_two_marked_entries() {
+        let mut app = App::default();
+        for wpm in [40.0, 50.0, 60.0] {
+            let mut entry = HistoryEntry::new(&SessionStats::default(), Mode::Random, Utc::now());
+            entry.wpm = wpm;
+            app.history.push(entry).unwrap();
+        }
+        app.push_screen(Screen::History);
+
+        app.handle_key_event(KeyCode::Char('c').into()).unwrap();
+        assert_eq!(app.screen, Screen::History);
+        assert!(app.status.is_some());
+
+        // the newest and the oldest
+        app.handle_key_event(KeyCode::Char(' ').into()).unwrap();
+        for code in [KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')] {
+            app.handle_key_event(code.into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Char('c').into()).unwrap();
+        assert_eq!(app.screen, Screen::Compare);
+        let (a, b) = app.compared().unwrap();
+        assert_eq!((a.wpm, b.wpm), (40.0, 60.0));
+
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(app.screen, Screen::History);
+    }
+}
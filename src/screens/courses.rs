@@ -0,0 +1,113 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::App;
+
+impl App<'_> {
+    /// the course and drill index of every drill, in the order they're listed
+    fn course_rows(&self) -> Vec<(usize, usize)> {
+        self.courses
+            .iter()
+            .enumerate()
+            .flat_map(|(c, course)| (0..course.drills.len()).map(move |d| (c, d)))
+            .collect()
+    }
+
+    pub(crate) fn handle_courses_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let rows = self.course_rows();
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.course_cursor = self.course_cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = rows.len().saturating_sub(1);
+                self.course_cursor = (self.course_cursor + 1).min(last);
+            }
+            KeyCode::Enter => {
+                if let Some((course, drill)) = rows.get(self.course_cursor).copied() {
+                    self.status = Some(self.start_course_drill(course, drill)?);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_courses(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(3), Constraint::Length(1)])
+            .margin(1)
+            .split(area);
+
+        Paragraph::new(" ↑/↓: select  Enter: practice  Esc: back")
+            .style(self.theme.title)
+            .render(layout[1], buf);
+        if self.courses.is_empty() {
+            Paragraph::new("no courses yet, load one with `metyping course load <pack.toml>`")
+                .centered()
+                .render(layout[0], buf);
+            return;
+        }
+
+        let rows: Vec<Row> = self
+            .course_rows()
+            .into_iter()
+            .map(|(c, d)| {
+                let course = &self.courses[c];
+                let drill = &course.drills[d];
+                let passed = self.course_progress.is_passed(course, drill);
+                let current = self.course_drill == Some((c, d));
+                let (marker, style) = if passed {
+                    ("✓", self.theme.hit)
+                } else if current {
+                    ("▶", self.theme.value)
+                } else if d < self.course_progress.unlocked(course) {
+                    ("", self.theme.value)
+                } else {
+                    ("", self.theme.border)
+                };
+                let pass = match (drill.pass.wpm, drill.pass.accuracy) {
+                    (wpm, accuracy) if wpm > 0.0 || accuracy > 0.0 => {
+                        format!("{wpm:.0} WPM {:.0}%", accuracy * 100.0)
+                    }
+                    _ => "finish".into(),
+                };
+                Row::new(vec![
+                    Line::styled(marker, style),
+                    // the name of the course once, above its drills
+                    Line::raw(if d == 0 {
+                        course.name.clone()
+                    } else {
+                        String::new()
+                    }),
+                    Line::styled(drill.name.clone(), style),
+                    Line::raw(drill.mode.to_string()),
+                    Line::raw(pass),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(16),
+            Constraint::Min(14),
+            Constraint::Length(12),
+            Constraint::Length(14),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec!["", "course", "drill", "mode", "to pass"]).style(self.theme.title),
+            )
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.course_cursor));
+        StatefulWidget::render(table, layout[0], buf, &mut state);
+    }
+}
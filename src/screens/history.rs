@@ -0,0 +1,323 @@
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, StatefulWidget, Table, TableState, Widget},
+};
+
+use crate::{
+    confirm::ConfirmAction,
+    form::{FieldEvent, TextInput},
+    practice::{self, Practice},
+    App, Screen,
+};
+
+/// The part of a history entry being edited in the browser
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryField {
+    Tags,
+    Note,
+}
+
+impl App<'_> {
+    /// index into the history of the selected row, the newest entry is at the
+    /// top. `None` while practice sessions are shown.
+    fn selected_entry(&self) -> Option<usize> {
+        let len = self.history.entries().len();
+        (!self.history_grouped && self.history_cursor < len).then(|| len - 1 - self.history_cursor)
+    }
+
+    /// the practice sessions of the history, newest first
+    fn practice_sessions(&self) -> Vec<Practice> {
+        let mut sessions = practice::sessions(self.history.entries(), practice::GAP);
+        sessions.reverse();
+        sessions
+    }
+
+    fn history_rows(&self) -> usize {
+        if self.history_grouped {
+            self.practice_sessions().len()
+        } else {
+            self.history.entries().len()
+        }
+    }
+
+    fn edit_entry(&mut self, field: EntryField) {
+        let Some(entry) = self
+            .selected_entry()
+            .and_then(|i| self.history.entries().get(i))
+        else {
+            return;
+        };
+        let value = match field {
+            EntryField::Tags => entry.tags.join(", "),
+            EntryField::Note => entry.note.clone(),
+        };
+        self.history_field = Some((field, TextInput::new(&value)));
+    }
+
+    /// removes the entry at `index` of the history
+    pub(crate) fn delete_entry(&mut self, index: usize) -> Result<()> {
+        self.history
+            .remove(index)
+            .wrap_err("deleting the session failed")?;
+        let last = self.history.entries().len().saturating_sub(1);
+        self.history_cursor = self.history_cursor.min(last);
+        // the indices after the removed entry moved
+        self.history_marked.clear();
+        Ok(())
+    }
+
+    /// marks the selected entry for the comparison, or unmarks it. Marking a
+    /// third entry unmarks the first.
+    fn mark_entry(&mut self) {
+        let Some(index) = self.selected_entry() else {
+            return;
+        };
+        if let Some(pos) = self.history_marked.iter().position(|i| *i == index) {
+            self.history_marked.remove(pos);
+            return;
+        }
+        if self.history_marked.len() == 2 {
+            self.history_marked.remove(0);
+        }
+        self.history_marked.push(index);
+    }
+
+    fn open_comparison(&mut self) {
+        if self.history_marked.len() == 2 {
+            self.push_screen(Screen::Compare);
+        } else {
+            self.status = Some("mark two sessions with Space to compare them".into());
+        }
+    }
+
+    fn handle_history_field_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some((field, input)) = &mut self.history_field else {
+            return Ok(());
+        };
+        match input.handle_key(key_event) {
+            FieldEvent::Submit => {
+                let (field, value) = (*field, input.value().trim().to_string());
+                self.history_field = None;
+                let Some(entry) = self.selected_entry().and_then(|i| self.history.get_mut(i))
+                else {
+                    return Ok(());
+                };
+                match field {
+                    EntryField::Tags => {
+                        entry.tags = value
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|t| !t.is_empty())
+                            .map(String::from)
+                            .collect();
+                    }
+                    EntryField::Note => entry.note = value,
+                }
+                self.history.save().wrap_err("saving the history failed")?;
+            }
+            FieldEvent::Cancel => self.history_field = None,
+            FieldEvent::Edited | FieldEvent::Ignored => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn handle_history_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if self.history_field.is_some() {
+            return self.handle_history_field_key(key_event);
+        }
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.history_cursor = self.history_cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.history_rows().saturating_sub(1);
+                self.history_cursor = (self.history_cursor + 1).min(last);
+            }
+            KeyCode::Char('g') => {
+                self.history_grouped = !self.history_grouped;
+                self.history_cursor = 0;
+            }
+            KeyCode::Char('t') => self.edit_entry(EntryField::Tags),
+            KeyCode::Char('n') => self.edit_entry(EntryField::Note),
+            KeyCode::Char(' ') => self.mark_entry(),
+            KeyCode::Char('c') => self.open_comparison(),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(index) = self.selected_entry() {
+                    let summary = self.history.entries()[index].summary();
+                    self.confirm(
+                        format!("Delete {summary}?"),
+                        ConfirmAction::DeleteEntry(index),
+                    );
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_history(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(4),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        if self.history_grouped {
+            self.render_practice_sessions(layout[0], buf);
+            Paragraph::new(" ↑/↓: select  g: single tests  Esc: back")
+                .style(self.theme.title)
+                .render(layout[2], buf);
+            return;
+        }
+
+        let len = self.history.entries().len();
+        let rows = self
+            .history
+            .entries()
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(row, entry)| {
+                let marked = self.history_marked.contains(&(len - 1 - row));
+                Row::new(vec![
+                    if marked { "•" } else { "" }.to_string(),
+                    self.config
+                        .timezone
+                        .datetime(entry.timestamp)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                    entry.category(),
+                    format!("{:.1}", entry.wpm),
+                    format!("{:.1}%", entry.accuracy() * 100.0),
+                    entry.tags.join(", "),
+                    entry.note.clone(),
+                ])
+            });
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Length(16),
+            Constraint::Length(18),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(14),
+            Constraint::Min(10),
+        ];
+        let block = Block::default()
+            .title(Span::styled(" HISTORY ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec!["", "date", "mode", "WPM", "accuracy", "tags", "note"])
+                    .style(self.theme.title),
+            )
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.history_cursor));
+        StatefulWidget::render(table, layout[0], buf, &mut state);
+
+        if let Some((field, input)) = &self.history_field {
+            let label = match field {
+                EntryField::Tags => " tags (comma separated): ",
+                EntryField::Note => " note: ",
+            };
+            let mut spans = vec![Span::raw(label)];
+            spans.extend(input.spans(self.theme.value));
+            Paragraph::new(Line::from(spans)).render(layout[1], buf);
+        }
+        let help = if self.history_field.is_some() {
+            " Enter: keep  Esc: discard"
+        } else {
+            " ↑/↓: select  t: tags  n: note  d: delete  Space: mark  c: compare  g: sessions  Esc: back"
+        };
+        Paragraph::new(help)
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+
+    /// tests taken with short breaks grouped, with how the speed changed
+    /// over each session
+    fn render_practice_sessions(&self, area: Rect, buf: &mut Buffer) {
+        let rows = self.practice_sessions().into_iter().map(|p| {
+            let start = self.config.timezone.datetime(p.start);
+            let end = self.config.timezone.datetime(p.end);
+            Row::new(vec![
+                format!(
+                    "{} - {}",
+                    start.format("%Y-%m-%d %H:%M"),
+                    end.format("%H:%M")
+                ),
+                p.tests.to_string(),
+                format!("{:.1}", p.wpm),
+                format!("{:+.1}", p.warm_up),
+                format!("{:.1}", p.fatigue),
+            ])
+        });
+        let widths = [
+            Constraint::Length(24),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(8),
+        ];
+        let block = Block::default()
+            .title(Span::styled(" PRACTICE SESSIONS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec!["time", "tests", "avg WPM", "warm-up", "fatigue"])
+                    .style(self.theme.title),
+            )
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.history_cursor));
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{history::HistoryEntry, stats::SessionStats, Mode, Screen};
+
+    #[test]
+    fn tag_and_delete_entries() {
+        let mut app = App::default();
+        for mode in [Mode::Random, Mode::Words(10)] {
+            let entry = HistoryEntry::new(&SessionStats::default(), mode, Utc::now());
+            app.history.push(entry).unwrap();
+        }
+        app.push_screen(Screen::History);
+
+        // the newest entry is selected
+        for code in [KeyCode::Char('t'), KeyCode::Char('a')] {
+            app.handle_key_event(code.into()).unwrap();
+        }
+        for c in ", b ,".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.history.entries()[1].tags, vec!["a", "b"]);
+
+        app.handle_key_event(KeyCode::Char('d').into()).unwrap();
+        app.handle_key_event(KeyCode::Char('n').into()).unwrap();
+        assert_eq!(app.history.entries().len(), 2);
+        app.handle_key_event(KeyCode::Char('d').into()).unwrap();
+        app.handle_key_event(KeyCode::Char('y').into()).unwrap();
+        assert_eq!(app.history.entries().len(), 1);
+        assert_eq!(app.history.entries()[0].mode, Mode::Random);
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::Utc;
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, Widget},
+};
+
+use crate::{leaderboard, App};
+
+impl App<'_> {
+    pub(crate) fn handle_leaderboard_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let categories = leaderboard::categories(self.history.entries()).len().max(1);
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.leaderboard_category =
+                    (self.leaderboard_category + categories - 1) % categories;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.leaderboard_category = (self.leaderboard_category + 1) % categories;
+            }
+            KeyCode::Char('d') => self.leaderboard_filter = self.leaderboard_filter.next(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_leaderboard(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        let categories = leaderboard::categories(self.history.entries());
+        let category = categories
+            .get(self.leaderboard_category % categories.len().max(1))
+            .cloned()
+            .unwrap_or_else(|| "-".into());
+        Paragraph::new(Line::from(vec![
+            Span::raw(" ◀ "),
+            Span::styled(category.as_str(), self.theme.value),
+            Span::raw(" ▶  "),
+            Span::raw(self.leaderboard_filter.to_string()),
+        ]))
+        .render(layout[0], buf);
+
+        let top = leaderboard::top(
+            self.history.entries(),
+            &category,
+            self.leaderboard_filter,
+            Utc::now(),
+            self.config.timezone,
+        );
+        let rows = top.iter().enumerate().map(|(i, entry)| {
+            Row::new(vec![
+                format!("{:>2}.", i + 1),
+                format!("{:.1}", entry.wpm),
+                format!("{:.1}%", entry.accuracy() * 100.0),
+                self.config
+                    .timezone
+                    .datetime(entry.timestamp)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Min(16),
+        ];
+        let block = Block::default()
+            .title(Span::styled(" PERSONAL BESTS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Table::new(rows, widths)
+            .header(Row::new(vec!["#", "WPM", "accuracy", "date"]).style(self.theme.title))
+            .block(block)
+            .render(layout[1], buf);
+
+        Paragraph::new(" ←/→: mode  d: date filter  Esc: back")
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+}
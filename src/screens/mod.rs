@@ -0,0 +1,15 @@
+//! Rendering and key handling of the screens besides the typing test
+
+mod badges;
+mod compare;
+mod courses;
+mod history;
+mod leaderboard;
+mod plan;
+mod profiles;
+mod results;
+mod settings;
+mod stats;
+mod trends;
+
+pub use history::EntryField;
@@ -0,0 +1,107 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table, Widget},
+};
+
+use crate::{plan, App};
+
+impl App<'_> {
+    pub(crate) fn handle_plan_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Enter => self.status = Some(self.run_plan()?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_plan(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Min(3),
+                Constraint::Length(9),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        Paragraph::new(" Enter: run today's plan  Esc: back")
+            .style(self.theme.title)
+            .render(layout[3], buf);
+        if self.config.plan.is_empty() {
+            Paragraph::new("no plan yet, add [[plan]] items to the config")
+                .centered()
+                .render(layout[1], buf);
+            return;
+        }
+
+        let today = self.today();
+        let zone = self.config.timezone;
+        let entries = self.history.entries();
+        let (done, total) = plan::progress(&self.config.plan, entries, today, zone);
+        Line::from(vec![
+            Span::raw(format!(" {}: ", today.format("%a %Y-%m-%d"))),
+            Span::styled(format!("{done} of {total} done"), self.theme.value),
+        ])
+        .render(layout[0], buf);
+
+        let rows: Vec<Row> = plan::scheduled(&self.config.plan, today)
+            .into_iter()
+            .map(|item| {
+                let result = item.result(entries, today, zone);
+                let current = self.running_plan && self.plan_item.as_ref() == Some(item);
+                let (marker, style) = match (result, current) {
+                    (Some(_), _) => ("✓", self.theme.hit),
+                    (None, true) => ("▶", self.theme.value),
+                    (None, false) => ("", self.theme.border),
+                };
+                let result = result.map_or_else(
+                    || "-".into(),
+                    |e| format!("{:.1} WPM {:.1}%", e.wpm, e.accuracy() * 100.0),
+                );
+                Row::new(vec![
+                    Line::styled(marker, style),
+                    Line::raw(item.name.clone()),
+                    Line::raw(item.mode.to_string()),
+                    Line::raw(result),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(16),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ];
+        Table::new(rows, widths)
+            .header(Row::new(vec!["", "item", "mode", "result"]).style(self.theme.title))
+            .render(layout[1], buf);
+
+        let week: Vec<Line> = plan::week(today)
+            .map(|day| {
+                let (done, total) = plan::progress(&self.config.plan, entries, day, zone);
+                let style = if total > 0 && done == total {
+                    self.theme.hit
+                } else {
+                    self.theme.value
+                };
+                let marker = if day == today { "▶" } else { " " };
+                Line::from(vec![
+                    Span::raw(format!(" {marker} {} ", day.format("%a"))),
+                    Span::styled(format!("{done}/{total}"), style),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(" THIS WEEK ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(week).block(block).render(layout[2], buf);
+    }
+}
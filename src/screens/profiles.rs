@@ -0,0 +1,146 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListState, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    form::{FieldEvent, TextInput},
+    profile::MAX_PROFILE_LEN,
+    App,
+};
+
+impl App<'_> {
+    fn handle_profile_input_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some(input) = &mut self.profile_input else {
+            return Ok(());
+        };
+        match input.handle_key(key_event) {
+            FieldEvent::Submit => {
+                let name = input.value().trim().to_string();
+                self.profile_input = None;
+                self.status = Some(self.switch_user(&name)?);
+            }
+            FieldEvent::Cancel => self.profile_input = None,
+            FieldEvent::Edited | FieldEvent::Ignored => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn handle_profiles_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if self.profile_input.is_some() {
+            return self.handle_profile_input_key(key_event);
+        }
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.profile_cursor = self.profile_cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.profiles.len().saturating_sub(1);
+                self.profile_cursor = (self.profile_cursor + 1).min(last);
+            }
+            KeyCode::Char('n') => {
+                self.profile_input = Some(TextInput::default().max_len(MAX_PROFILE_LEN));
+            }
+            KeyCode::Enter => match self.profiles.get(self.profile_cursor).cloned() {
+                // the current profile goes on where it was
+                Some(name) if name == self.config.profile => self.pop_screen(),
+                Some(name) => self.status = Some(self.switch_user(&name)?),
+                None => {}
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_profiles(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        let items: Vec<Line> = self
+            .profiles
+            .iter()
+            .map(|name| {
+                if *name == self.config.profile {
+                    Line::from(vec![
+                        Span::raw(format!(" {name} ")),
+                        Span::styled("(current)", self.theme.value),
+                    ])
+                } else {
+                    Line::raw(format!(" {name}"))
+                }
+            })
+            .collect();
+        let title = match self.kiosk {
+            Some(_) => " USERS ",
+            None => " PROFILES ",
+        };
+        let block = Block::default()
+            .title(Span::styled(title, self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.profile_cursor));
+        StatefulWidget::render(list, layout[0], buf, &mut state);
+
+        if let Some(input) = &self.profile_input {
+            let mut spans = vec![Span::raw(" new profile: ")];
+            spans.extend(input.spans(self.theme.value));
+            Paragraph::new(Line::from(spans)).render(layout[1], buf);
+        }
+        let help = if self.profile_input.is_some() {
+            " Enter: create  Esc: discard"
+        } else {
+            " ↑/↓: select  Enter: switch  n: new  Esc: back"
+        };
+        Paragraph::new(help)
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputMode, Screen};
+
+    #[test]
+    fn pick_a_profile() {
+        let mut app = App {
+            profiles: vec!["default".into(), "colemak".into()],
+            ..Default::default()
+        };
+        app.push_screen(Screen::Profiles);
+
+        // the current one just goes back
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.screen, Screen::Typing);
+
+        app.push_screen(Screen::Profiles);
+        app.handle_key_event(KeyCode::Char('n').into()).unwrap();
+        assert_eq!(app.input_mode(), InputMode::Form);
+        for c in "a/b".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(
+            app.status.as_deref(),
+            Some("profile names are made of letters, digits, - and _")
+        );
+        assert_eq!(app.config.profile, "default");
+    }
+}
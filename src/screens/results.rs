@@ -0,0 +1,420 @@
+use chrono::Utc;
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{BarChart, Block, Borders, Paragraph, Widget},
+};
+
+use crate::{
+    anim::Anim,
+    history::HistoryEntry,
+    score::ScoreFormula,
+    stats::{RoundRecord, WordTiming},
+    App, Mode, Screen,
+};
+
+/// Width of a histogram bucket in WPM
+const BUCKET_WIDTH: u32 = 10;
+
+impl App<'_> {
+    pub(crate) fn handle_results_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.exit(),
+            KeyCode::Tab if self.running_plan => self.status = Some(self.run_plan()?),
+            KeyCode::Tab => self.new_session()?,
+            KeyCode::Char('p') => self.push_screen(Screen::Plan),
+            KeyCode::Char('s') => self.push_screen(Screen::Stats),
+            KeyCode::Char('l') => self.push_screen(Screen::Leaderboard),
+            KeyCode::Char('t') => self.push_screen(Screen::Trends),
+            KeyCode::Char('h') => self.push_screen(Screen::History),
+            KeyCode::Char('d') => self.status = Some(self.start_drill()?),
+            // a kiosk leaves no files behind
+            KeyCode::Char('c') if self.kiosk_locks("certificates") => {}
+            KeyCode::Char('c') => self.status = Some(self.write_certificate()?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_results(&self, area: Rect, buf: &mut Buffer) {
+        let lines = self.summary_lines();
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(lines.len() as u16 + 2),
+                // the histogram gives way to the summary on short terminals
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        let summary = self.render_banner(layout[0], buf);
+        self.render_summary(lines, summary, buf);
+        self.render_histogram(layout[1], buf);
+        Paragraph::new(self.footer(layout[2].width))
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+
+    /// the keys of the results that fit in `width`, the least needed are
+    /// left out first
+    fn footer(&self, width: u16) -> String {
+        let next = if self.running_plan {
+            "Tab: next in plan"
+        } else {
+            "Tab: new session"
+        };
+        // in the order shown, with how much each is needed, 0 the most
+        let mut hints = vec![
+            (next, 0),
+            ("s: stats", 2),
+            ("l: leaderboard", 5),
+            ("t: trends", 6),
+            ("h: history", 3),
+            ("p: plan", 7),
+            ("d: drill", 4),
+            ("c: certificate", 8),
+            ("^S: settings", 1),
+            (":: command", 1),
+            ("Esc: quit", 0),
+        ];
+        let text = |hints: &[(&str, u8)]| {
+            let keys: Vec<&str> = hints.iter().map(|(hint, _)| *hint).collect();
+            format!(" {}", keys.join("  "))
+        };
+        while text(&hints).chars().count() > usize::from(width) {
+            let Some(least) = hints
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, need))| *need > 0)
+                .max_by_key(|(_, (_, need))| *need)
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+            hints.remove(least);
+        }
+        text(&hints)
+    }
+
+    /// the lines of the summary, the most important first
+    fn summary_lines(&self) -> Vec<Line<'_>> {
+        let stats = &self.stats;
+        let value = |v: String| Span::styled(v, self.theme.value);
+        let word = |label: &'static str, word: Option<&WordTiming>| {
+            let text = word
+                .map(|w| format!("{} ({:.0} WPM)", w.word, w.wpm()))
+                .unwrap_or_else(|| "-".into());
+            Line::from(vec![Span::raw(label), value(text)])
+        };
+
+        let range = |wpm: fn(&RoundRecord) -> f64| {
+            let wpm = stats.rounds.iter().map(wpm);
+            match (
+                wpm.clone().min_by(f64::total_cmp),
+                wpm.max_by(f64::total_cmp),
+            ) {
+                (Some(min), Some(max)) => format!("{min:.0} - {max:.0}"),
+                _ => "-".into(),
+            }
+        };
+
+        let formula = self.config.score.formula;
+        let entry = HistoryEntry::new(stats, self.mode, Utc::now());
+        let score = entry.score(formula);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::raw("rounds: "),
+                value(stats.rounds.len().to_string()),
+                Span::raw("  wins: "),
+                value(self.wins.to_string()),
+                Span::raw("  fails: "),
+                value(self.fails.to_string()),
+            ]),
+            Line::from(vec![
+                Span::raw("speed: "),
+                value(format!("{:.1} WPM", stats.wpm())),
+                Span::raw("  accuracy: "),
+                value(format!("{:.1}%", stats.accuracy() * 100.0)),
+                Span::raw("  time: "),
+                value(format!("{:.1}s", stats.duration().as_secs_f64())),
+            ]),
+            Line::from(vec![
+                Span::raw("raw: "),
+                value(format!("{:.1} WPM", entry.raw_wpm)),
+                Span::raw("  net: "),
+                value(format!("{:.1} WPM", entry.output_wpm())),
+                Span::raw("  consistency: "),
+                value(format!("{:.0}%", entry.consistency)),
+            ]),
+            Line::from(vec![
+                Span::raw("errors: "),
+                value(entry.corrected().to_string()),
+                Span::raw(" corrected, "),
+                value(entry.uncorrected.to_string()),
+                Span::raw(" uncorrected"),
+            ]),
+            Line::from(vec![
+                Span::raw("normalized: "),
+                value(format!("{:.1} WPM", stats.normalized_wpm())),
+            ]),
+            Line::from(vec![
+                Span::raw("round speed: "),
+                value(format!("{} WPM", range(RoundRecord::wpm))),
+                Span::raw("  normalized: "),
+                value(format!("{} WPM", range(RoundRecord::normalized_wpm))),
+            ]),
+            Line::from(vec![
+                Span::raw("skill score: "),
+                value(format!("{:.1}", score)),
+                Span::raw(format!(" ({formula})")),
+            ]),
+            word("fastest word: ", stats.fastest_word()),
+            word("slowest word: ", stats.slowest_word()),
+            self.class_accuracy_line(&stats.classes()),
+            self.latency_line(&stats.latencies()),
+            self.bigram_line(&stats.bigrams()),
+        ];
+
+        // what transcription work is judged by comes first
+        if formula == ScoreFormula::Transcription {
+            lines.insert(
+                0,
+                Line::from(vec![
+                    Span::styled("errors per 100 words: ", self.theme.title),
+                    value(format!("{:.1}", entry.errors_per_100_words())),
+                    Span::raw("  output: "),
+                    value(format!("{:.1} WPM", entry.output_wpm())),
+                    Span::raw("  uncorrected: "),
+                    value(entry.uncorrected.to_string()),
+                ]),
+            );
+        }
+
+        // and a personal best before it
+        let best = self
+            .personal_best
+            .map(|best| format!("new personal best: {:.1} WPM, up from {best:.1}", entry.wpm));
+        if let Some(best) = best {
+            let line = if self.config.effects.celebrate {
+                Line::from(self.celebrated(&best, self.theme.value))
+            } else {
+                Line::styled(best, self.theme.value)
+            };
+            lines.insert(0, line);
+        }
+
+        let languages = self.words.tallies(&stats.rounds);
+        if self.mode.uses_words() && !languages.is_empty() {
+            let mut line = vec![Span::raw("accuracy by language:")];
+            for (name, tally) in &languages {
+                line.push(Span::raw(format!(" {name} ")));
+                line.push(value(format!("{:.1}%", tally.accuracy() * 100.0)));
+            }
+            lines.push(Line::from(line));
+        }
+
+        if let (Some(mean), Some(longest)) = (self.holds.mean(), self.holds.longest()) {
+            lines.push(Line::from(vec![
+                Span::raw("key holds: "),
+                value(format!("{}ms", mean.as_millis())),
+                Span::raw(format!(
+                    " average, {}ms longest, {} repeats not typed",
+                    longest.as_millis(),
+                    self.holds.repeats()
+                )),
+            ]));
+        }
+
+        let pace = self.config.session.pace_wpm;
+        if pace > 0 && !stats.rounds.is_empty() {
+            let lead = stats.pace_lead(pace.into());
+            let (ahead, verb) = if lead >= 0.0 {
+                (lead, "ahead of")
+            } else {
+                (-lead, "behind")
+            };
+            lines.push(Line::from(vec![
+                Span::raw("pace: "),
+                value(format!("{:+.1} WPM", stats.wpm() - f64::from(pace))),
+                Span::raw(format!(", {ahead:.1}s {verb} {pace} WPM")),
+            ]));
+        }
+
+        if let (Mode::Race(_), Some(round)) = (self.mode, stats.rounds.last()) {
+            let racers = self.race.bots.len() + 1;
+            let mut line = vec![
+                Span::raw("race: place "),
+                value(format!("{} of {racers}", self.race.place(round.duration))),
+            ];
+            if let Some(bot) = self.race.fastest_bot() {
+                line.push(Span::raw(format!(
+                    "  fastest bot: {} ({:.1}s)",
+                    bot.name,
+                    bot.finish_time().as_secs_f64()
+                )));
+            }
+            lines.insert(0, Line::from(line));
+        }
+
+        // how well the phrases were translated, apart from how fast
+        if self.mode == Mode::Translate {
+            let right = stats.rounds.iter().filter(|round| !round.missed).count();
+            lines.insert(
+                0,
+                Line::from(vec![
+                    Span::raw("translation: "),
+                    value(format!("{right} of {}", stats.rounds.len())),
+                    Span::raw(" phrases without a miss, accuracy "),
+                    value(format!("{:.1}%", stats.accuracy() * 100.0)),
+                ]),
+            );
+        }
+
+        // why the session isn't in the history matters most
+        if let Some(flag) = self.guard.flag() {
+            lines.insert(
+                0,
+                Line::from(Span::styled(
+                    format!("not recorded: {flag}"),
+                    self.theme.miss,
+                )),
+            );
+        }
+        lines
+    }
+
+    fn render_summary(&self, lines: Vec<Line>, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(Span::styled(" RESULTS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    fn render_histogram(&self, area: Rect, buf: &mut Buffer) {
+        let histogram = self.stats.word_histogram(BUCKET_WIDTH);
+        // the bars grow to their height
+        let reveal = self.animated(Anim::Reveal, 1.0);
+        let data: Vec<(&str, u64)> = histogram
+            .iter()
+            .map(|(l, c)| (l.as_str(), (*c as f64 * reveal).round() as u64))
+            .collect();
+        let max = histogram.iter().map(|(_, c)| *c).max().unwrap_or_default();
+
+        let block = Block::default()
+            .title(Span::styled(" WORDS PER WPM ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        BarChart::default()
+            .block(block)
+            .data(&data)
+            .max(max)
+            .bar_width(4)
+            .bar_style(self.theme.hit)
+            .value_style(self.theme.value)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::words::WordList;
+
+    /// the rows of `app` drawn on a terminal of `width` x `height`
+    fn draw(app: &App, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        app.render_results(area, &mut buf);
+        (0..height)
+            .map(|y| (0..width).map(|x| buf.get(x, y).symbol()).collect())
+            .collect()
+    }
+
+    /// an app with a round of "abc" typed
+    fn typed() -> App<'static> {
+        let mut app = App::default().with_text("abc");
+        app.next_round().unwrap();
+        for c in "abc".chars() {
+            app.handle_key_event(KeyCode::Char(c).into()).unwrap();
+        }
+        app
+    }
+
+    fn mix_languages(app: &mut App) {
+        app.mode = Mode::Words(10);
+        app.words =
+            WordList::from_text("english", "abc").mixed(WordList::from_text("german", "xyz"), 0.5);
+    }
+
+    #[test]
+    fn fit_every_line_of_the_summary() {
+        let mut app = typed();
+        app.guard.paste();
+        app.finish_session(Instant::now()).unwrap();
+        app.personal_best = Some(1.0);
+        app.config.score.formula = ScoreFormula::Transcription;
+
+        let rows = draw(&app, 80, 30);
+        let row = |text: &str| rows.iter().position(|row| row.contains(text));
+        assert_eq!(row("not recorded"), Some(2));
+        assert!(row("new personal best") < row("rounds:"));
+        let last = row("slowest transitions").unwrap();
+        assert!(rows[last + 1].contains('└'));
+    }
+
+    #[test]
+    fn show_the_languages_on_a_small_terminal() {
+        let mut app = typed();
+        app.finish_session(Instant::now()).unwrap();
+        app.personal_best = Some(1.0);
+        app.config.score.formula = ScoreFormula::Transcription;
+        mix_languages(&mut app);
+
+        let rows = draw(&app, 80, 24);
+        let row = rows
+            .iter()
+            .position(|row| row.contains("accuracy by language: english 100.0%"))
+            .unwrap();
+        assert!(rows[row + 1].contains('└'));
+    }
+
+    #[test]
+    fn leave_out_the_least_needed_keys() {
+        let app = App::default();
+        let footer = app.footer(80);
+        assert!(footer.chars().count() <= 80);
+        assert!(footer.starts_with(" Tab: new session  s: stats"));
+        assert!(footer.ends_with("^S: settings  :: command  Esc: quit"));
+        assert!(!footer.contains("certificate"));
+        assert!(app.footer(200).contains("c: certificate"));
+        assert_eq!(app.footer(10), " Tab: new session  Esc: quit");
+    }
+
+    #[test]
+    fn show_the_key_holds_after_the_other_lines() {
+        let now = Instant::now();
+        let mut app = typed();
+        app.holds.press(KeyCode::Char('a'), now);
+        app.holds
+            .release(KeyCode::Char('a'), now + Duration::from_millis(90));
+        app.finish_session(now).unwrap();
+        app.config.session.pace_wpm = 40;
+        mix_languages(&mut app);
+
+        let rows = draw(&app, 80, 24);
+        let row = |text: &str| rows.iter().position(|row| row.contains(text));
+        assert!(row("accuracy by language").is_some());
+        assert!(row("pace: ").is_some());
+        let holds = row("key holds: 90ms average").unwrap();
+        assert!(rows[holds + 2].contains('└'));
+    }
+}
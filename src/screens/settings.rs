@@ -0,0 +1,383 @@
+use std::rc::Rc;
+
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::{
+    config::Config,
+    form::{Field, FieldEvent, NumberInput, TextInput},
+    profile::{self, MAX_PROFILE_LEN},
+    App, Screen,
+};
+
+/// Longest countdown before a timed session in seconds
+const MAX_COUNTDOWN: u32 = 10;
+/// Fastest pace caret in WPM
+const MAX_PACE: u32 = 300;
+/// Most rounds per session
+const MAX_ROUNDS: u32 = 99;
+
+/// A setting that can be changed from the settings screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Setting {
+    Practice,
+    Ghost,
+    Header,
+    Clock,
+    SkipOnSpace,
+    ReviewMisses,
+    Numpad,
+    Symbols,
+    ReducedMotion,
+    Profile,
+    Countdown,
+    Pace,
+    Rounds,
+}
+
+const SETTINGS: [Setting; 13] = [
+    Setting::Practice,
+    Setting::Ghost,
+    Setting::Header,
+    Setting::Clock,
+    Setting::SkipOnSpace,
+    Setting::ReviewMisses,
+    Setting::Numpad,
+    Setting::Symbols,
+    Setting::ReducedMotion,
+    Setting::Profile,
+    Setting::Countdown,
+    Setting::Pace,
+    Setting::Rounds,
+];
+
+impl Setting {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Practice => "practice mode (misses don't count, nothing is recorded)",
+            Self::Ghost => "race against a ghost of your recent sessions",
+            Self::Header => "show the header",
+            Self::Clock => "show the clock in the header",
+            Self::SkipOnSpace => "space skips the rest of the word",
+            Self::ReviewMisses => "show where the misses were after each round",
+            Self::Numpad => "digits are typed on the numpad (if the terminal can't tell)",
+            Self::Symbols => "show ✓ and ✗ next to wins, fails and the text",
+            Self::ReducedMotion => "reduce motion (no caret glide, shaking or growing charts)",
+            Self::Profile => "profile",
+            Self::Countdown => "countdown before timed sessions (seconds)",
+            Self::Pace => "pace caret (WPM, 0 = off)",
+            Self::Rounds => "rounds per session (0 = until Enter)",
+        }
+    }
+
+    /// the value of an on/off setting, `None` for the ones edited in a field
+    fn value(self, config: &Config) -> Option<bool> {
+        match self {
+            Self::Practice => Some(config.practice),
+            Self::Ghost => Some(config.race.ghost),
+            Self::Header => Some(config.header.show),
+            Self::Clock => Some(config.header.show_clock),
+            Self::SkipOnSpace => Some(config.session.skip_on_space),
+            Self::ReviewMisses => Some(config.session.review_misses),
+            Self::Numpad => Some(config.session.numpad),
+            Self::Symbols => Some(config.accessibility.symbols),
+            Self::ReducedMotion => Some(config.accessibility.reduced_motion),
+            Self::Profile | Self::Countdown | Self::Pace | Self::Rounds => None,
+        }
+    }
+
+    /// a field to edit the setting in, `None` for on/off settings
+    fn field(self, config: &Config) -> Option<Field> {
+        match self {
+            Self::Profile => Some(Field::Text(
+                TextInput::new(&config.profile).max_len(MAX_PROFILE_LEN),
+            )),
+            Self::Countdown => Some(Field::Number(NumberInput::new(
+                config.session.countdown.into(),
+                0,
+                MAX_COUNTDOWN,
+            ))),
+            Self::Pace => Some(Field::Number(NumberInput::new(
+                config.session.pace_wpm.into(),
+                0,
+                MAX_PACE,
+            ))),
+            Self::Rounds => Some(Field::Number(NumberInput::new(
+                config.session.rounds.into(),
+                0,
+                MAX_ROUNDS,
+            ))),
+            _ => None,
+        }
+    }
+
+    /// the value of a field setting as shown when it's not edited
+    fn text(self, config: &Config) -> String {
+        match self {
+            Self::Profile => config.profile.clone(),
+            Self::Countdown => config.session.countdown.to_string(),
+            Self::Pace => config.session.pace_wpm.to_string(),
+            Self::Rounds => config.session.rounds.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    fn toggle(self, config: &mut Config) {
+        let value = match self {
+            Self::Practice => &mut config.practice,
+            Self::Ghost => &mut config.race.ghost,
+            Self::Header => &mut config.header.show,
+            Self::Clock => &mut config.header.show_clock,
+            Self::SkipOnSpace => &mut config.session.skip_on_space,
+            Self::ReviewMisses => &mut config.session.review_misses,
+            Self::Numpad => &mut config.session.numpad,
+            Self::Symbols => &mut config.accessibility.symbols,
+            Self::ReducedMotion => &mut config.accessibility.reduced_motion,
+            Self::Profile | Self::Countdown | Self::Pace | Self::Rounds => return,
+        };
+        *value = !*value;
+    }
+
+    /// stores the value of `field` or tells why it can't be stored
+    fn apply(self, field: &Field, config: &mut Config) -> Result<(), String> {
+        match (self, field) {
+            // switched to by the app once the name is valid
+            (Self::Profile, Field::Text(input)) => profile::validate(input.value().trim())?,
+            (Self::Countdown, Field::Number(input)) => {
+                config.session.countdown = input.value()? as u8;
+            }
+            (Self::Pace, Field::Number(input)) => {
+                config.session.pace_wpm = input.value()? as u16;
+            }
+            (Self::Rounds, Field::Number(input)) => {
+                config.session.rounds = input.value()? as u8;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl App<'_> {
+    pub(crate) fn open_settings(&mut self) {
+        if self.kiosk_locks("settings") {
+            return;
+        }
+        self.push_screen(Screen::Settings);
+    }
+
+    /// goes back to the previous screen and saves the changes
+    fn close_settings(&mut self) -> Result<()> {
+        self.pop_screen();
+        self.save_config()
+    }
+
+    pub(crate) fn save_config(&self) -> Result<()> {
+        match &self.config_path {
+            Some(path) => self
+                .config
+                .save(path)
+                .wrap_err("saving the settings failed"),
+            None => Ok(()),
+        }
+    }
+
+    /// toggles the selected setting or starts editing it
+    fn activate_setting(&mut self) {
+        let setting = SETTINGS[self.settings_cursor];
+        self.settings_error = None;
+        match setting.field(&self.config) {
+            Some(field) => self.settings_field = Some(field),
+            None => setting.toggle(&mut self.config),
+        }
+    }
+
+    fn handle_settings_field_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        let Some(field) = &mut self.settings_field else {
+            return Ok(());
+        };
+        match field.handle_key(key_event) {
+            FieldEvent::Submit => {
+                let setting = SETTINGS[self.settings_cursor];
+                match setting.apply(field, &mut self.config) {
+                    Ok(()) => {
+                        let field = self.settings_field.take();
+                        if let (Setting::Profile, Some(Field::Text(input))) = (setting, field) {
+                            let name = input.value().trim();
+                            if name != self.config.profile {
+                                self.status = Some(self.switch_profile(name)?);
+                            }
+                        }
+                    }
+                    Err(error) => self.settings_error = Some(error),
+                }
+            }
+            FieldEvent::Cancel => {
+                self.settings_field = None;
+                self.settings_error = None;
+            }
+            FieldEvent::Edited | FieldEvent::Ignored => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn handle_settings_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if self.settings_field.is_some() {
+            return self.handle_settings_field_key(key_event);
+        }
+        match key_event.code {
+            KeyCode::Esc => self.close_settings()?,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.settings_cursor = self.settings_cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.settings_cursor = (self.settings_cursor + 1).min(SETTINGS.len() - 1);
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => self.activate_setting(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// activates the setting at the clicked row of the settings screen in
+    /// `area`
+    pub(crate) fn click_settings(&mut self, area: Rect, click: Position) -> Result<()> {
+        let rows = Self::settings_layout(area)[0].inner(Margin::new(1, 1));
+        if rows.contains(click) && self.settings_field.is_none() {
+            let row = (click.y - rows.y) as usize;
+            if row < SETTINGS.len() {
+                self.settings_cursor = row;
+                self.activate_setting();
+            }
+        }
+        Ok(())
+    }
+
+    fn settings_layout(area: Rect) -> Rc<[Rect]> {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(SETTINGS.len() as u16 + 2),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area)
+    }
+
+    pub(crate) fn render_settings(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Self::settings_layout(area);
+        let lines: Vec<Line> = SETTINGS
+            .iter()
+            .enumerate()
+            .map(|(i, setting)| {
+                let selected = i == self.settings_cursor;
+                let style = if selected && self.settings_field.is_none() {
+                    Style::new().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::new()
+                };
+                let Some(on) = setting.value(&self.config) else {
+                    let mut spans = vec![
+                        Span::raw("    "),
+                        Span::styled(setting.label(), style),
+                        Span::raw(": "),
+                    ];
+                    match &self.settings_field {
+                        Some(field) if selected => spans.extend(field.spans(self.theme.value)),
+                        _ => spans.push(Span::styled(setting.text(&self.config), self.theme.value)),
+                    }
+                    return Line::from(spans);
+                };
+                let check = if on { "[x]" } else { "[ ]" };
+                Line::from(vec![
+                    Span::styled(check, self.theme.value),
+                    Span::raw(" "),
+                    Span::styled(setting.label(), style),
+                ])
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(Span::styled(" SETTINGS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(layout[0], buf);
+        if let Some(error) = &self.settings_error {
+            Paragraph::new(format!(" {error}"))
+                .style(self.theme.miss)
+                .render(layout[1], buf);
+        }
+        let help = if self.settings_field.is_some() {
+            " Enter: keep  Esc: discard"
+        } else {
+            " ↑/↓: select  Space: change  Esc: save and back"
+        };
+        Paragraph::new(help)
+            .style(self.theme.title)
+            .render(layout[3], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::InputMode;
+
+    #[test]
+    fn toggle_settings() {
+        let mut app = App::default();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(app.screen, Screen::Settings);
+
+        app.handle_key_event(KeyCode::Char(' ').into()).unwrap();
+        assert!(app.config.practice);
+
+        app.handle_key_event(KeyCode::Down.into()).unwrap();
+        app.handle_key_event(KeyCode::Down.into()).unwrap();
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(!app.config.header.show);
+
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(app.screen, Screen::Typing);
+    }
+
+    #[test]
+    fn edit_fields() {
+        let mut app = App::default();
+        app.open_settings();
+        app.settings_cursor = SETTINGS.len() - 3;
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.input_mode(), InputMode::Form);
+
+        // j and k are text now, not navigation
+        for key in [KeyCode::Backspace, KeyCode::Char('k'), KeyCode::Char('7')] {
+            app.handle_key_event(key.into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert_eq!(app.config.session.countdown, 7);
+        assert_eq!(app.input_mode(), InputMode::Menu);
+
+        app.handle_key_event(KeyCode::Up.into()).unwrap();
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        for _ in 0..10 {
+            app.handle_key_event(KeyCode::Backspace.into()).unwrap();
+        }
+        app.handle_key_event(KeyCode::Enter.into()).unwrap();
+        assert!(app.settings_error.is_some());
+        app.handle_key_event(KeyCode::Esc.into()).unwrap();
+        assert_eq!(app.config.profile, "default");
+        assert_eq!(app.screen, Screen::Settings);
+    }
+}
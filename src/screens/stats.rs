@@ -0,0 +1,187 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget},
+};
+
+use crate::{
+    score::ScoreFormula,
+    stats::{BigramLatencies, ClassTallies, KeyLatencies},
+    App, Screen,
+};
+
+/// Number of keys shown as the slowest
+const SLOWEST_KEYS: usize = 5;
+/// Number of transitions between keys shown as the slowest
+const SLOWEST_BIGRAMS: usize = 5;
+/// Presses of a key before its latency is shown
+pub(super) const MIN_PRESSES: usize = 3;
+
+impl App<'_> {
+    pub(crate) fn handle_stats_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Char('b') => self.push_screen(Screen::Badges),
+            KeyCode::Char('f') => {
+                let formulas = ScoreFormula::ALL;
+                let current = formulas
+                    .iter()
+                    .position(|f| *f == self.config.score.formula)
+                    .unwrap_or_default();
+                self.config.score.formula = formulas[(current + 1) % formulas.len()];
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_stats(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(7),
+                Constraint::Min(5),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        let formula = self.config.score.formula;
+        let scores: Vec<(f64, f64)> = self
+            .history
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i as f64, e.score(formula)))
+            .collect();
+
+        self.render_skill_summary(layout[0], buf, &scores);
+        self.render_skill_chart(layout[1], buf, &scores);
+        Paragraph::new(" f: change formula  b: badges  Esc: back")
+            .style(self.theme.title)
+            .render(layout[2], buf);
+    }
+
+    /// accuracy of every class of characters that was typed
+    pub(crate) fn class_accuracy_line(&self, classes: &ClassTallies) -> Line<'static> {
+        let mut spans = vec![Span::raw("accuracy by class:")];
+        for (class, tally) in classes.iter() {
+            spans.push(Span::raw(format!(" {class} ")));
+            spans.push(Span::styled(
+                format!("{:.1}%", tally.accuracy() * 100.0),
+                self.theme.value,
+            ));
+        }
+        if spans.len() == 1 {
+            spans.push(Span::raw(" -"));
+        }
+        Line::from(spans)
+    }
+
+    /// the keys that take longest to press with their average and 90th
+    /// percentile latency
+    pub(crate) fn latency_line(&self, latencies: &KeyLatencies) -> Line<'static> {
+        let mut spans = vec![Span::raw("slowest keys:")];
+        for key in latencies.slowest(SLOWEST_KEYS, MIN_PRESSES) {
+            let name = match key.key {
+                ' ' => "space".to_string(),
+                c => c.to_string(),
+            };
+            spans.push(Span::raw(format!(" {name} ")));
+            spans.push(Span::styled(
+                format!("{:.0}ms", key.mean_ms),
+                self.theme.value,
+            ));
+            spans.push(Span::raw(format!(" (p90 {}ms)", key.p90_ms)));
+        }
+        if spans.len() == 1 {
+            spans.push(Span::raw(" -"));
+        }
+        Line::from(spans)
+    }
+
+    /// the pairs of keys within words whose second key takes longest to
+    /// press after the first
+    pub(crate) fn bigram_line(&self, bigrams: &BigramLatencies) -> Line<'static> {
+        let mut spans = vec![Span::raw("slowest transitions:")];
+        for bigram in bigrams.slowest(SLOWEST_BIGRAMS, MIN_PRESSES) {
+            spans.push(Span::raw(format!(" {} ", bigram.key)));
+            spans.push(Span::styled(
+                format!("{:.0}ms", bigram.mean_ms),
+                self.theme.value,
+            ));
+        }
+        if spans.len() == 1 {
+            spans.push(Span::raw(" -"));
+        }
+        Line::from(spans)
+    }
+
+    fn render_skill_summary(&self, area: Rect, buf: &mut Buffer, scores: &[(f64, f64)]) {
+        let value = |v: String| Span::styled(v, self.theme.value);
+        let best = scores.iter().map(|(_, s)| *s).max_by(f64::total_cmp);
+        let latest = scores.last().map(|(_, s)| *s);
+        let fmt = |s: Option<f64>| s.map(|s| format!("{s:.1}")).unwrap_or_else(|| "-".into());
+
+        let lines = vec![
+            Line::from(vec![
+                Span::raw("skill score: "),
+                value(fmt(latest)),
+                Span::raw("  best: "),
+                value(fmt(best)),
+                Span::raw("  sessions: "),
+                value(scores.len().to_string()),
+            ]),
+            Line::from(vec![
+                Span::raw("formula: "),
+                value(self.config.score.formula.to_string()),
+            ]),
+            self.class_accuracy_line(&self.history.classes()),
+            self.latency_line(&self.history.latencies()),
+            self.bigram_line(&self.history.bigrams()),
+        ];
+
+        let block = Block::default()
+            .title(Span::styled(" PROGRESS ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    fn render_skill_chart(&self, area: Rect, buf: &mut Buffer, scores: &[(f64, f64)]) {
+        let max_x = (scores.len().max(2) - 1) as f64;
+        let max_y = scores.iter().map(|(_, s)| *s).fold(10.0, f64::max).ceil();
+
+        let dataset = Dataset::default()
+            .name("skill score")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(self.theme.hit)
+            .data(scores);
+        let block = Block::default()
+            .title(Span::styled(" SKILL SCORE OVER TIME ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+
+        Chart::new(vec![dataset])
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .title("session")
+                    .style(self.theme.border)
+                    .bounds([0.0, max_x])
+                    .labels(vec!["1".into(), format!("{}", max_x as usize + 1).into()]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(self.theme.border)
+                    .bounds([0.0, max_y])
+                    .labels(vec!["0".into(), format!("{max_y:.0}").into()]),
+            )
+            .render(area, buf);
+    }
+}
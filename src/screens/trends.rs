@@ -0,0 +1,175 @@
+use color_eyre::Result;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Direction, Layout, Rect},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget,
+    },
+};
+
+use crate::{
+    trends::{self, Trend},
+    App,
+};
+
+impl App<'_> {
+    pub(crate) fn handle_trends_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Esc => self.pop_screen(),
+            KeyCode::Char('p') => self.trend_period = self.trend_period.next(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(crate) fn render_trends(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(1),
+                Constraint::Min(6),
+                Constraint::Length(8),
+                Constraint::Length(1),
+            ])
+            .margin(1)
+            .split(area);
+
+        let trends = trends::trends(
+            self.history.entries(),
+            self.trend_period,
+            self.config.timezone,
+        );
+        let value = |v: String| Span::styled(v, self.theme.value);
+        let minutes: f64 = trends.iter().map(Trend::minutes).sum();
+        Paragraph::new(Line::from(vec![
+            value(self.trend_period.to_string()),
+            Span::raw("  sessions: "),
+            value(trends.iter().map(|t| t.sessions).sum::<usize>().to_string()),
+            Span::raw("  practiced: "),
+            value(format!("{minutes:.0} min")),
+        ]))
+        .render(layout[0], buf);
+
+        self.render_trend_chart(layout[1], buf, &trends);
+        self.render_practice_time(layout[2], buf, &trends);
+        Paragraph::new(" p: weekly/monthly  Esc: back")
+            .style(self.theme.title)
+            .render(layout[3], buf);
+    }
+
+    fn render_trend_chart(&self, area: Rect, buf: &mut Buffer, trends: &[Trend]) {
+        let points = |f: fn(&Trend) -> f64| -> Vec<(f64, f64)> {
+            trends
+                .iter()
+                .enumerate()
+                .map(|(i, t)| (i as f64, f(t)))
+                .collect()
+        };
+        let recorded = |f: fn(&Trend) -> Option<f64>| -> Vec<(f64, f64)> {
+            trends
+                .iter()
+                .enumerate()
+                .filter_map(|(i, t)| Some((i as f64, f(t)?)))
+                .collect()
+        };
+        let wpm = points(|t| t.wpm);
+        let accuracy = points(|t| t.accuracy * 100.0);
+        let raw_wpm = recorded(|t| t.raw_wpm);
+        let consistency = recorded(|t| t.consistency);
+        let max_x = (trends.len().max(2) - 1) as f64;
+        let max_y = wpm
+            .iter()
+            .chain(&raw_wpm)
+            .map(|(_, y)| *y)
+            .fold(100.0, f64::max)
+            .ceil();
+        let label = |i: usize| {
+            trends
+                .get(i)
+                .map(|t| self.trend_period.label(t.start))
+                .unwrap_or_default()
+        };
+
+        let datasets = vec![
+            Dataset::default()
+                .name("WPM")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(self.theme.hit)
+                .data(&wpm),
+            Dataset::default()
+                .name("accuracy %")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(self.theme.value)
+                .data(&accuracy),
+            Dataset::default()
+                .name("raw WPM")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(self.theme.pending)
+                .data(&raw_wpm),
+            Dataset::default()
+                .name("consistency %")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(self.theme.border)
+                .data(&consistency),
+        ];
+        let block = Block::default()
+            .title(Span::styled(" SPEED AND ACCURACY ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+
+        Chart::new(datasets)
+            .block(block)
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+            .x_axis(
+                Axis::default()
+                    .style(self.theme.border)
+                    .bounds([0.0, max_x])
+                    .labels(vec![
+                        label(0).into(),
+                        label(trends.len().saturating_sub(1)).into(),
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(self.theme.border)
+                    .bounds([0.0, max_y])
+                    .labels(vec!["0".into(), format!("{max_y:.0}").into()]),
+            )
+            .render(area, buf);
+    }
+
+    fn render_practice_time(&self, area: Rect, buf: &mut Buffer, trends: &[Trend]) {
+        let bars: Vec<Bar> = trends
+            .iter()
+            .map(|t| {
+                Bar::default()
+                    .value(t.minutes().round() as u64)
+                    .label(self.trend_period.label(t.start).into())
+            })
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(" MINUTES PRACTICED ", self.theme.title))
+            .borders(Borders::ALL)
+            .border_style(self.theme.border);
+
+        // the latest periods if not all of them fit
+        let width = 5;
+        let fit = (area.width.saturating_sub(2) / (width + 1)) as usize;
+        let skip = bars.len().saturating_sub(fit);
+        BarChart::default()
+            .block(block)
+            .bar_width(width)
+            .bar_gap(1)
+            .bar_style(self.theme.hit)
+            .value_style(self.theme.value)
+            .data(BarGroup::default().bars(&bars[skip..]))
+            .render(area, buf);
+    }
+}
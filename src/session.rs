@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Where the current session is at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionState {
+    /// waiting for the user to start a timed session
+    Ready,
+    /// counting down, typing starts at `until`
+    Countdown { until: Instant },
+    /// typing, `started` is set once the clock runs
+    Running { started: Option<Instant> },
+    /// the session is over and its results are shown
+    Finished,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self::Running { started: None }
+    }
+}
+
+impl SessionState {
+    /// whole seconds left in the countdown, rounded up
+    pub fn countdown_left(&self, now: Instant) -> Option<u64> {
+        match self {
+            Self::Countdown { until } => {
+                let left = until.saturating_duration_since(now);
+                Some(left.as_millis().div_ceil(1000) as u64)
+            }
+            _ => None,
+        }
+    }
+
+    /// time since the clock started, `None` before it did
+    pub fn elapsed(&self, now: Instant) -> Option<Duration> {
+        match self {
+            Self::Running {
+                started: Some(started),
+            } => Some(now.duration_since(*started)),
+            _ => None,
+        }
+    }
+
+    /// time left of a session limited to `limit`, the full limit until the
+    /// clock starts
+    pub fn time_left(&self, limit: Duration, now: Instant) -> Duration {
+        match self {
+            Self::Running {
+                started: Some(started),
+            } => limit.saturating_sub(now.duration_since(*started)),
+            Self::Finished => Duration::ZERO,
+            _ => limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// seconds to count down before a timed session, 0 to start right away
+    pub countdown: u8,
+    /// start the clock on the first keypress instead of the end of the
+    /// countdown, so the reaction time doesn't count
+    pub start_on_keypress: bool,
+    /// speed of the pace caret in WPM, 0 hides it
+    pub pace_wpm: u16,
+    /// Space in the middle of a word skips to the next one in word based
+    /// modes, the letters left out count as misses
+    pub skip_on_space: bool,
+    /// rounds after which the results are shown, 0 to go on until Enter.
+    /// Timed and race sessions have their own end.
+    pub rounds: u8,
+    /// after a round with misses, show where they were until a key is
+    /// pressed. Not in timed and race sessions, their clock keeps running.
+    pub review_misses: bool,
+    /// count every digit as typed on the numeric keypad, for terminals that
+    /// don't tell its keys from the number row
+    pub numpad: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            countdown: 3,
+            start_on_keypress: true,
+            pace_wpm: 0,
+            skip_on_space: false,
+            rounds: 0,
+            review_misses: false,
+            numpad: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown_and_time_left() {
+        let now = Instant::now();
+        let countdown = SessionState::Countdown {
+            until: now + Duration::from_millis(2500),
+        };
+        assert_eq!(countdown.countdown_left(now), Some(3));
+        assert_eq!(
+            countdown.countdown_left(now + Duration::from_secs(2)),
+            Some(1)
+        );
+        assert_eq!(
+            countdown.countdown_left(now + Duration::from_secs(3)),
+            Some(0)
+        );
+
+        let limit = Duration::from_secs(30);
+        assert_eq!(SessionState::default().time_left(limit, now), limit);
+        assert_eq!(SessionState::default().elapsed(now), None);
+        let running = SessionState::Running { started: Some(now) };
+        assert_eq!(
+            running.time_left(limit, now + Duration::from_secs(10)),
+            Duration::from_secs(20)
+        );
+        assert_eq!(
+            running.elapsed(now + Duration::from_secs(10)),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            running.time_left(limit, now + Duration::from_secs(40)),
+            Duration::ZERO
+        );
+    }
+}
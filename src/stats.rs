@@ -0,0 +1,509 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Typing speed in words per minute, counting five characters as a word
+pub fn wpm(chars: usize, duration: Duration) -> f64 {
+    let minutes = duration.as_secs_f64() / 60.0;
+    if minutes == 0.0 {
+        return 0.0;
+    }
+    chars as f64 / 5.0 / minutes
+}
+
+/// Share of correct keystrokes, 1.0 if nothing was typed yet
+pub fn accuracy(chars: usize, misses: usize) -> f64 {
+    if chars + misses == 0 {
+        return 1.0;
+    }
+    chars as f64 / (chars + misses) as f64
+}
+
+/// How even the time between keystrokes is in percent, 100 for a metronome.
+/// Maps the coefficient of variation of `intervals` like monkeytype does,
+/// 100 with fewer than two of them.
+pub fn consistency(intervals: &[f64]) -> f64 {
+    if intervals.len() < 2 {
+        return 100.0;
+    }
+    let n = intervals.len() as f64;
+    let mean = intervals.iter().sum::<f64>() / n;
+    if mean == 0.0 {
+        return 100.0;
+    }
+    let variance = intervals.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let cv = variance.sqrt() / mean;
+    100.0 * (1.0 - (cv + cv.powi(3) / 3.0 + cv.powi(5) / 5.0).tanh())
+}
+
+/// Kinds of characters whose accuracy is tracked separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CharClass {
+    Letter,
+    Digit,
+    /// digits typed on the numeric keypad
+    Numpad,
+    Symbol,
+}
+
+impl CharClass {
+    /// the class of `c`, `None` for whitespace
+    pub fn of(c: char) -> Option<Self> {
+        match c {
+            c if c.is_whitespace() => None,
+            c if c.is_ascii_digit() => Some(Self::Digit),
+            c if c.is_alphabetic() => Some(Self::Letter),
+            _ => Some(Self::Symbol),
+        }
+    }
+
+    /// the class of `c` typed on the numeric keypad if `keypad`, so 10-key
+    /// practice is told apart from the number row
+    pub fn of_key(c: char, keypad: bool) -> Option<Self> {
+        match Self::of(c) {
+            Some(Self::Digit) if keypad => Some(Self::Numpad),
+            class => class,
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Letter => write!(f, "letters"),
+            Self::Digit => write!(f, "digits"),
+            Self::Numpad => write!(f, "numpad"),
+            Self::Symbol => write!(f, "symbols"),
+        }
+    }
+}
+
+/// Correct keystrokes and misses of one class of characters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tally {
+    pub chars: usize,
+    pub misses: usize,
+}
+
+impl Tally {
+    pub fn accuracy(&self) -> f64 {
+        accuracy(self.chars, self.misses)
+    }
+}
+
+/// Keystrokes per [`CharClass`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassTallies(BTreeMap<CharClass, Tally>);
+
+impl ClassTallies {
+    /// counts `c` as typed correctly
+    pub fn hit(&mut self, c: char) {
+        self.hit_key(c, false);
+    }
+
+    /// counts `c` as typed correctly, on the numeric keypad if `keypad`
+    pub fn hit_key(&mut self, c: char, keypad: bool) {
+        if let Some(class) = CharClass::of_key(c, keypad) {
+            self.0.entry(class).or_default().chars += 1;
+        }
+    }
+
+    /// counts a miss of the expected character `c`
+    pub fn miss(&mut self, c: char) {
+        self.miss_key(c, false);
+    }
+
+    /// counts a miss of the expected character `c`, on the numeric keypad if
+    /// `keypad`
+    pub fn miss_key(&mut self, c: char, keypad: bool) {
+        if let Some(class) = CharClass::of_key(c, keypad) {
+            self.0.entry(class).or_default().misses += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (class, tally) in &other.0 {
+            let total = self.0.entry(*class).or_default();
+            total.chars += tally.chars;
+            total.misses += tally.misses;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (CharClass, Tally)> + '_ {
+        self.0.iter().map(|(class, tally)| (*class, *tally))
+    }
+}
+
+/// Average and 90th percentile of the time to press a key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyLatency<K = char> {
+    pub key: K,
+    pub count: usize,
+    pub mean_ms: f64,
+    pub p90_ms: u32,
+}
+
+/// Milliseconds from a character becoming the expected one to its correct
+/// press, per key or per transition between two keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Latencies<K: Ord>(BTreeMap<K, Vec<u32>>);
+
+/// Latencies per key
+pub type KeyLatencies = Latencies<char>;
+/// Latencies per pair of consecutive characters inside a word, e.g. `th`
+pub type BigramLatencies = Latencies<String>;
+
+impl<K: Ord> Default for Latencies<K> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<K: Ord + Clone> Latencies<K> {
+    pub fn record(&mut self, key: K, latency: Duration) {
+        let ms = latency.as_millis().min(u32::MAX.into()) as u32;
+        self.0.entry(key).or_default().push(ms);
+    }
+
+    /// every latency of every key, in milliseconds
+    pub fn samples(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.values().flatten().copied()
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        for (key, samples) in &other.0 {
+            self.0.entry(key.clone()).or_default().extend(samples);
+        }
+    }
+
+    /// the `n` keys with the highest average latency, keys pressed fewer
+    /// than `min_count` times are left out as too noisy
+    pub fn slowest(&self, n: usize, min_count: usize) -> Vec<KeyLatency<K>> {
+        let mut keys = self.keys(min_count);
+        keys.sort_by(|a, b| b.mean_ms.total_cmp(&a.mean_ms));
+        keys.truncate(n);
+        keys
+    }
+
+    /// the latency of every key pressed at least `min_count` times, in key
+    /// order
+    pub fn keys(&self, min_count: usize) -> Vec<KeyLatency<K>> {
+        self.0
+            .iter()
+            .filter(|(_, samples)| samples.len() >= min_count.max(1))
+            .map(|(key, samples)| {
+                let mut sorted = samples.clone();
+                sorted.sort_unstable();
+                // nearest rank
+                let rank = (sorted.len() * 9).div_ceil(10).max(1);
+                KeyLatency {
+                    key: key.clone(),
+                    count: sorted.len(),
+                    mean_ms: sorted.iter().map(|ms| f64::from(*ms)).sum::<f64>()
+                        / sorted.len() as f64,
+                    p90_ms: sorted[rank - 1],
+                }
+            })
+            .collect()
+    }
+}
+
+/// Time it took to type a single word of a round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    /// number of keystrokes, including the space leading into the word
+    pub chars: usize,
+    pub duration: Duration,
+}
+
+impl WordTiming {
+    pub fn wpm(&self) -> f64 {
+        wpm(self.chars, self.duration)
+    }
+}
+
+/// A finished round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundRecord {
+    pub target: String,
+    pub duration: Duration,
+    pub missed: bool,
+    /// number of wrong keystrokes
+    pub misses: usize,
+    /// see [`crate::difficulty::score`]
+    pub difficulty: f64,
+    pub words: Vec<WordTiming>,
+    pub classes: ClassTallies,
+    #[serde(default)]
+    pub latencies: KeyLatencies,
+    #[serde(default)]
+    pub bigrams: BigramLatencies,
+    /// indices of the characters of the target that were missed
+    #[serde(default)]
+    pub miss_positions: Vec<usize>,
+    /// characters skipped with Space, left wrong in the output
+    #[serde(default)]
+    pub uncorrected: usize,
+}
+
+impl RoundRecord {
+    pub fn wpm(&self) -> f64 {
+        wpm(self.target.chars().count(), self.duration)
+    }
+
+    /// speed weighted by the difficulty of the text
+    pub fn normalized_wpm(&self) -> f64 {
+        self.wpm() * self.difficulty
+    }
+}
+
+/// All rounds of the current session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub rounds: Vec<RoundRecord>,
+}
+
+impl SessionStats {
+    pub fn push(&mut self, round: RoundRecord) {
+        self.rounds.push(round);
+    }
+
+    pub fn clear(&mut self) {
+        self.rounds.clear();
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &WordTiming> {
+        self.rounds.iter().flat_map(|r| r.words.iter())
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.rounds.iter().map(|r| r.duration).sum()
+    }
+
+    /// number of correctly typed characters
+    pub fn chars(&self) -> usize {
+        self.rounds.iter().map(|r| r.target.chars().count()).sum()
+    }
+
+    pub fn misses(&self) -> usize {
+        self.rounds.iter().map(|r| r.misses).sum()
+    }
+
+    pub fn uncorrected(&self) -> usize {
+        self.rounds.iter().map(|r| r.uncorrected).sum()
+    }
+
+    /// the number of misses of each expected key
+    pub fn key_misses(&self) -> BTreeMap<char, usize> {
+        let mut misses = BTreeMap::new();
+        for round in &self.rounds {
+            let chars: Vec<char> = round.target.chars().collect();
+            for c in round.miss_positions.iter().filter_map(|i| chars.get(*i)) {
+                *misses.entry(*c).or_default() += 1;
+            }
+        }
+        misses
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        accuracy(self.chars(), self.misses())
+    }
+
+    /// seconds ahead of a typist going at `target_wpm`, negative when behind
+    pub fn pace_lead(&self, target_wpm: f64) -> f64 {
+        let chars_per_sec = target_wpm * 5.0 / 60.0;
+        if chars_per_sec <= 0.0 {
+            return 0.0;
+        }
+        self.chars() as f64 / chars_per_sec - self.duration().as_secs_f64()
+    }
+
+    pub fn classes(&self) -> ClassTallies {
+        let mut classes = ClassTallies::default();
+        self.rounds.iter().for_each(|r| classes.merge(&r.classes));
+        classes
+    }
+
+    pub fn latencies(&self) -> KeyLatencies {
+        let mut latencies = KeyLatencies::default();
+        self.rounds
+            .iter()
+            .for_each(|r| latencies.merge(&r.latencies));
+        latencies
+    }
+
+    pub fn bigrams(&self) -> BigramLatencies {
+        let mut bigrams = BigramLatencies::default();
+        self.rounds.iter().for_each(|r| bigrams.merge(&r.bigrams));
+        bigrams
+    }
+
+    /// average speed over the whole session
+    pub fn wpm(&self) -> f64 {
+        wpm(self.chars(), self.duration())
+    }
+
+    /// speed counting the missed keystrokes too, the characters skipped
+    /// with Space are misses that were never typed
+    pub fn raw_wpm(&self) -> f64 {
+        let keystrokes = self.chars() + self.misses() - self.uncorrected();
+        wpm(keystrokes, self.duration())
+    }
+
+    /// see [`consistency`], over the time it took to press each key
+    pub fn consistency(&self) -> f64 {
+        let intervals: Vec<f64> = self.latencies().samples().map(f64::from).collect();
+        consistency(&intervals)
+    }
+
+    /// average speed with every round weighted by its difficulty, so sessions
+    /// of easy and hard texts can be compared
+    pub fn normalized_wpm(&self) -> f64 {
+        let chars = self
+            .rounds
+            .iter()
+            .map(|r| r.target.chars().count() as f64 * r.difficulty)
+            .sum::<f64>();
+        wpm(1, self.duration()) * chars
+    }
+
+    pub fn fastest_word(&self) -> Option<&WordTiming> {
+        self.words().max_by(|a, b| a.wpm().total_cmp(&b.wpm()))
+    }
+
+    pub fn slowest_word(&self) -> Option<&WordTiming> {
+        self.words().min_by(|a, b| a.wpm().total_cmp(&b.wpm()))
+    }
+
+    /// Counts words per speed bucket of `width` WPM. Buckets run from the
+    /// slowest to the fastest word, the label is the lower bound of the bucket.
+    pub fn word_histogram(&self, width: u32) -> Vec<(String, u64)> {
+        let width = width.max(1);
+        let buckets: Vec<usize> = self
+            .words()
+            .map(|w| w.wpm() as usize / width as usize)
+            .collect();
+        let (Some(min), Some(max)) = (buckets.iter().min(), buckets.iter().max()) else {
+            return vec![];
+        };
+
+        let mut counts = vec![0; max - min + 1];
+        buckets.iter().for_each(|b| counts[b - min] += 1);
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (((min + i) * width as usize).to_string(), count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word: &str, millis: u64) -> WordTiming {
+        WordTiming {
+            word: word.into(),
+            chars: word.len(),
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn session_stats() {
+        let mut stats = SessionStats::default();
+        assert!(stats.fastest_word().is_none());
+        assert!(stats.word_histogram(10).is_empty());
+
+        stats.push(RoundRecord {
+            words: vec![word("hello", 1000), word("world", 1000)],
+            ..RoundRecord::typed("hello world", Duration::from_secs(2))
+        });
+        stats.push(RoundRecord {
+            missed: true,
+            misses: 3,
+            difficulty: 2.0,
+            words: vec![word("hi", 200)],
+            ..RoundRecord::typed("hi", Duration::from_millis(200))
+        });
+
+        // 13 chars in 2.2 seconds
+        assert!((stats.wpm() - 70.909).abs() < 0.01);
+        // 16 keystrokes in 2.2 seconds
+        assert!((stats.raw_wpm() - 87.273).abs() < 0.01);
+        assert_eq!(consistency(&[120.0, 120.0, 120.0]), 100.0);
+        assert!((consistency(&[50.0, 150.0]) - 50.1).abs() < 0.1);
+        assert_eq!(stats.accuracy(), 0.8125);
+        // 11 + 2 * 2 weighted chars
+        assert!((stats.normalized_wpm() - 81.818).abs() < 0.01);
+        assert_eq!(stats.fastest_word().unwrap().word, "hi");
+        assert_eq!(stats.slowest_word().unwrap().word, "hello");
+        // 13 chars take 2.6 seconds at 60 WPM
+        assert!((stats.pace_lead(60.0) - 0.4).abs() < 1e-9);
+
+        // hello/world: 60 WPM, hi: 120 WPM
+        let histogram = stats.word_histogram(50);
+        assert_eq!(histogram, vec![("50".into(), 2), ("100".into(), 1)]);
+    }
+
+    #[test]
+    fn tallies_per_class() {
+        let mut classes = ClassTallies::default();
+        "a1 $".chars().for_each(|c| classes.hit(c));
+        classes.miss('$');
+        classes.miss('9');
+        let mut total = classes.clone();
+        total.merge(&classes);
+
+        let tallies: Vec<(CharClass, Tally)> = total.iter().collect();
+        assert_eq!(
+            tallies,
+            vec![
+                (
+                    CharClass::Letter,
+                    Tally {
+                        chars: 2,
+                        misses: 0
+                    }
+                ),
+                (
+                    CharClass::Digit,
+                    Tally {
+                        chars: 2,
+                        misses: 2
+                    }
+                ),
+                (
+                    CharClass::Symbol,
+                    Tally {
+                        chars: 2,
+                        misses: 2
+                    }
+                ),
+            ]
+        );
+        assert_eq!(tallies[1].1.accuracy(), 0.5);
+    }
+
+    #[test]
+    fn slowest_keys() {
+        let mut latencies = KeyLatencies::default();
+        for ms in 1..=10 {
+            latencies.record('a', Duration::from_millis(ms * 10));
+        }
+        latencies.record('b', Duration::from_millis(500));
+        let mut more = KeyLatencies::default();
+        more.record('c', Duration::from_millis(200));
+        more.record('c', Duration::from_millis(300));
+        latencies.merge(&more);
+
+        let slowest = latencies.slowest(5, 2);
+        assert_eq!(slowest.len(), 2);
+        assert_eq!((slowest[0].key, slowest[0].mean_ms), ('c', 250.0));
+        assert_eq!(slowest[0].p90_ms, 300);
+        assert_eq!((slowest[1].key, slowest[1].count), ('a', 10));
+        assert_eq!(slowest[1].p90_ms, 90);
+    }
+}
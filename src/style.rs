@@ -0,0 +1,354 @@
+//! Style files laid over the theme, so themes can be shared without
+//! changing the code. They read like CSS:
+//!
+//! ```css
+//! /* gruvbox-ish misses */
+//! miss { color: #fb4934; background: black; modifiers: bold underlined; }
+//! caret { modifiers: none; background: darkgray; }
+//! ```
+//!
+//! Every element of [`Theme`] can be styled with `color`, `background` and
+//! `modifiers`. The file in the config is reloaded when it changes and
+//! `metyping style check` validates one.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::{eyre::WrapErr, Result};
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::{theme::Theme, App};
+
+/// How often the style file is checked for changes
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The elements of the theme that can be styled
+const ELEMENTS: [&str; 9] = [
+    "header", "border", "title", "value", "hit", "miss", "pending", "caret", "pace",
+];
+
+/// A mistake in a style file
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// All mistakes in a style file, one per line
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleErrors(pub Vec<StyleError>);
+
+impl fmt::Display for StyleErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl std::error::Error for StyleErrors {}
+
+/// The rules of a style file, the style of an element and what it changes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleSheet {
+    rules: Vec<(&'static str, Style)>,
+}
+
+impl StyleSheet {
+    /// parses `source`, with every mistake in it if there are any
+    pub fn parse(source: &str) -> Result<Self, StyleErrors> {
+        let mut sheet = Self::default();
+        let mut errors = vec![];
+        let mut error = |line, message: String| errors.push(StyleError { line, message });
+
+        let source = strip_comments(source);
+        let mut rest = source.as_str();
+        let mut line = 1;
+        while let Some(open) = rest.find('{') {
+            let selector = rest[..open].trim();
+            let selector_line = line + count_leading_newlines(&rest[..open]);
+            let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                error(selector_line, format!("`{selector}` isn't closed with }}"));
+                rest = "";
+                break;
+            };
+            let body = &rest[open + 1..close];
+            match ELEMENTS.iter().find(|element| **element == selector) {
+                Some(element) => {
+                    let mut style = Style::new();
+                    let mut at = line + rest[..=open].matches('\n').count();
+                    for declaration in body.split(';') {
+                        if !declaration.trim().is_empty() {
+                            if let Err(message) = declare(&mut style, declaration.trim()) {
+                                error(at + count_leading_newlines(declaration), message);
+                            }
+                        }
+                        at += declaration.matches('\n').count();
+                    }
+                    sheet.rules.push((element, style));
+                }
+                None => error(
+                    selector_line,
+                    format!(
+                        "unknown element `{selector}`, available: {}",
+                        ELEMENTS.join(", ")
+                    ),
+                ),
+            }
+            line += rest[..=close].matches('\n').count();
+            rest = &rest[close + 1..];
+        }
+        if !rest.trim().is_empty() {
+            let at = line + count_leading_newlines(rest);
+            error(at, format!("`{}` has no {{ }}", rest.trim()));
+        }
+
+        if errors.is_empty() {
+            Ok(sheet)
+        } else {
+            Err(StyleErrors(errors))
+        }
+    }
+
+    /// reads and parses the style file at `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .wrap_err_with(|| format!("reading {} failed", path.display()))?;
+        Self::parse(&source).wrap_err_with(|| format!("{} has mistakes", path.display()))
+    }
+
+    /// `theme` with the rules laid over it in order
+    pub fn apply(&self, mut theme: Theme) -> Theme {
+        for (element, style) in &self.rules {
+            let target = match *element {
+                "header" => &mut theme.header,
+                "border" => &mut theme.border,
+                "title" => &mut theme.title,
+                "value" => &mut theme.value,
+                "hit" => &mut theme.hit,
+                "miss" => &mut theme.miss,
+                "pending" => &mut theme.pending,
+                "caret" => &mut theme.caret,
+                _ => &mut theme.pace,
+            };
+            *target = target.patch(*style);
+        }
+        theme
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+}
+
+/// the lines `text` starts below the line it begins on
+fn count_leading_newlines(text: &str) -> usize {
+    let trimmed = text.trim_start();
+    text[..text.len() - trimmed.len()].matches('\n').count()
+}
+
+/// `source` with the `/* */` comments blanked out, keeping the lines
+fn strip_comments(source: &str) -> String {
+    let mut text = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        text.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("*/")
+            .map_or(rest.len(), |end| start + end + 2);
+        text.extend(rest[start..end].chars().filter(|c| *c == '\n'));
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    text
+}
+
+/// adds the declaration `property: value` to `style`
+fn declare(style: &mut Style, declaration: &str) -> Result<(), String> {
+    let Some((property, value)) = declaration.split_once(':') else {
+        return Err(format!("`{declaration}` isn't a `property: value`"));
+    };
+    let value = value.trim();
+    let color = || Color::from_str(value).map_err(|_| format!("unknown color `{value}`"));
+    match property.trim() {
+        "color" => *style = style.fg(color()?),
+        "background" => *style = style.bg(color()?),
+        "modifiers" if value == "none" => *style = style.remove_modifier(Modifier::all()),
+        "modifiers" => {
+            for name in value.split_whitespace() {
+                let modifier = match name {
+                    "bold" => Modifier::BOLD,
+                    "dim" => Modifier::DIM,
+                    "italic" => Modifier::ITALIC,
+                    "underlined" => Modifier::UNDERLINED,
+                    "reversed" => Modifier::REVERSED,
+                    "crossed-out" => Modifier::CROSSED_OUT,
+                    "slow-blink" => Modifier::SLOW_BLINK,
+                    _ => return Err(format!("unknown modifier `{name}`")),
+                };
+                *style = style.add_modifier(modifier);
+            }
+        }
+        property => {
+            return Err(format!(
+                "unknown property `{property}`, available: color, background, modifiers"
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// checks the style file at `path` and prints what's wrong with it
+pub fn check_file(path: &Path) -> Result<()> {
+    let sheet = StyleSheet::load(path)?;
+    println!("{} is fine, {} rules", path.display(), sheet.len());
+    Ok(())
+}
+
+/// The style file of the config and when it last changed
+#[derive(Debug, Clone)]
+pub struct StyleWatch {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    checked: Option<Instant>,
+    pub sheet: StyleSheet,
+}
+
+impl StyleWatch {
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self {
+            path: path.into(),
+            modified: modified(path),
+            checked: None,
+            sheet: StyleSheet::load(path)?,
+        })
+    }
+
+    /// the sheet if the file changed since it was loaded, checked once in a
+    /// while
+    fn reload(&mut self, now: Instant) -> Option<Result<StyleSheet>> {
+        if self
+            .checked
+            .is_some_and(|checked| now.saturating_duration_since(checked) < RELOAD_INTERVAL)
+        {
+            return None;
+        }
+        self.checked = Some(now);
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(StyleSheet::load(&self.path))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+impl App<'_> {
+    /// uses `theme` with the style file and the accessibility options laid
+    /// over it
+    pub(crate) fn use_theme(&mut self, theme: Theme) {
+        let theme = match &self.style {
+            Some(watch) => watch.sheet.apply(theme),
+            None => theme,
+        };
+        self.theme = theme.with_accessibility(&self.config.accessibility);
+    }
+
+    /// applies the style file again once it changed, a broken one is
+    /// reported and the last good one kept
+    pub(crate) fn reload_style(&mut self, now: Instant) {
+        let Some(result) = self.style.as_mut().and_then(|watch| watch.reload(now)) else {
+            return;
+        };
+        match result {
+            Ok(sheet) => {
+                if let Some(watch) = &mut self.style {
+                    watch.sheet = sheet;
+                }
+                if let Some(theme) = Theme::by_name(&self.config.theme) {
+                    self.use_theme(theme);
+                }
+                self.status = Some("style reloaded".into());
+            }
+            Err(error) => self.status = Some(format!("{error:#}").replace('\n', " ")),
+        }
+        self.dirty = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn parse_and_apply_style_files() {
+        let sheet = StyleSheet::parse(
+            "/* shared\n theme */\nmiss {\n  color: #ff0000;\n  modifiers: bold italic;\n}\nhit { color: blue } caret { modifiers: none; background: 8 }",
+        )
+        .unwrap();
+        let theme = sheet.apply(Theme::default());
+        assert_eq!(
+            theme.miss,
+            Theme::default()
+                .miss
+                .fg(Color::Rgb(255, 0, 0))
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(theme.hit, Style::new().fg(Color::Blue));
+        assert_eq!(
+            theme.caret,
+            Style::new()
+                .bg(Color::Indexed(8))
+                .remove_modifier(Modifier::all())
+        );
+
+        let errors =
+            StyleSheet::parse("hit {\n  colour: red;\n  color: reddish;\n}\n\nheadr { }\npace")
+                .unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "line 2: unknown property `colour`, available: color, background, modifiers\n\
+             line 3: unknown color `reddish`\n\
+             line 6: unknown element `headr`, available: header, border, title, value, hit, miss, pending, caret, pace\n\
+             line 7: `pace` has no { }"
+        );
+    }
+    #[test]
+    fn keep_the_last_good_sheet_on_a_broken_reload() {
+        let path = temp_path("style.css");
+        fs::write(&path, "hit { color: blue }").unwrap();
+        let mut app = App {
+            style: Some(StyleWatch::load(&path).unwrap()),
+            ..Default::default()
+        };
+        app.use_theme(Theme::default());
+        app.next_round().unwrap();
+
+        fs::write(&path, "hit { color: blu }").unwrap();
+        if let Some(watch) = &mut app.style {
+            // the file may be written within the same tick of the clock
+            watch.modified = None;
+        }
+        app.reload_style(Instant::now());
+        fs::remove_file(&path).unwrap();
+        let status = app.status.clone().unwrap();
+        assert!(status.contains("has mistakes: line 1: unknown color `blu`"));
+        assert_eq!(app.theme.hit, Style::new().fg(Color::Blue));
+        assert!(!app.exit);
+        assert_eq!(app.input_mode(), crate::InputMode::Test);
+    }
+}
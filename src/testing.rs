@@ -0,0 +1,58 @@
+//! What the tests of several modules share: paths of their own in the
+//! temporary directory and rounds to fill sessions with.
+
+use std::{
+    env,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crate::stats::RoundRecord;
+
+/// Number of the next temporary path, the tests run at the same time
+static NEXT_PATH: AtomicUsize = AtomicUsize::new(0);
+
+/// a path in the temporary directory that no other test gets, ending in
+/// `name`, e.g. `history.jsonl`
+pub fn temp_path(name: &str) -> PathBuf {
+    let n = NEXT_PATH.fetch_add(1, Ordering::Relaxed);
+    env::temp_dir().join(format!("metyping-{}-{n}-{name}", process::id()))
+}
+
+impl RoundRecord {
+    /// `target` typed in `duration` without a miss, the rest is filled in
+    /// with `..RoundRecord::typed(…)`
+    pub fn typed(target: &str, duration: Duration) -> Self {
+        Self {
+            target: target.into(),
+            duration,
+            missed: false,
+            misses: 0,
+            difficulty: 1.0,
+            words: vec![],
+            classes: Default::default(),
+            latencies: Default::default(),
+            bigrams: Default::default(),
+            miss_positions: vec![],
+            uncorrected: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_test_gets_its_own_path() {
+        let (a, b) = (temp_path("a.json"), temp_path("a.json"));
+        assert_ne!(a, b);
+        assert!(a.to_string_lossy().ends_with("-a.json"));
+        assert_eq!(
+            RoundRecord::typed("abc", Duration::from_secs(1)).wpm(),
+            36.0
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// Styles for every semantic element the app renders
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub header: Style,
+    pub border: Style,
+    pub title: Style,
+    pub value: Style,
+    pub hit: Style,
+    pub miss: Style,
+    pub pending: Style,
+    /// the character to type next
+    pub caret: Style,
+    /// the position of the pace caret
+    pub pace: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "default",
+            header: Style::new().fg(Color::Black).bg(Color::Cyan),
+            border: Style::new(),
+            title: Style::new().add_modifier(Modifier::BOLD),
+            value: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            hit: Style::new().fg(Color::Green),
+            miss: Style::new()
+                .fg(Color::Red)
+                .add_modifier(Modifier::UNDERLINED),
+            pending: Style::new().add_modifier(Modifier::BOLD),
+            caret: Style::new().add_modifier(Modifier::REVERSED),
+            pace: Style::new().bg(Color::DarkGray),
+        }
+    }
+}
+
+impl Theme {
+    /// Names of all built-in themes
+    pub const NAMES: [&'static str; 4] = ["default", "gruvbox", "mono", "high-contrast"];
+
+    /// Looks up a built-in theme, returns `None` for unknown names
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "gruvbox" => Some(Self::gruvbox()),
+            "mono" => Some(Self::mono()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    fn gruvbox() -> Self {
+        let fg = Color::Rgb(235, 219, 178);
+        Self {
+            name: "gruvbox",
+            header: Style::new()
+                .fg(Color::Rgb(40, 40, 40))
+                .bg(Color::Rgb(215, 153, 33)),
+            border: Style::new().fg(Color::Rgb(146, 131, 116)),
+            title: Style::new().fg(fg).add_modifier(Modifier::BOLD),
+            value: Style::new()
+                .fg(Color::Rgb(250, 189, 47))
+                .add_modifier(Modifier::BOLD),
+            hit: Style::new().fg(Color::Rgb(184, 187, 38)),
+            miss: Style::new()
+                .fg(Color::Rgb(251, 73, 52))
+                .add_modifier(Modifier::UNDERLINED),
+            pending: Style::new().fg(fg).add_modifier(Modifier::BOLD),
+            caret: Style::new().add_modifier(Modifier::REVERSED),
+            pace: Style::new().bg(Color::Rgb(80, 73, 69)),
+        }
+    }
+
+    fn mono() -> Self {
+        Self {
+            name: "mono",
+            header: Style::new().add_modifier(Modifier::REVERSED),
+            border: Style::new(),
+            title: Style::new().add_modifier(Modifier::BOLD),
+            value: Style::new().add_modifier(Modifier::BOLD),
+            hit: Style::new().add_modifier(Modifier::DIM),
+            miss: Style::new().add_modifier(Modifier::UNDERLINED),
+            pending: Style::new().add_modifier(Modifier::BOLD),
+            caret: Style::new().add_modifier(Modifier::REVERSED),
+            pace: Style::new().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// bright colors on black, with hits and misses in blue and orange
+    /// instead of green and red
+    fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast",
+            header: Style::new()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            border: Style::new().fg(Color::White),
+            title: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            value: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            hit: Style::new().fg(Color::LightBlue),
+            miss: Style::new()
+                .fg(Color::Black)
+                .bg(Color::Rgb(255, 160, 0))
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            pending: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            caret: Style::new().add_modifier(Modifier::REVERSED),
+            pace: Style::new().bg(Color::Gray),
+        }
+    }
+
+    /// marks misses as configured, independent of the theme's colors
+    pub fn with_accessibility(mut self, accessibility: &Accessibility) -> Self {
+        let markers = Modifier::UNDERLINED | Modifier::CROSSED_OUT;
+        self.miss = self.miss.remove_modifier(markers);
+        self.miss = match accessibility.miss_marker {
+            MissMarker::Color => self.miss,
+            MissMarker::Underline => self.miss.add_modifier(Modifier::UNDERLINED),
+            MissMarker::Strikethrough => self.miss.add_modifier(Modifier::CROSSED_OUT),
+        };
+        self
+    }
+}
+
+/// Options that keep hits and misses apart without telling colors apart,
+/// combinable with any theme
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    /// how missed characters are marked besides their color
+    pub miss_marker: MissMarker,
+    /// show ✓ and ✗ for the state of the round and the wins and fails
+    pub symbols: bool,
+    /// no caret gliding, shaking on a miss or growing charts
+    pub reduced_motion: bool,
+}
+
+/// Text decoration of missed characters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MissMarker {
+    /// only the theme's color
+    Color,
+    #[default]
+    Underline,
+    Strikethrough,
+}
+
+impl Accessibility {
+    /// marker of a round without misses
+    pub const HIT: &'static str = "✓";
+    /// marker of a round with misses
+    pub const MISS: &'static str = "✗";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_name_knows_all_builtin_themes() {
+        for name in Theme::NAMES {
+            assert_eq!(Theme::by_name(name).unwrap().name, name);
+        }
+        assert!(Theme::by_name("nope").is_none());
+    }
+
+    #[test]
+    fn miss_marker_replaces_the_underline() {
+        let accessibility = Accessibility {
+            miss_marker: MissMarker::Strikethrough,
+            ..Default::default()
+        };
+        let miss = Theme::default().with_accessibility(&accessibility).miss;
+        assert!(miss.add_modifier.contains(Modifier::CROSSED_OUT));
+        assert!(!miss.add_modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(miss.fg, Theme::default().miss.fg);
+
+        let plain = Accessibility {
+            miss_marker: MissMarker::Color,
+            ..Default::default()
+        };
+        let miss = Theme::by_name("mono")
+            .unwrap()
+            .with_accessibility(&plain)
+            .miss;
+        assert!(miss.add_modifier.is_empty());
+    }
+}
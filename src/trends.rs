@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, fmt};
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{history::HistoryEntry, zone::Zone};
+
+/// Length of the periods the history is grouped by
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Period {
+    #[default]
+    Week,
+    Month,
+}
+
+impl Period {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Week => Self::Month,
+            Self::Month => Self::Week,
+        }
+    }
+
+    /// first day of the period `date` is in, weeks start on Monday
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday().into()),
+            Self::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// short label of the period starting at `start`
+    pub fn label(self, start: NaiveDate) -> String {
+        match self {
+            Self::Week => start.format("%m/%d").to_string(),
+            Self::Month => start.format("%y-%m").to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Week => write!(f, "weekly"),
+            Self::Month => write!(f, "monthly"),
+        }
+    }
+}
+
+/// The sessions of one period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub start: NaiveDate,
+    pub sessions: usize,
+    /// average speed of the sessions
+    pub wpm: f64,
+    pub accuracy: f64,
+    /// average raw speed and consistency of the sessions recording them
+    pub raw_wpm: Option<f64>,
+    pub consistency: Option<f64>,
+    pub duration_ms: u64,
+}
+
+impl Trend {
+    /// time practiced in minutes
+    pub fn minutes(&self) -> f64 {
+        self.duration_ms as f64 / 60_000.0
+    }
+}
+
+/// The history grouped by `period` with days starting in `zone`, oldest
+/// first. Periods without sessions are left out.
+pub fn trends(entries: &[HistoryEntry], period: Period, zone: Zone) -> Vec<Trend> {
+    let mut groups: BTreeMap<NaiveDate, Vec<&HistoryEntry>> = BTreeMap::new();
+    for entry in entries {
+        let date = zone.date(entry.timestamp);
+        groups.entry(period.start(date)).or_default().push(entry);
+    }
+    groups
+        .into_iter()
+        .map(|(start, entries)| {
+            let chars = entries.iter().map(|e| e.chars).sum();
+            let misses = entries.iter().map(|e| e.misses).sum();
+            // older entries don't have them
+            let recorded = |value: fn(&HistoryEntry) -> f64| {
+                let values: Vec<f64> = entries
+                    .iter()
+                    .map(|e| value(e))
+                    .filter(|v| *v > 0.0)
+                    .collect();
+                (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+            };
+            Trend {
+                start,
+                sessions: entries.len(),
+                wpm: entries.iter().map(|e| e.wpm).sum::<f64>() / entries.len() as f64,
+                accuracy: crate::stats::accuracy(chars, misses),
+                raw_wpm: recorded(|e| e.raw_wpm),
+                consistency: recorded(|e| e.consistency),
+                duration_ms: entries.iter().map(|e| e.duration_ms).sum(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::{stats::ClassTallies, Mode};
+
+    fn entry(day: u32, wpm: f64) -> HistoryEntry {
+        HistoryEntry {
+            // noon, so the local date is the same day in every time zone
+            timestamp: Utc.with_ymd_and_hms(2024, 5, day, 12, 0, 0).unwrap(),
+            mode: Mode::Words(10),
+            word_list: None,
+            rounds: 1,
+            chars: 90,
+            misses: 10,
+            uncorrected: 0,
+            duration_ms: 30_000,
+            wpm,
+            normalized_wpm: wpm,
+            raw_wpm: 0.0,
+            consistency: 0.0,
+            classes: ClassTallies::default(),
+            latencies: Default::default(),
+            bigrams: Default::default(),
+            key_misses: Default::default(),
+            curve: vec![],
+            tags: vec![],
+            note: String::new(),
+            plan: None,
+        }
+    }
+
+    #[test]
+    fn group_by_week_and_month() {
+        // Friday, Sunday and the Monday after
+        let mut entries = [entry(3, 40.0), entry(5, 50.0), entry(6, 60.0)];
+        entries[2].consistency = 80.0;
+
+        let weeks = trends(&entries, Period::Week, Zone::Local);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(
+            weeks[0].start,
+            NaiveDate::from_ymd_opt(2024, 4, 29).unwrap()
+        );
+        assert_eq!(weeks[0].sessions, 2);
+        assert_eq!(weeks[0].wpm, 45.0);
+        assert_eq!(weeks[0].accuracy, 0.9);
+        assert_eq!(weeks[0].minutes(), 1.0);
+        assert_eq!(Period::Week.label(weeks[1].start), "05/06");
+        // left out where the entries don't record it
+        assert_eq!(weeks[0].consistency, None);
+        assert_eq!(weeks[1].consistency, Some(80.0));
+
+        let months = trends(&entries, Period::Month, Zone::Local);
+        assert_eq!(months.len(), 1);
+        assert_eq!(months[0].wpm, 50.0);
+        assert_eq!(Period::Month.label(months[0].start), "24-05");
+    }
+}
@@ -1,38 +1,120 @@
 use std::{
     io::{self, stdout, Stdout},
+    ops::{Deref, DerefMut},
     process,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
+        event::{
+            DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+            KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        },
         execute,
-        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+        terminal::{
+            disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+            LeaveAlternateScreen,
+        },
     },
-    terminal::Terminal,
+    terminal::{Terminal, TerminalOptions, Viewport},
 };
 
 use crate::errors;
 
-/// A type alias for the terminal type used in this application
-pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+/// Rows of the inline viewport, enough for every screen
+const INLINE_HEIGHT: u16 = 22;
 
-/// Initialize the terminal
-pub fn init() -> io::Result<Tui> {
+/// Whether the terminal was set up inline, [`restore`] must not leave an
+/// alternate screen it never entered
+static INLINE: AtomicBool = AtomicBool::new(false);
+/// Whether the keyboard enhancement flags were pushed, [`restore`] pops them
+static ENHANCED: AtomicBool = AtomicBool::new(false);
+
+/// The terminal used in this application, restored when dropped
+pub struct Tui {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+}
+
+impl Deref for Tui {
+    type Target = Terminal<CrosstermBackend<Stdout>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl DerefMut for Tui {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        if INLINE.load(Ordering::Relaxed) {
+            // leave the last frame in the scrollback and continue below it
+            let area = self.terminal.get_frame().size();
+            let _ = self.terminal.set_cursor(0, area.bottom().saturating_sub(1));
+            println!();
+        }
+        let _ = restore(); // nothing left to do if it fails
+    }
+}
+
+/// Initialize the terminal, capturing mouse events if `mouse` is set. An
+/// `inline` terminal draws below the prompt instead of on the alternate
+/// screen, so the results stay in the scrollback.
+pub fn init(mouse: bool, inline: bool) -> io::Result<Tui> {
     let res = errors::install_hooks();
     if res.is_err() {
         println!("Unable to install error hooks: {}", res.err().unwrap());
         process::exit(1);
     }
 
-    execute!(stdout(), EnterAlternateScreen)?;
+    INLINE.store(inline, Ordering::Relaxed);
+    if !inline {
+        execute!(stdout(), EnterAlternateScreen)?;
+    }
+    // pastes arrive as one event instead of keystrokes that look typed
+    execute!(stdout(), EnableBracketedPaste)?;
+    if mouse {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
-    Terminal::new(CrosstermBackend::new(stdout()))
+    // releases and repeats of keys, for the holds, see [`crate::holds`]
+    if supports_keyboard_enhancement().unwrap_or(false) {
+        execute!(
+            stdout(),
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )?;
+        ENHANCED.store(true, Ordering::Relaxed);
+    }
+    let viewport = if inline {
+        Viewport::Inline(INLINE_HEIGHT)
+    } else {
+        Viewport::Fullscreen
+    };
+    let terminal = Terminal::with_options(
+        CrosstermBackend::new(stdout()),
+        TerminalOptions { viewport },
+    )?;
+    Ok(Tui { terminal })
 }
 
 /// Restore the terminal to its original state
 pub fn restore() -> io::Result<()> {
-    execute!(stdout(), LeaveAlternateScreen)?;
+    if ENHANCED.swap(false, Ordering::Relaxed) {
+        execute!(stdout(), PopKeyboardEnhancementFlags)?;
+    }
+    execute!(stdout(), DisableBracketedPaste, DisableMouseCapture)?;
+    if !INLINE.load(Ordering::Relaxed) {
+        execute!(stdout(), LeaveAlternateScreen)?;
+    }
     disable_raw_mode()?;
     Ok(())
 }
@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::{RoundRecord, Tally};
+
+/// Common english words, one per line
+const ENGLISH: &str = include_str!("../assets/words/english.txt");
+/// Common german words, one per line
+const GERMAN: &str = include_str!("../assets/words/german.txt");
+
+/// A list of words to draw practice text from
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordList {
+    pub name: String,
+    pub words: Vec<String>,
+    /// a second list some of the words are drawn from
+    pub mix: Option<Box<Mix>>,
+    /// what the words were filtered with, see [`WordList::filtered`]
+    pub filter: WordFilter,
+}
+
+/// Words of a second list mixed into a list, see [`WordList::mixed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mix {
+    pub list: WordList,
+    /// share of the words drawn from `list`, between 0 and 1
+    pub ratio: f64,
+}
+
+/// A second word list to mix in, for practicing two languages at once
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MixConfig {
+    /// name of the list, see [`WordList::NAMES`]. Nothing is mixed in if
+    /// empty.
+    pub list: String,
+    /// share of the words drawn from it, between 0 and 1
+    pub ratio: f64,
+}
+
+impl Default for MixConfig {
+    fn default() -> Self {
+        Self {
+            list: String::new(),
+            ratio: 0.3,
+        }
+    }
+}
+
+/// Which words of a list are practiced, to tune the difficulty without
+/// another list
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WordFilter {
+    /// fewest letters of a word, 0 for no limit
+    pub min_length: usize,
+    /// most letters of a word, 0 for no limit
+    pub max_length: usize,
+    /// only the rarer half of the list, the lists go from common to rare
+    pub hard_only: bool,
+    /// no word twice in a session until every word came up
+    pub no_repeats: bool,
+}
+
+impl WordFilter {
+    fn keeps(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        len >= self.min_length && (self.max_length == 0 || len <= self.max_length)
+    }
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        Self::from_text("english", ENGLISH)
+    }
+}
+
+impl WordList {
+    /// Names of the built-in lists
+    pub const NAMES: [&'static str; 2] = ["english", "german"];
+
+    /// The built-in list called `name`
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Self::from_text(name, ENGLISH)),
+            "german" => Some(Self::from_text(name, GERMAN)),
+            _ => None,
+        }
+    }
+
+    /// Builds a list from whitespace separated words, skipping duplicates
+    pub fn from_text(name: &str, text: &str) -> Self {
+        let mut words: Vec<String> = vec![];
+        for word in text.split_whitespace() {
+            if !words.iter().any(|w| w == word) {
+                words.push(word.to_string());
+            }
+        }
+        Self {
+            name: name.into(),
+            words,
+            mix: None,
+            filter: WordFilter::default(),
+        }
+    }
+
+    /// this list with `ratio` of the words drawn from `other` instead
+    pub fn mixed(self, other: WordList, ratio: f64) -> Self {
+        Self {
+            mix: Some(Box::new(Mix {
+                list: other,
+                ratio: ratio.clamp(0.0, 1.0),
+            })),
+            ..self
+        }
+    }
+
+    /// this list and the one mixed in with only the words `filter` keeps,
+    /// `None` if it keeps none of either
+    pub fn filtered(mut self, filter: &WordFilter) -> Option<Self> {
+        if filter.hard_only {
+            self.words.drain(..self.words.len() / 2);
+        }
+        self.words.retain(|word| filter.keeps(word));
+        if let Some(mix) = self.mix.take() {
+            self.mix = Some(Box::new(Mix {
+                list: mix.list.filtered(filter)?,
+                ratio: mix.ratio,
+            }));
+        }
+        self.filter = filter.clone();
+        (!self.words.is_empty()).then_some(self)
+    }
+
+    /// the name of the list and of the one mixed in, e.g. `english + german`
+    pub fn label(&self) -> String {
+        match &self.mix {
+            Some(mix) => format!("{} + {}", self.name, mix.list.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// `count` random words joined by single spaces
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> String {
+        (0..count)
+            .filter_map(|_| match &self.mix {
+                Some(mix) if rng.gen_bool(mix.ratio) => mix.list.words.choose(rng),
+                _ => self.words.choose(rng),
+            })
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// like [`WordList::sample`] but none of the words in `used`, which
+    /// starts over once every word of a list was used
+    pub fn sample_unused<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        count: usize,
+        used: &mut HashSet<String>,
+    ) -> String {
+        (0..count)
+            .map(|_| {
+                let list = match &self.mix {
+                    Some(mix) if rng.gen_bool(mix.ratio) => &mix.list.words,
+                    _ => &self.words,
+                };
+                let mut unused: Vec<&String> =
+                    list.iter().filter(|word| !used.contains(*word)).collect();
+                if unused.is_empty() {
+                    used.retain(|word| !list.contains(word));
+                    unused = list.iter().collect();
+                }
+                let word = unused.choose(rng).copied().cloned().unwrap_or_default();
+                used.insert(word.clone());
+                word
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// the name of the list `word` is from, words in both count for this
+    /// one
+    pub fn origin(&self, word: &str) -> &str {
+        match &self.mix {
+            Some(mix)
+                if !self.words.iter().any(|w| w == word)
+                    && mix.list.words.iter().any(|w| w == word) =>
+            {
+                &mix.list.name
+            }
+            _ => &self.name,
+        }
+    }
+
+    /// the characters and misses of the words of `rounds` per list they're
+    /// from, empty if nothing is mixed in
+    pub fn tallies(&self, rounds: &[RoundRecord]) -> BTreeMap<String, Tally> {
+        let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+        let Some(mix) = &self.mix else {
+            return tallies;
+        };
+        // both lists are listed even if one wasn't drawn from
+        tallies.insert(self.name.clone(), Tally::default());
+        tallies.insert(mix.list.name.clone(), Tally::default());
+        for round in rounds {
+            let mut start = 0;
+            for word in round.target.split(' ') {
+                let len = word.chars().count();
+                let tally = tallies.entry(self.origin(word).into()).or_default();
+                tally.chars += len;
+                tally.misses += round
+                    .miss_positions
+                    .iter()
+                    .filter(|&&i| (start..start + len).contains(&i))
+                    .count();
+                start += len + 1;
+            }
+        }
+        tallies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_joins_words_from_the_list() {
+        let list = WordList::from_text("test", "foo bar\nfoo baz");
+        assert_eq!(list.words, vec!["foo", "bar", "baz"]);
+
+        let text = list.sample(&mut rand::thread_rng(), 5);
+        let words: Vec<&str> = text.split(' ').collect();
+        assert_eq!(words.len(), 5);
+        assert!(words.iter().all(|w| list.words.iter().any(|l| l == w)));
+    }
+
+    #[test]
+    fn filter_the_words() {
+        let list = WordList::from_text("test", "a be sea deer eagle fishes");
+        let filter = WordFilter {
+            min_length: 2,
+            max_length: 5,
+            ..Default::default()
+        };
+        let short = list.clone().filtered(&filter).unwrap();
+        assert_eq!(short.words, ["be", "sea", "deer", "eagle"]);
+        let hard = WordFilter {
+            hard_only: true,
+            ..filter
+        };
+        assert_eq!(
+            list.clone().filtered(&hard).unwrap().words,
+            ["deer", "eagle"]
+        );
+        let none = WordFilter {
+            min_length: 9,
+            ..Default::default()
+        };
+        assert_eq!(list.filtered(&none), None);
+
+        let mut used = HashSet::new();
+        let text = short.sample_unused(&mut rand::thread_rng(), 4, &mut used);
+        let mut words: Vec<&str> = text.split(' ').collect();
+        words.sort();
+        assert_eq!(words, ["be", "deer", "eagle", "sea"]);
+        let text = short.sample_unused(&mut rand::thread_rng(), 2, &mut used);
+        assert_ne!(text.split(' ').next(), text.split(' ').nth(1));
+    }
+
+    #[test]
+    fn mix_in_a_second_list() {
+        let english = WordList::from_text("english", "the cat");
+        let german = WordList::from_text("german", "die katze");
+        let list = english.mixed(german, 1.0);
+        assert_eq!(list.label(), "english + german");
+        let text = list.sample(&mut rand::thread_rng(), 4);
+        assert!(text.split(' ').all(|w| ["die", "katze"].contains(&w)));
+
+        let round = RoundRecord {
+            missed: true,
+            misses: 2,
+            miss_positions: vec![1, 5],
+            ..RoundRecord::typed("the katze", Default::default())
+        };
+        let tallies = list.tallies(&[round]);
+        assert_eq!(
+            tallies["english"],
+            Tally {
+                chars: 3,
+                misses: 1
+            }
+        );
+        assert_eq!(
+            tallies["german"],
+            Tally {
+                chars: 5,
+                misses: 1
+            }
+        );
+        assert!(WordList::default().tallies(&[]).is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Offset, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The time zone days start in for the history, timestamps are stored in
+/// UTC. Written as `local` or an offset like `+05:30` in the config.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Zone {
+    /// the time zone of the system
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    /// `timestamp` as the time of day in this zone
+    pub fn datetime(self, timestamp: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local => timestamp.with_timezone(&Local).fixed_offset(),
+            Self::Fixed(offset) => timestamp.with_timezone(&offset),
+        }
+    }
+
+    /// the day `timestamp` is on in this zone
+    pub fn date(self, timestamp: DateTime<Utc>) -> NaiveDate {
+        self.datetime(timestamp).date_naive()
+    }
+}
+
+impl FromStr for Zone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "local" => Ok(Self::Local),
+            "utc" | "UTC" | "Z" => Ok(Self::Fixed(Utc.fix())),
+            offset => offset
+                .parse()
+                .map(Self::Fixed)
+                .map_err(|_| format!("expected local, utc or an offset like +05:30, got '{s}'")),
+        }
+    }
+}
+
+impl TryFrom<String> for Zone {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Zone> for String {
+    fn from(zone: Zone) -> Self {
+        zone.to_string()
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Fixed(offset) => write!(f, "{offset}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn days_start_in_the_zone() {
+        let late = Utc.with_ymd_and_hms(2024, 5, 3, 23, 30, 0).unwrap();
+        let tokyo: Zone = "+09:00".parse().unwrap();
+        assert_eq!(
+            tokyo.date(late),
+            NaiveDate::from_ymd_opt(2024, 5, 4).unwrap()
+        );
+        let utc: Zone = "utc".parse().unwrap();
+        assert_eq!(utc.date(late), NaiveDate::from_ymd_opt(2024, 5, 3).unwrap());
+
+        assert_eq!(tokyo.to_string(), "+09:00");
+        assert_eq!("local".parse(), Ok(Zone::Local));
+        assert!("mars".parse::<Zone>().is_err());
+    }
+}